@@ -1,8 +1,20 @@
 // LNXDrive Application — adw::Application subclass
 //
 // On activation the app checks the daemon's authentication state over D-Bus
-// and shows the onboarding wizard or the preferences panel accordingly.
+// and shows the onboarding wizard or the preferences panel accordingly; a
+// `--page` given before sign-in is opened once onboarding completes. It then
+// follows AuthStateChanged for as long as the app runs: a sign-out on another
+// device returns the window to onboarding, an expired token offers inline
+// re-authentication.
+//
+// The app also follows the sync state for desktop notifications (see
+// `notifications`). When the user opts in with the `run-in-background`
+// setting, it holds itself alive once connected, and closing the window only
+// hides it, so the app keeps running in the background; `app.quit` (Ctrl+Q)
+// exits, asking first while a window reports a sync in progress.
 
+use futures_util::future::{AbortHandle, Abortable};
+use futures_util::StreamExt;
 use gettextrs::gettext;
 use gtk4::gio;
 use gtk4::glib;
@@ -11,18 +23,31 @@ use gtk4::subclass::prelude::ObjectSubclassIsExt;
 use libadwaita as adw;
 use libadwaita::prelude::*;
 
-use crate::dbus_client::DbusClient;
+use crate::dbus_client::{
+    DbusClient, DbusError, LnxdriveAuthProxy, LnxdriveConflictsProxy, LnxdriveSyncProxy,
+};
+use crate::notifications;
+use crate::settings::{keys, AppSettings, NotificationLevel};
 use crate::window::LnxdriveWindow;
 
 mod imp {
     use super::*;
-    use std::cell::OnceCell;
+    use std::cell::{OnceCell, RefCell};
     use gtk4::subclass::prelude::*;
     use libadwaita::subclass::prelude::*;
 
     #[derive(Default)]
     pub struct LnxdriveApp {
         pub initial_page: OnceCell<Option<String>>,
+        /// Long-lived AuthStateChanged subscription, aborted on shutdown.
+        pub auth_watch: RefCell<Option<AbortHandle>>,
+        /// SyncStatus/ConflictDetected subscription for notifications.
+        pub notification_watch: RefCell<Option<AbortHandle>>,
+        /// Kept for its change notifications on the `run-in-background` key.
+        pub settings: OnceCell<AppSettings>,
+        /// Held while `run-in-background` is on, so the app outlives its
+        /// window.
+        pub background_hold: RefCell<Option<gio::ApplicationHoldGuard>>,
     }
 
     #[glib::object_subclass]
@@ -35,6 +60,62 @@ mod imp {
     impl ObjectImpl for LnxdriveApp {}
 
     impl ApplicationImpl for LnxdriveApp {
+        fn handle_local_options(&self, options: &glib::VariantDict) -> glib::ExitCode {
+            // --status and --check are answered by the invoking process and
+            // never reach the primary instance, so they work even while a
+            // window is open and never create one.
+            if options.contains("json") && !options.contains("status") {
+                eprintln!("--json can only be used with --status");
+                return glib::ExitCode::FAILURE;
+            }
+            if options.contains("check") {
+                return crate::cli::print_check();
+            }
+            if options.contains("status") {
+                let json = options.contains("json");
+                return crate::cli::print_status(json);
+            }
+            self.parent_handle_local_options(options)
+        }
+
+        fn startup(&self) {
+            self.parent_startup();
+            apply_text_direction_override();
+            load_stylesheet();
+            let app = self.obj();
+            app.set_accels_for_action("win.refresh", &["F5"]);
+
+            let quit_action = gio::SimpleAction::new("quit", None);
+            let weak_app = app.downgrade();
+            quit_action.connect_activate(move |_, _| {
+                if let Some(app) = weak_app.upgrade() {
+                    app.quit_after_confirmation();
+                }
+            });
+            app.add_action(&quit_action);
+            app.set_accels_for_action("app.quit", &["<Control>q"]);
+
+            let settings = AppSettings::new();
+            let weak_app = app.downgrade();
+            settings.connect_changed(keys::RUN_IN_BACKGROUND, move || {
+                if let Some(app) = weak_app.upgrade() {
+                    app.update_background_hold();
+                }
+            });
+            let _ = self.settings.set(settings);
+        }
+
+        fn shutdown(&self) {
+            if let Some(handle) = self.auth_watch.borrow_mut().take() {
+                handle.abort();
+            }
+            if let Some(handle) = self.notification_watch.borrow_mut().take() {
+                handle.abort();
+            }
+            self.background_hold.replace(None);
+            self.parent_shutdown();
+        }
+
         fn activate(&self) {
             let app = self.obj();
             app.on_activate();
@@ -81,6 +162,30 @@ impl LnxdriveApp {
             "Navigate directly to a preferences page",
             Some("PAGE"),
         );
+        app.add_main_option(
+            "status",
+            glib::Char(0),
+            glib::OptionFlags::NONE,
+            glib::OptionArg::None,
+            "Print the daemon status and exit",
+            None,
+        );
+        app.add_main_option(
+            "check",
+            glib::Char(0),
+            glib::OptionFlags::NONE,
+            glib::OptionArg::None,
+            "Check that the daemon is reachable over D-Bus and exit",
+            None,
+        );
+        app.add_main_option(
+            "json",
+            glib::Char(0),
+            glib::OptionFlags::NONE,
+            glib::OptionArg::None,
+            "Use JSON output for --status",
+            None,
+        );
 
         app
     }
@@ -102,21 +207,28 @@ impl LnxdriveApp {
         let window = LnxdriveWindow::new(self);
 
         // Attempt D-Bus connection and auth check asynchronously.
+        let app = self.clone();
         let win = window.clone();
         glib::MainContext::default().spawn_local(async move {
-            match DbusClient::new().await {
-                Ok(client) => match client.is_authenticated().await {
-                    Ok(true) => win.show_preferences(
-                        &client,
-                        initial_page.as_deref(),
-                    ),
-                    Ok(false) => win.show_onboarding(client),
-                    Err(e) => win.show_dbus_error(&format!(
-                        "{}: {}",
-                        gettext("Could not query authentication state"),
-                        e
-                    )),
-                },
+            match connect_daemon().await {
+                Ok(client) => {
+                    app.watch_auth_state(&client, &win);
+                    app.watch_notifications(&client);
+                    app.update_background_hold();
+                    match client.is_authenticated().await {
+                        Ok(true) => win.show_preferences(
+                            &client,
+                            initial_page.as_deref(),
+                        ),
+                        // The page is opened after sign-in instead.
+                        Ok(false) => win.show_onboarding(client, initial_page.as_deref()),
+                        Err(e) => win.show_dbus_error(&format!(
+                            "{}: {}",
+                            gettext("Could not query authentication state"),
+                            e
+                        )),
+                    }
+                }
                 Err(e) => win.show_dbus_error(&format!(
                     "{}: {}",
                     gettext("Could not connect to LNXDrive daemon"),
@@ -127,6 +239,224 @@ impl LnxdriveApp {
 
         window.present();
     }
+
+    /// Subscribe to AuthStateChanged and let the window react whenever the
+    /// daemon reports that the account is no longer signed in.
+    fn watch_auth_state(&self, client: &DbusClient, window: &LnxdriveWindow) {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        if let Some(previous) = self.imp().auth_watch.replace(Some(abort_handle)) {
+            previous.abort();
+        }
+
+        let client = client.clone();
+        let win = window.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let _ = Abortable::new(async move {
+                let connection = client.connection().clone();
+                let proxy = match LnxdriveAuthProxy::new(&connection).await {
+                    Ok(p) => p,
+                    Err(e) => {
+                        eprintln!("Could not create auth proxy for signals: {e}");
+                        return;
+                    }
+                };
+
+                let mut stream = match proxy.receive_auth_state_changed().await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("Could not subscribe to AuthStateChanged: {e}");
+                        return;
+                    }
+                };
+
+                while let Some(signal) = stream.next().await {
+                    if let Ok(args) = signal.args() {
+                        match args.state {
+                            "unauthenticated" | "disconnected" => match win.upgrade() {
+                                Some(win) => win.on_signed_out(&client),
+                                None => break,
+                            },
+                            "error" | "reauth_required" => match win.upgrade() {
+                                Some(win) => win.on_session_expired(&client),
+                                None => break,
+                            },
+                            _ => {}
+                        }
+                    }
+                }
+            }, abort_registration).await;
+        });
+    }
+
+    fn notification_level(&self) -> NotificationLevel {
+        self.imp()
+            .settings
+            .get()
+            .map_or(NotificationLevel::None, AppSettings::notification_level)
+    }
+
+    /// Whether the app keeps running in the background, so closing the
+    /// window should hide it rather than end it.
+    pub fn runs_in_background(&self) -> bool {
+        self.imp().background_hold.borrow().is_some()
+    }
+
+    /// Quit, but first ask on the window showing a sync in progress, if any.
+    fn quit_after_confirmation(&self) {
+        let syncing_window = self
+            .windows()
+            .into_iter()
+            .filter_map(|window| window.downcast::<LnxdriveWindow>().ok())
+            .find(LnxdriveWindow::is_syncing);
+
+        match syncing_window {
+            Some(win) => {
+                win.present();
+                let app = self.downgrade();
+                win.confirm_quit_during_sync(move || {
+                    if let Some(app) = app.upgrade() {
+                        app.quit();
+                    }
+                });
+            }
+            None => self.quit(),
+        }
+    }
+
+    /// Hold the app while `run-in-background` is on and release it otherwise,
+    /// so it keeps running (or not) once the window is closed. First called
+    /// once the daemon is connected, since there is nothing to report
+    /// without it, then whenever the setting changes.
+    fn update_background_hold(&self) {
+        let imp = self.imp();
+        let wanted = self
+            .imp()
+            .settings
+            .get()
+            .is_some_and(AppSettings::run_in_background);
+        if wanted != imp.background_hold.borrow().is_some() {
+            imp.background_hold.replace(wanted.then(|| self.hold()));
+        }
+    }
+
+    /// Follow SyncStatus and ConflictDetected and send a notification for
+    /// each transition the `notifications` setting asks for.
+    fn watch_notifications(&self, client: &DbusClient) {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        if let Some(previous) = self.imp().notification_watch.replace(Some(abort_handle)) {
+            previous.abort();
+        }
+
+        let client = client.clone();
+        let app = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let _ = Abortable::new(async move {
+                let connection = client.connection().clone();
+                let (sync_proxy, conflicts_proxy) = match (
+                    LnxdriveSyncProxy::new(&connection).await,
+                    LnxdriveConflictsProxy::new(&connection).await,
+                ) {
+                    (Ok(s), Ok(c)) => (s, c),
+                    (Err(e), _) | (_, Err(e)) => {
+                        eprintln!("Could not create proxies for notifications: {e}");
+                        return;
+                    }
+                };
+
+                let status_changes = sync_proxy.receive_sync_status_changed().await;
+                let detected = match conflicts_proxy.receive_conflict_detected().await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("Could not subscribe to ConflictDetected: {e}");
+                        return;
+                    }
+                };
+
+                // `None` for a status change (re-read below), the conflict
+                // JSON for a new conflict.
+                let mut events = futures_util::stream::select(
+                    status_changes.map(|_| None),
+                    detected.map(|signal| {
+                        signal.args().ok().map(|args| args.conflict_json.to_string())
+                    }),
+                );
+
+                let mut status = client.get_sync_status().await.unwrap_or_default();
+                while let Some(event) = events.next().await {
+                    let Some(app) = app.upgrade() else { break };
+                    let level = app.notification_level();
+
+                    let notification = match event {
+                        Some(conflict_json) => notifications::for_conflict(&conflict_json, level),
+                        None => {
+                            let Ok(current) = client.get_sync_status().await else {
+                                continue;
+                            };
+                            let error = match current.as_str() {
+                                "error" => client.get_last_error().await.ok().flatten(),
+                                _ => None,
+                            };
+                            let message = error.as_ref().map(|e| e.message.as_str());
+                            let notification =
+                                notifications::for_status_change(&status, &current, message, level);
+                            status = current;
+                            notification
+                        }
+                    };
+
+                    if let Some((id, notification)) = notification {
+                        app.send_notification(Some(id), &notification);
+                    }
+                }
+            }, abort_registration).await;
+        });
+    }
+}
+
+/// Connect to the daemon on the bus selected by `LNXDRIVE_DBUS_ADDRESS`:
+/// unset or "session" for the session bus (the default), "system" for the
+/// system bus, or any other value as a D-Bus address.
+pub async fn connect_daemon() -> Result<DbusClient, DbusError> {
+    match std::env::var("LNXDRIVE_DBUS_ADDRESS").as_deref() {
+        Err(_) | Ok("") | Ok("session") => DbusClient::new().await,
+        Ok("system") => {
+            let connection = zbus::Connection::system().await?;
+            Ok(DbusClient::from_connection(connection))
+        }
+        Ok(address) => DbusClient::from_address(address).await,
+    }
+}
+
+/// App-wide style rules. `compact` on a list view is the "Compact Lists"
+/// density from the Advanced page.
+const STYLESHEET: &str = "
+.conflict-row { padding: 8px 12px; }
+listview.compact > row { min-height: 0; padding-top: 0; padding-bottom: 0; }
+listview.compact .conflict-row { padding-top: 2px; padding-bottom: 2px; }
+";
+
+fn load_stylesheet() {
+    let Some(display) = gtk4::gdk::Display::default() else {
+        return;
+    };
+    let provider = gtk4::CssProvider::new();
+    provider.load_from_string(STYLESHEET);
+    gtk4::style_context_add_provider_for_display(
+        &display,
+        &provider,
+        gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
+    );
+}
+
+/// Honour `LNXDRIVE_TEXT_DIRECTION=rtl|ltr` to force the layout direction,
+/// so right-to-left rendering can be checked without switching locales.
+fn apply_text_direction_override() {
+    let direction = match std::env::var("LNXDRIVE_TEXT_DIRECTION").as_deref() {
+        Ok("rtl") => gtk4::TextDirection::Rtl,
+        Ok("ltr") => gtk4::TextDirection::Ltr,
+        _ => return,
+    };
+    gtk4::Widget::set_default_direction(direction);
 }
 
 impl Default for LnxdriveApp {
@@ -0,0 +1,110 @@
+// Auth Flow — the interactive sign-in sequence shared by onboarding and
+// inline re-authentication
+//
+// Calls StartAuth(), opens the returned URL in the default browser and waits
+// for the daemon's AuthStateChanged signal. Callers own all UI: AuthPage
+// pushes the next wizard page on success, the main window hides its
+// "session expired" banner and reopens preferences. Both are handed the
+// auth URL, so they can offer it again or for copying while the flow waits.
+
+use std::fmt;
+
+use futures_util::StreamExt;
+use gettextrs::gettext;
+use gtk4::glib;
+
+use crate::dbus_client::{DbusClient, DbusError, LnxdriveAuthProxy};
+
+/// Why a sign-in attempt did not complete.
+#[derive(Debug)]
+pub enum AuthFlowError {
+    /// StartAuth() failed.
+    Start(DbusError),
+    /// Could not subscribe to AuthStateChanged, or the stream ended.
+    Listen(String),
+    /// The daemon reported an "error" auth state.
+    Rejected,
+}
+
+impl fmt::Display for AuthFlowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthFlowError::Start(e) => {
+                write!(f, "{}: {}", gettext("Could not start authentication"), e)
+            }
+            AuthFlowError::Listen(e) => {
+                write!(f, "{}: {}", gettext("Could not listen for auth events"), e)
+            }
+            AuthFlowError::Rejected => {
+                write!(f, "{}", gettext("Authentication failed. Please try again."))
+            }
+        }
+    }
+}
+
+/// The organisation or email domain the auth URL signs in to, from its
+/// `domain_hint` or `login_hint` parameter, if the daemon set one.
+pub fn auth_domain(auth_url: &str) -> Option<String> {
+    let uri = glib::Uri::parse(auth_url, glib::UriFlags::NONE).ok()?;
+    let query = uri.query()?;
+    let hint = |key: &str| {
+        query
+            .split('&')
+            .filter_map(|param| param.split_once('='))
+            .find(|(name, _)| *name == key)
+            .and_then(|(_, value)| glib::Uri::unescape_string(value, None))
+            .map(String::from)
+            .filter(|value| !value.is_empty())
+    };
+    hint("domain_hint")
+        .or_else(|| hint("login_hint")?.rsplit_once('@').map(|(_, domain)| domain.to_string()))
+}
+
+/// Run the browser sign-in flow and resolve once the daemon reports
+/// "authenticated". `on_auth_url` receives the auth URL and whether the
+/// browser opened it; if it did not, the caller can offer the link for
+/// copying. The flow keeps waiting for the signal either way.
+pub async fn sign_in(
+    client: &DbusClient,
+    parent: Option<&gtk4::Window>,
+    on_auth_url: impl Fn(&str, bool),
+) -> Result<(), AuthFlowError> {
+    // Subscribe before opening the browser so a fast redirect can't emit
+    // the signal before we are listening. Clone the connection so the
+    // proxy doesn't borrow the client.
+    let connection = client.connection().clone();
+    let proxy = LnxdriveAuthProxy::new(&connection)
+        .await
+        .map_err(|e| AuthFlowError::Listen(e.to_string()))?;
+    let mut stream = proxy
+        .receive_auth_state_changed()
+        .await
+        .map_err(|e| AuthFlowError::Listen(e.to_string()))?;
+
+    let (auth_url, _state) = client.start_auth().await.map_err(AuthFlowError::Start)?;
+
+    let launcher = gtk4::UriLauncher::new(&auth_url);
+    match launcher.launch_future(parent).await {
+        Ok(()) => on_auth_url(&auth_url, true),
+        Err(e) => {
+            // Don't give up: let the user open the link elsewhere and keep
+            // listening for the signal.
+            eprintln!("Could not open browser: {}", e);
+            on_auth_url(&auth_url, false);
+        }
+    }
+
+    while let Some(signal) = stream.next().await {
+        if let Ok(args) = signal.args() {
+            match args.state {
+                "authenticated" => return Ok(()),
+                "error" => return Err(AuthFlowError::Rejected),
+                _ => {
+                    // Other transient states; keep waiting.
+                }
+            }
+        }
+    }
+
+    Err(AuthFlowError::Listen(gettext("The daemon stopped sending auth events")))
+}
@@ -0,0 +1,93 @@
+// Starting the LNXDrive daemon at login
+//
+// Where the daemon is installed as the `lnxdrive.service` systemd user unit,
+// "Enable Sync at Login" enables or disables that unit. Without the unit it
+// writes ~/.config/autostart/lnxdrive.desktop, which every XDG-compliant
+// session starts at login; disabling removes the file. An entry the user
+// disabled by hand (Hidden=true or X-GNOME-Autostart-enabled=false) counts as
+// disabled. Both checks go through `systemctl`, so these functions are async.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use gtk4::gio;
+use gtk4::glib;
+
+use crate::service;
+
+/// File name of the autostart entry.
+const DESKTOP_FILE: &str = "lnxdrive.desktop";
+
+const DESKTOP_ENTRY: &str = "[Desktop Entry]
+Type=Application
+Name=LNXDrive
+Comment=Sync OneDrive files
+Exec=lnxdrive-daemon
+Icon=com.enigmora.LNXDrive
+NoDisplay=true
+Terminal=false
+X-GNOME-Autostart-enabled=true
+";
+
+fn entry_path() -> PathBuf {
+    glib::user_config_dir().join("autostart").join(DESKTOP_FILE)
+}
+
+/// Whether the systemd user unit is installed.
+async fn has_unit() -> bool {
+    service::active_state().await.is_some()
+}
+
+/// Whether the daemon is set to start at login.
+pub async fn is_enabled() -> bool {
+    if has_unit().await {
+        return service::is_enabled().await;
+    }
+    match fs::read_to_string(entry_path()) {
+        Ok(contents) => !contents.lines().map(str::trim).any(|line| {
+            line == "Hidden=true" || line == "X-GNOME-Autostart-enabled=false"
+        }),
+        Err(_) => false,
+    }
+}
+
+/// Enable or disable the systemd user unit if it is installed, otherwise
+/// create or remove the autostart entry, creating the autostart directory
+/// if needed.
+pub async fn set_enabled(enabled: bool) -> Result<(), glib::Error> {
+    if has_unit().await {
+        // An entry left from before the unit was installed would start a
+        // second daemon.
+        remove_entry().map_err(io_error)?;
+        return if enabled {
+            service::enable().await
+        } else {
+            service::disable().await
+        };
+    }
+    if enabled {
+        write_entry().map_err(io_error)
+    } else {
+        remove_entry().map_err(io_error)
+    }
+}
+
+fn write_entry() -> io::Result<()> {
+    let path = entry_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, DESKTOP_ENTRY)
+}
+
+fn remove_entry() -> io::Result<()> {
+    match fs::remove_file(entry_path()) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn io_error(e: io::Error) -> glib::Error {
+    glib::Error::new(gio::IOErrorEnum::Failed, &e.to_string())
+}
@@ -0,0 +1,132 @@
+// Command-line helpers — headless queries that run without a window
+//
+// `--status` connects to the daemon, prints a one-shot snapshot of its state
+// and exits. The default output is one `key=value` pair per line so shell
+// scripts can grep or `eval` it; `--json` switches to a single JSON object.
+//
+// `--check` only verifies that the daemon is reachable, for packaging smoke
+// tests and health checks: it prints "OK", or "FAIL: " and the error.
+//
+// Exit codes:
+//   0 — the daemon answered (individual fields may still be "unknown")
+//   1 — the daemon is unreachable over D-Bus
+
+use gtk4::glib;
+
+use crate::conflicts::conflict_dialog::ConflictInfo;
+
+/// Snapshot of the daemon state printed by `--status`. Optional fields are
+/// `None` when the corresponding D-Bus call failed.
+struct StatusReport {
+    authenticated: bool,
+    quota: Option<(u64, u64)>,
+    sync_status: Option<String>,
+    conflicts: Option<usize>,
+}
+
+impl StatusReport {
+    /// Query every field from the daemon. Fails only if the daemon does not
+    /// answer the initial authentication query.
+    async fn fetch() -> Result<Self, String> {
+        let client = crate::app::connect_daemon()
+            .await
+            .map_err(|e| e.to_string())?;
+        let authenticated = client.is_authenticated().await.map_err(|e| e.to_string())?;
+
+        let quota = client.get_quota().await.ok();
+        let sync_status = client.get_sync_status().await.ok();
+        let conflicts = client
+            .list_conflicts()
+            .await
+            .ok()
+            .map(|json| ConflictInfo::from_json_array(&json).len());
+
+        Ok(Self {
+            authenticated,
+            quota,
+            sync_status,
+            conflicts,
+        })
+    }
+
+    fn to_key_value(&self) -> String {
+        fn or_unknown<T: ToString>(value: Option<T>) -> String {
+            value
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        }
+
+        format!(
+            "authenticated={}\nquota_used={}\nquota_total={}\nsync_status={}\nconflicts={}\n",
+            self.authenticated,
+            or_unknown(self.quota.map(|(used, _)| used)),
+            or_unknown(self.quota.map(|(_, total)| total)),
+            or_unknown(self.sync_status.as_deref()),
+            or_unknown(self.conflicts),
+        )
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::json!({
+            "authenticated": self.authenticated,
+            "quota_used": self.quota.map(|(used, _)| used),
+            "quota_total": self.quota.map(|(_, total)| total),
+            "sync_status": self.sync_status,
+            "conflicts": self.conflicts,
+        })
+        .to_string()
+    }
+}
+
+/// Print the daemon status to stdout and return the process exit code.
+/// Blocks on the default MainContext, so it must be called before the GTK
+/// main loop starts (i.e. from `handle_local_options`).
+pub fn print_status(json: bool) -> glib::ExitCode {
+    match glib::MainContext::default().block_on(StatusReport::fetch()) {
+        Ok(report) => {
+            if json {
+                println!("{}", report.to_json());
+            } else {
+                print!("{}", report.to_key_value());
+            }
+            glib::ExitCode::SUCCESS
+        }
+        Err(e) => {
+            if json {
+                println!("{}", serde_json::json!({ "error": e }));
+            }
+            eprintln!("Could not reach LNXDrive daemon: {e}");
+            glib::ExitCode::FAILURE
+        }
+    }
+}
+
+/// Connect to the daemon, confirm it owns its bus name and answers a call.
+async fn check_daemon() -> Result<(), String> {
+    let client = crate::app::connect_daemon()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !client.daemon_running().await.map_err(|e| e.to_string())? {
+        return Err(format!("{} is not running on the bus", crate::dbus_client::BUS_NAME));
+    }
+    client
+        .is_authenticated()
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Print whether the daemon is reachable and return the process exit code.
+/// Like `print_status`, must be called before the GTK main loop starts.
+pub fn print_check() -> glib::ExitCode {
+    match glib::MainContext::default().block_on(check_daemon()) {
+        Ok(()) => {
+            println!("OK");
+            glib::ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("FAIL: {e}");
+            glib::ExitCode::FAILURE
+        }
+    }
+}
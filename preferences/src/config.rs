@@ -0,0 +1,403 @@
+// Config YAML helpers — text-level operations on the daemon's config
+//
+// The daemon owns the config schema, so the app never maps the YAML it gets
+// from GetConfig() onto typed structs; pages scan the lines they care about.
+// This module holds the helpers that must treat the whole document: masking
+// secrets before the config is shown or copied, checking that hand-edited
+// YAML parses before it is sent back with SetConfig(), and reading top-level
+// lists, which may be written in flow or block style.
+//
+// Redaction parses the config and walks it: every scalar under a sensitive
+// key is replaced, however deeply nested and whether it was written in block
+// or flow style (`auth: {access_token: abc}`), and the document is written
+// back with its keys and shape intact (comments are dropped). A config that
+// doesn't parse, such as one being edited, is masked line by line instead,
+// following indentation for blocks and masking the rest of any line after a
+// sensitive key. The config editor shows the line-by-line redaction even
+// for a valid config, so its buffer is the original text with only secret
+// values replaced, and `restore_redacted` splices the original lines back in
+// for the placeholders the user left alone before the edit is saved. Daemon
+// log lines are redacted the same way, one at a time: everything after a
+// sensitive `key:` or `key=` on the line is masked.
+
+use gettextrs::gettext;
+use serde_yaml_ng::Value;
+
+/// Placeholder written in place of a redacted value.
+const REDACTED_VALUE: &str = "<redacted>";
+
+/// The placeholder quoted, for line-based redaction.
+const REDACTED: &str = "\"<redacted>\"";
+
+/// Key fragments whose values are never shown. Matched case-insensitively
+/// anywhere in the key, so `refresh_token`, `clientSecret` and
+/// `proxy_password` are all caught.
+const SENSITIVE_KEY_PARTS: &[&str] = &["token", "secret", "password"];
+
+/// Check that `yaml` parses and is a mapping at the top level. The error
+/// names the line and column of the first problem.
+pub fn validate_config(yaml: &str) -> Result<(), String> {
+    match serde_yaml_ng::from_str::<serde_yaml_ng::Value>(yaml) {
+        Ok(serde_yaml_ng::Value::Mapping(_)) => Ok(()),
+        Ok(serde_yaml_ng::Value::Null) => Err(gettext("The configuration is empty")),
+        Ok(_) => Err(gettext(
+            "The configuration must be a list of \"key: value\" settings",
+        )),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Value of the top-level scalar `key`, unquoted, e.g. the `sync_root`.
+pub fn top_level_value(yaml: &str, key: &str) -> Option<String> {
+    yaml.lines()
+        .filter(|line| !line.starts_with(' ') && !line.starts_with('\t'))
+        .filter_map(|line| split_key(line.trim_end()))
+        .find(|(k, _)| *k == key)
+        .map(|(_, value)| value.trim_matches(|c| c == '"' || c == '\'').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Items of the top-level list `key`, e.g. `[wifi, ethernet]`. `None` if the
+/// config does not parse or `key` is missing or not a list.
+pub fn top_level_list(yaml: &str, key: &str) -> Option<Vec<String>> {
+    let document: serde_yaml_ng::Value = serde_yaml_ng::from_str(yaml).ok()?;
+    let items = document.get(key)?.as_sequence()?;
+    Some(
+        items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+    )
+}
+
+/// A one-key partial config setting `key` to the string `value`, quoted so
+/// paths with spaces or `#` survive, for SetConfig().
+pub fn string_setting(key: &str, value: &str) -> String {
+    format!(
+        "{key}: \"{}\"\n",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Return `yaml` with the values of sensitive keys masked.
+pub fn redact_config(yaml: &str) -> String {
+    if yaml.trim().is_empty() {
+        return yaml.to_string();
+    }
+    let Ok(mut document) = serde_yaml_ng::from_str::<Value>(yaml) else {
+        return redact_config_lines(yaml);
+    };
+    redact_value(&mut document, false);
+    serde_yaml_ng::to_string(&document).unwrap_or_else(|_| redact_config_lines(yaml))
+}
+
+/// Undo `redact_config_lines` for a redacted config the user has edited:
+/// every line of `edited` that still holds a placeholder and matches a
+/// masked line exactly gets the line at that place in `original` back, so
+/// saving doesn't overwrite secrets with the placeholder. Identical masked
+/// lines, such as the items of a sensitive list, are taken in order. Every
+/// other line is kept as the user wrote it. Fails if a placeholder line has
+/// no original, e.g. because its key was renamed.
+pub fn restore_redacted(edited: &str, original: &str) -> Result<String, String> {
+    let redacted = redact_config_lines(original);
+    let mut masked: Vec<(&str, &str)> = redacted
+        .lines()
+        .zip(original.lines())
+        .filter(|(redacted, original)| redacted != original)
+        .collect();
+
+    let mut restored: Vec<&str> = Vec::new();
+    for line in edited.lines() {
+        if !line.contains(REDACTED_VALUE) {
+            restored.push(line);
+            continue;
+        }
+        match masked.iter().position(|(redacted, _)| *redacted == line) {
+            Some(i) => restored.push(masked.remove(i).1),
+            // A placeholder that was in the config to begin with.
+            None if original.lines().any(|original| original == line) => restored.push(line),
+            None => {
+                return Err(gettext(
+                    "Replace every \"<redacted>\" value that has no saved value before saving",
+                ))
+            }
+        }
+    }
+
+    let mut text = restored.join("\n");
+    if edited.ends_with('\n') {
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Mask the values of sensitive keys anywhere in `value`. With `masked`,
+/// `value` is itself under a sensitive key and every scalar in it is masked.
+fn redact_value(value: &mut Value, masked: bool) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, child) in mapping.iter_mut() {
+                let sensitive = key.as_str().is_some_and(is_sensitive_key);
+                redact_value(child, masked || sensitive);
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                redact_value(item, masked);
+            }
+        }
+        Value::Tagged(tagged) => redact_value(&mut tagged.value, masked),
+        // Nothing to hide in an empty value.
+        Value::Null => {}
+        _ if masked => *value = Value::String(REDACTED_VALUE.to_string()),
+        _ => {}
+    }
+}
+
+/// Line-based `redact_config`, for YAML that doesn't parse and for the
+/// config editor. Returns exactly one line for each line of `yaml`, with
+/// comments and layout untouched.
+pub fn redact_config_lines(yaml: &str) -> String {
+    // Indentation of the sensitive key whose block we are inside, if any.
+    let mut redact_below: Option<usize> = None;
+
+    let mut out: Vec<String> = Vec::new();
+    for line in yaml.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            out.push(line.to_string());
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+
+        if let Some(block_indent) = redact_below {
+            // YAML allows a block list at the same indentation as its key.
+            if indent > block_indent || (indent == block_indent && trimmed.starts_with('-')) {
+                out.push(mask_line(line));
+                continue;
+            }
+            redact_below = None;
+        }
+
+        let (prefix, rest) = split_list_markers(line);
+        match split_key(rest) {
+            Some((key, value)) if is_sensitive_key(key) => {
+                if value.is_empty() || value.starts_with('|') || value.starts_with('>') {
+                    // Block follows; the key line itself carries no secret.
+                    redact_below = Some(indent);
+                    out.push(line.to_string());
+                } else {
+                    out.push(format!("{prefix}{key}: {REDACTED}"));
+                }
+            }
+            // Catches flow mappings such as `proxy: {password: x`.
+            _ => out.push(redact_log_line(line)),
+        }
+    }
+
+    let mut redacted = out.join("\n");
+    if yaml.ends_with('\n') {
+        redacted.push('\n');
+    }
+    redacted
+}
+
+/// Return a daemon log line with the rest of the line after a sensitive
+/// `key:` or `key=` masked, e.g. `auth: refresh_token: abc` becomes
+/// `auth: refresh_token: "<redacted>"`.
+pub fn redact_log_line(line: &str) -> String {
+    let mut offset = 0;
+    for word in line.split_inclusive(char::is_whitespace) {
+        let token = word.trim_end();
+        if let Some(key) = token.strip_suffix(':') {
+            if is_sensitive_key(key) {
+                return format!("{}{token} {REDACTED}", &line[..offset]);
+            }
+        } else if let Some((key, _)) = token.split_once('=') {
+            if is_sensitive_key(key) {
+                return format!("{}{key}={REDACTED}", &line[..offset]);
+            }
+        }
+        offset += word.len();
+    }
+    line.to_string()
+}
+
+/// Mask the scalar on a line inside a sensitive block, keeping any key and
+/// list markers.
+fn mask_line(line: &str) -> String {
+    let (prefix, rest) = split_list_markers(line);
+    match split_key(rest) {
+        // A nested key that opens its own block: keep it, its children are
+        // masked in turn.
+        Some((_, "")) => line.to_string(),
+        Some((key, _)) => format!("{prefix}{key}: {REDACTED}"),
+        None => format!("{prefix}{REDACTED}"),
+    }
+}
+
+/// Split a line into its leading indentation plus `- ` list markers and the
+/// remainder.
+fn split_list_markers(line: &str) -> (&str, &str) {
+    let mut rest = line.trim_start();
+    loop {
+        match rest.strip_prefix('-') {
+            Some(after) if after.is_empty() || after.starts_with(' ') => {
+                rest = after.trim_start();
+            }
+            _ => break,
+        }
+    }
+    let prefix_len = line.len() - rest.len();
+    (&line[..prefix_len], rest)
+}
+
+/// Split `key: value` (or a bare `key:`) into key and trimmed value. Returns
+/// `None` for plain scalars such as list items or URLs.
+fn split_key(rest: &str) -> Option<(&str, &str)> {
+    if let Some(idx) = rest.find(": ") {
+        Some((&rest[..idx], rest[idx + 2..].trim()))
+    } else {
+        rest.strip_suffix(':').map(|key| (key, ""))
+    }
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.trim_matches(|c| c == '"' || c == '\'').to_lowercase();
+    SENSITIVE_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> Value {
+        serde_yaml_ng::from_str(yaml).expect("redacted config should parse")
+    }
+
+    #[test]
+    fn redacts_nested_keys() {
+        let yaml = concat!(
+            "sync_root: /home/user/OneDrive\n",
+            "auth:\n",
+            "  refresh_token: abc123\n",
+            "  client:\n",
+            "    clientSecret: xyz789\n",
+            "    id: app\n",
+        );
+        let redacted = redact_config(yaml);
+        assert!(!redacted.contains("abc123"));
+        assert!(!redacted.contains("xyz789"));
+
+        let document = parse(&redacted);
+        assert_eq!(document["sync_root"], "/home/user/OneDrive");
+        assert_eq!(document["auth"]["refresh_token"], REDACTED_VALUE);
+        assert_eq!(document["auth"]["client"]["clientSecret"], REDACTED_VALUE);
+        assert_eq!(document["auth"]["client"]["id"], "app");
+    }
+
+    #[test]
+    fn redacts_every_item_of_a_sensitive_list() {
+        let yaml = "api_tokens:\n  - first-token\n  - second-token\nexclude:\n  - \"*.tmp\"\n";
+        let redacted = redact_config(yaml);
+        assert!(!redacted.contains("first-token"));
+        assert!(!redacted.contains("second-token"));
+
+        let document = parse(&redacted);
+        assert_eq!(document["api_tokens"][0], REDACTED_VALUE);
+        assert_eq!(document["api_tokens"][1], REDACTED_VALUE);
+        assert_eq!(document["exclude"][0], "*.tmp");
+    }
+
+    #[test]
+    fn redacts_sensitive_keys_inside_list_items() {
+        let yaml = "accounts:\n  - name: work\n    password: hunter2\n";
+        let redacted = redact_config(yaml);
+        assert!(!redacted.contains("hunter2"));
+
+        let document = parse(&redacted);
+        assert_eq!(document["accounts"][0]["name"], "work");
+        assert_eq!(document["accounts"][0]["password"], REDACTED_VALUE);
+    }
+
+    #[test]
+    fn redacts_flow_mappings_and_sequences() {
+        let yaml = concat!(
+            "auth: {access_token: abc123, user: me}\n",
+            "clients: [{client_secret: xyz789}, {id: app}]\n",
+        );
+        let redacted = redact_config(yaml);
+        assert!(!redacted.contains("abc123"));
+        assert!(!redacted.contains("xyz789"));
+
+        let document = parse(&redacted);
+        assert_eq!(document["auth"]["access_token"], REDACTED_VALUE);
+        assert_eq!(document["auth"]["user"], "me");
+        assert_eq!(document["clients"][0]["client_secret"], REDACTED_VALUE);
+        assert_eq!(document["clients"][1]["id"], "app");
+    }
+
+    #[test]
+    fn redacts_unparseable_config_line_by_line() {
+        let yaml = "auth:\n  refresh_token: abc123\n bad: [\nproxy: {password: hunter2\n";
+        assert!(serde_yaml_ng::from_str::<Value>(yaml).is_err());
+
+        let redacted = redact_config(yaml);
+        assert!(!redacted.contains("abc123"));
+        assert!(!redacted.contains("hunter2"));
+        assert!(redacted.contains("proxy:"));
+    }
+
+    #[test]
+    fn restores_untouched_placeholders() {
+        let original = concat!(
+            "sync_root: /home/user/OneDrive\n",
+            "auth:\n",
+            "  refresh_token: abc123\n",
+            "  client_secret: xyz789\n",
+            "api_tokens:\n  - first-token\n  - second-token\n",
+        );
+        let edited = redact_config_lines(original)
+            .replace("/home/user/OneDrive", "/home/user/Cloud")
+            .replace("client_secret: \"<redacted>\"", "client_secret: new-secret");
+
+        let document = parse(&restore_redacted(&edited, original).unwrap());
+        assert_eq!(document["sync_root"], "/home/user/Cloud");
+        assert_eq!(document["auth"]["refresh_token"], "abc123");
+        assert_eq!(document["auth"]["client_secret"], "new-secret");
+        assert_eq!(document["api_tokens"][0], "first-token");
+        assert_eq!(document["api_tokens"][1], "second-token");
+    }
+
+    #[test]
+    fn keeps_comments_through_an_unedited_save() {
+        let original = concat!(
+            "# LNXDrive configuration\n",
+            "sync_root: /home/user/OneDrive  # where files go\n",
+            "\n",
+            "auth:\n",
+            "  # issued at sign-in\n",
+            "  refresh_token: abc123\n",
+            "proxy: {url: http://proxy:3128, password: hunter2}\n",
+        );
+        let redacted = redact_config_lines(original);
+        assert!(!redacted.contains("abc123"));
+        assert!(!redacted.contains("hunter2"));
+        assert!(redacted.contains("# issued at sign-in"));
+
+        assert_eq!(restore_redacted(&redacted, original).unwrap(), original);
+    }
+
+    #[test]
+    fn refuses_placeholders_without_an_original_value() {
+        let original = "auth:\n  refresh_token: abc123\n";
+        let edited = "auth:\n  renamed_token: \"<redacted>\"\n";
+        assert!(restore_redacted(edited, original).is_err());
+    }
+
+    #[test]
+    fn leaves_edits_without_placeholders_as_they_are() {
+        let edited = "# comment\nsync_root: /home/user/OneDrive\n";
+        assert_eq!(restore_redacted(edited, "").unwrap(), edited);
+    }
+}
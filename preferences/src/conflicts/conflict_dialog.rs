@@ -1,12 +1,19 @@
 // ConflictDetailDialog — adw::Dialog subclass
 //
-// Shows side-by-side details for a single conflict (local vs remote version)
-// and lets the user choose a resolution strategy. Optionally allows creating
+// Shows side-by-side details for a single conflict (local vs remote version),
+// with a one-line summary of how they differ, and lets the user choose a
+// resolution strategy. The version `ConflictInfo::recommended_side` picks is
+// badged "Recommended" as a hint only; nothing is preselected, and the hint
+// can be turned off on the Sync page. Optionally allows creating
 // a persistent rule for the file type ("Remember for this file type").
+// "Copy Details" puts the raw conflict data on the clipboard for bug reports.
+// After "Keep Both" a toast on the preferences dialog names the renamed
+// local copy and can show it in Files.
 
 use std::cell::RefCell;
 
-use gettextrs::gettext;
+use gettextrs::{gettext, ngettext};
+use gtk4::gio;
 use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
@@ -14,11 +21,25 @@ use libadwaita as adw;
 use libadwaita::prelude::*;
 
 use crate::dbus_client::DbusClient;
+use crate::settings::AppSettings;
+use crate::util::format_size;
+
+use super::conflict_object::ConflictObject;
 
 // ---------------------------------------------------------------------------
 // ConflictInfo — deserialized from daemon JSON
 // ---------------------------------------------------------------------------
 
+/// One of the two versions of a conflicting file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionSide {
+    Local,
+    Remote,
+}
+
+/// Modification times closer than this count as the same.
+const SAME_TIME_SECS: i64 = 60;
+
 /// Lightweight struct holding the data needed to display a conflict.
 #[derive(Debug, Clone)]
 pub struct ConflictInfo {
@@ -32,6 +53,9 @@ pub struct ConflictInfo {
     pub remote_hash: String,
     pub remote_size: u64,
     pub remote_modified: String,
+    /// Who last changed the remote version, for files in shared folders.
+    /// Empty when the daemon does not know.
+    pub remote_modified_by: String,
 }
 
 impl ConflictInfo {
@@ -80,6 +104,12 @@ impl ConflictInfo {
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string(),
+            remote_modified_by: val
+                .get("remote_version")
+                .and_then(|v| v.get("modified_by"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
         })
     }
 
@@ -99,30 +129,181 @@ impl ConflictInfo {
             .unwrap_or(&self.item_path)
     }
 
+    /// Parse `detected_at` (ISO 8601) into a DateTime, if valid.
+    pub fn detected_datetime(&self) -> Option<glib::DateTime> {
+        glib::DateTime::from_iso8601(&self.detected_at, Some(&glib::TimeZone::utc())).ok()
+    }
+
+    /// Human-friendly detection time, e.g. "detected 3 hours ago".
+    /// Falls back to the raw timestamp if it cannot be parsed.
+    pub fn detected_relative(&self) -> String {
+        let Some(detected) = self.detected_datetime() else {
+            return self.detected_at.clone();
+        };
+        let Ok(now) = glib::DateTime::now_utc() else {
+            return self.detected_at.clone();
+        };
+
+        // Literal msgids in every arm so xgettext can extract them.
+        let secs = now.difference(&detected).as_seconds().max(0) as u32;
+        match secs {
+            0..=59 => gettext("detected just now"),
+            60..=3_599 => {
+                let n = secs / 60;
+                ngettext("detected {} minute ago", "detected {} minutes ago", n)
+                    .replace("{}", &n.to_string())
+            }
+            3_600..=86_399 => {
+                let n = secs / 3_600;
+                ngettext("detected {} hour ago", "detected {} hours ago", n)
+                    .replace("{}", &n.to_string())
+            }
+            _ => {
+                let n = secs / 86_400;
+                ngettext("detected {} day ago", "detected {} days ago", n)
+                    .replace("{}", &n.to_string())
+            }
+        }
+    }
+
+    /// Detection time in the user's local time zone and locale format,
+    /// or the raw timestamp if it cannot be parsed.
+    pub fn detected_absolute(&self) -> String {
+        self.detected_datetime()
+            .and_then(|dt| dt.to_local().ok())
+            .and_then(|dt| dt.format("%c").ok())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| self.detected_at.clone())
+    }
+
+    /// How the versions differ, e.g. "The remote version is newer by 3
+    /// hours. The remote version is 200 KiB larger." Each half falls back to
+    /// a neutral sentence when times or sizes are equal, and the times are
+    /// skipped if either can't be parsed.
+    pub fn summarize_difference(&self) -> String {
+        let time = match self.modified_difference() {
+            Some(secs) => {
+                let by = format_duration(secs.unsigned_abs());
+                if secs.abs() < SAME_TIME_SECS {
+                    gettext("Both versions were modified at the same time.")
+                } else if secs > 0 {
+                    gettext("The remote version is newer by {}.").replace("{}", &by)
+                } else {
+                    gettext("The local version is newer by {}.").replace("{}", &by)
+                }
+            }
+            None => gettext("The modification times can't be compared."),
+        };
+
+        let by = format_size(self.local_size.abs_diff(self.remote_size));
+        let size = match self.remote_size.cmp(&self.local_size) {
+            std::cmp::Ordering::Equal => gettext("Both versions are the same size."),
+            std::cmp::Ordering::Greater => {
+                gettext("The remote version is {} larger.").replace("{}", &by)
+            }
+            std::cmp::Ordering::Less => {
+                gettext("The local version is {} larger.").replace("{}", &by)
+            }
+        };
+
+        format!("{} {}", time, size)
+    }
+
+    /// Seconds by which the remote version is newer than the local one
+    /// (negative if older), or `None` if either time can't be parsed.
+    fn modified_difference(&self) -> Option<i64> {
+        let parse = |s: &str| glib::DateTime::from_iso8601(s, Some(&glib::TimeZone::utc())).ok();
+        let local = parse(&self.local_modified)?;
+        let remote = parse(&self.remote_modified)?;
+        Some(remote.difference(&local).as_seconds())
+    }
+
+    /// The version worth suggesting: the newer one; with equal or
+    /// unparseable times, the larger one; otherwise neither.
+    pub fn recommended_side(&self) -> Option<VersionSide> {
+        match self.modified_difference() {
+            Some(secs) if secs >= SAME_TIME_SECS => return Some(VersionSide::Remote),
+            Some(secs) if secs <= -SAME_TIME_SECS => return Some(VersionSide::Local),
+            _ => {}
+        }
+        match self.remote_size.cmp(&self.local_size) {
+            std::cmp::Ordering::Greater => Some(VersionSide::Remote),
+            std::cmp::Ordering::Less => Some(VersionSide::Local),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
     /// Return the file extension, if any.
     pub fn extension(&self) -> Option<&str> {
         self.item_path.rsplit('.').next()
     }
-}
 
-/// Format a byte count into a human-readable string.
-fn format_bytes(bytes: u64) -> String {
-    if bytes == 0 {
-        return "0 B".to_string();
+    /// Plain-text dump of every field, for pasting into bug reports.
+    /// Deliberately untranslated and unformatted (raw timestamps, exact
+    /// byte counts) so maintainers can read any user's report.
+    pub fn details_text(&self) -> String {
+        let mut text = format!(
+            "Conflict: {}\nItem ID: {}\nPath: {}\nDetected: {}\n",
+            self.id, self.item_id, self.item_path, self.detected_at
+        );
+        text.push_str(&format!(
+            "Local version:\n  Size: {} bytes\n  Modified: {}\n  Hash: {}\n",
+            self.local_size, self.local_modified, self.local_hash
+        ));
+        text.push_str(&format!(
+            "Remote version:\n  Size: {} bytes\n  Modified: {}\n",
+            self.remote_size, self.remote_modified
+        ));
+        if !self.remote_modified_by.is_empty() {
+            text.push_str(&format!("  Modified by: {}\n", self.remote_modified_by));
+        }
+        text.push_str(&format!("  Hash: {}\n", self.remote_hash));
+        text
     }
-    let units = ["B", "KB", "MB", "GB", "TB"];
-    let k = 1024_f64;
-    let i = (bytes as f64).ln() / k.ln();
-    let i = i.floor() as usize;
-    let i = i.min(units.len() - 1);
-    let value = bytes as f64 / k.powi(i as i32);
-    if i == 0 {
-        format!("{} {}", value as u64, units[i])
-    } else {
-        format!("{:.1} {}", value, units[i])
+}
+
+/// Format a duration in seconds as "3 minutes", "2 hours" or "5 days".
+fn format_duration(secs: u64) -> String {
+    // Literal msgids in every arm so xgettext can extract them.
+    match secs {
+        0..=3_599 => {
+            let n = (secs / 60).max(1) as u32;
+            ngettext("{} minute", "{} minutes", n).replace("{}", &n.to_string())
+        }
+        3_600..=86_399 => {
+            let n = (secs / 3_600) as u32;
+            ngettext("{} hour", "{} hours", n).replace("{}", &n.to_string())
+        }
+        _ => {
+            let n = (secs / 86_400).min(u64::from(u32::MAX)) as u32;
+            ngettext("{} day", "{} days", n).replace("{}", &n.to_string())
+        }
     }
 }
 
+/// Create an icon with an accessible label, so screen readers describe it
+/// instead of skipping it or reading the icon name.
+pub fn labelled_icon(icon_name: &str, label: &str) -> gtk4::Image {
+    let image = gtk4::Image::from_icon_name(icon_name);
+    image.update_property(&[gtk4::accessible::Property::Label(label)]);
+    image
+}
+
+/// "Recommended" badge for the version group `recommended_side` picked;
+/// the tooltip states why.
+fn recommended_badge(conflict: &ConflictInfo) -> gtk4::Label {
+    let reason = match conflict.modified_difference() {
+        Some(secs) if secs.abs() >= SAME_TIME_SECS => gettext("This version is newer"),
+        _ => gettext("This version is larger"),
+    };
+    gtk4::Label::builder()
+        .label(&gettext("Recommended"))
+        .tooltip_text(&reason)
+        .css_classes(["caption-heading", "accent"])
+        .valign(gtk4::Align::Center)
+        .build()
+}
+
 // ---------------------------------------------------------------------------
 // ConflictDetailDialog
 // ---------------------------------------------------------------------------
@@ -136,6 +317,9 @@ mod imp {
         pub dbus_client: RefCell<Option<DbusClient>>,
         pub conflict_id: RefCell<String>,
         pub toast_overlay: RefCell<Option<adw::ToastOverlay>>,
+        /// Widget the dialog was presented from; its PreferencesDialog shows
+        /// toasts that outlive this dialog.
+        pub origin: glib::WeakRef<gtk4::Widget>,
     }
 
     impl Default for ConflictDetailDialog {
@@ -144,6 +328,7 @@ mod imp {
                 dbus_client: RefCell::new(None),
                 conflict_id: RefCell::new(String::new()),
                 toast_overlay: RefCell::new(None),
+                origin: glib::WeakRef::new(),
             }
         }
     }
@@ -168,7 +353,7 @@ glib::wrapper! {
 
 impl ConflictDetailDialog {
     /// Create and populate the dialog for a given conflict.
-    pub fn new(conflict: &ConflictInfo, dbus_client: &DbusClient) -> Self {
+    pub fn new(conflict: &ConflictObject, dbus_client: &DbusClient) -> Self {
         let dialog: Self = glib::Object::builder()
             .property("title", gettext("Resolve Conflict"))
             .build();
@@ -180,17 +365,37 @@ impl ConflictDetailDialog {
         dialog
             .imp()
             .conflict_id
-            .replace(conflict.id.clone());
+            .replace(conflict.id());
 
         dialog.build_ui(conflict);
         dialog
     }
 
-    fn build_ui(&self, conflict: &ConflictInfo) {
+    /// Present the dialog over `origin`, e.g. the conflict list page.
+    pub fn present_from(&self, origin: &impl IsA<gtk4::Widget>) {
+        self.imp().origin.set(Some(origin.upcast_ref()));
+        self.present(Some(origin));
+    }
+
+    fn build_ui(&self, object: &ConflictObject) {
+        let conflict = &object.info();
         let toolbar_view = adw::ToolbarView::new();
         let header = adw::HeaderBar::new();
+
+        let copy_button = gtk4::Button::builder()
+            .label(&gettext("Copy Details"))
+            .tooltip_text(&gettext("Copy the conflict details for a bug report"))
+            .build();
+        header.pack_start(&copy_button);
         toolbar_view.add_top_bar(&header);
 
+        let dialog = self.clone();
+        let conflict_object = object.clone();
+        copy_button.connect_clicked(move |button| {
+            button.clipboard().set_text(&conflict_object.info().details_text());
+            dialog.show_toast(&gettext("Conflict details copied to clipboard"));
+        });
+
         let content = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
         content.set_margin_start(24);
         content.set_margin_end(24);
@@ -199,20 +404,35 @@ impl ConflictDetailDialog {
 
         // -- File info header ------------------------------------------------
         let file_label = gtk4::Label::builder()
-            .label(conflict.filename())
             .css_classes(["title-2"])
             .halign(gtk4::Align::Start)
             .build();
+        object
+            .bind_property("filename", &file_label, "label")
+            .sync_create()
+            .build();
         content.append(&file_label);
 
         let path_label = gtk4::Label::builder()
-            .label(&conflict.item_path)
             .css_classes(["dim-label"])
             .halign(gtk4::Align::Start)
             .ellipsize(gtk4::pango::EllipsizeMode::Middle)
             .build();
+        object
+            .bind_property("path", &path_label, "label")
+            .sync_create()
+            .build();
         content.append(&path_label);
 
+        // -- What changed? ----------------------------------------------------
+        let summary_label = gtk4::Label::builder()
+            .label(&conflict.summarize_difference())
+            .wrap(true)
+            .xalign(0.0)
+            .halign(gtk4::Align::Start)
+            .build();
+        content.append(&summary_label);
+
         // -- Side-by-side version comparison ----------------------------------
         let comparison_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 24);
         comparison_box.set_homogeneous(true);
@@ -223,7 +443,11 @@ impl ConflictDetailDialog {
             .build();
         let local_size_row = adw::ActionRow::builder()
             .title(&gettext("Size"))
-            .subtitle(&format_bytes(conflict.local_size))
+            .build();
+        object
+            .bind_property("local-size", &local_size_row, "subtitle")
+            .transform_to(|_, bytes: u64| Some(format_size(bytes)))
+            .sync_create()
             .build();
         let local_modified_row = adw::ActionRow::builder()
             .title(&gettext("Modified"))
@@ -243,7 +467,11 @@ impl ConflictDetailDialog {
             .build();
         let remote_size_row = adw::ActionRow::builder()
             .title(&gettext("Size"))
-            .subtitle(&format_bytes(conflict.remote_size))
+            .build();
+        object
+            .bind_property("remote-size", &remote_size_row, "subtitle")
+            .transform_to(|_, bytes: u64| Some(format_size(bytes)))
+            .sync_create()
             .build();
         let remote_modified_row = adw::ActionRow::builder()
             .title(&gettext("Modified"))
@@ -255,8 +483,27 @@ impl ConflictDetailDialog {
             .build();
         remote_group.add(&remote_size_row);
         remote_group.add(&remote_modified_row);
+        if !conflict.remote_modified_by.is_empty() {
+            let remote_modified_by_row = adw::ActionRow::builder()
+                .title(&gettext("Modified By"))
+                .subtitle(&conflict.remote_modified_by)
+                .build();
+            remote_group.add(&remote_modified_by_row);
+        }
         remote_group.add(&remote_hash_row);
 
+        if AppSettings::new().recommend_conflict_version() {
+            match conflict.recommended_side() {
+                Some(VersionSide::Local) => {
+                    local_group.set_header_suffix(Some(&recommended_badge(conflict)));
+                }
+                Some(VersionSide::Remote) => {
+                    remote_group.set_header_suffix(Some(&recommended_badge(conflict)));
+                }
+                None => {}
+            }
+        }
+
         comparison_box.append(&local_group);
         comparison_box.append(&remote_group);
         content.append(&comparison_box);
@@ -271,21 +518,24 @@ impl ConflictDetailDialog {
             .subtitle(&gettext("Upload the local version, overwriting the remote"))
             .activatable(true)
             .build();
-        keep_local_row.add_suffix(&gtk4::Image::from_icon_name("go-up-symbolic"));
+        keep_local_row.add_suffix(&labelled_icon("go-up-symbolic", &gettext("Upload")));
 
         let keep_remote_row = adw::ActionRow::builder()
             .title(&gettext("Keep Remote"))
             .subtitle(&gettext("Download the remote version, overwriting the local"))
             .activatable(true)
             .build();
-        keep_remote_row.add_suffix(&gtk4::Image::from_icon_name("go-down-symbolic"));
+        keep_remote_row.add_suffix(&labelled_icon("go-down-symbolic", &gettext("Download")));
 
         let keep_both_row = adw::ActionRow::builder()
             .title(&gettext("Keep Both"))
             .subtitle(&gettext("Rename the local file and download the remote version"))
             .activatable(true)
             .build();
-        keep_both_row.add_suffix(&gtk4::Image::from_icon_name("edit-copy-symbolic"));
+        keep_both_row.add_suffix(&labelled_icon(
+            "edit-copy-symbolic",
+            &gettext("Keep both versions"),
+        ));
 
         actions_group.add(&keep_local_row);
         actions_group.add(&keep_remote_row);
@@ -339,7 +589,16 @@ impl ConflictDetailDialog {
         glib::MainContext::default().spawn_local(async move {
             match client.resolve_conflict(&conflict_id, &strategy).await {
                 Ok(true) => {
+                    let renamed_copy = if strategy == "keep_both" {
+                        Self::renamed_copy(&client, &conflict_id).await
+                    } else {
+                        None
+                    };
+                    let origin = dialog.imp().origin.upgrade();
                     dialog.close();
+                    if let (Some(path), Some(origin)) = (renamed_copy, origin) {
+                        show_renamed_copy_toast(&origin, path);
+                    }
                 }
                 Ok(false) => {
                     dialog.show_toast(&format!(
@@ -359,9 +618,197 @@ impl ConflictDetailDialog {
         });
     }
 
+    /// Where "keep both" moved the local version, from the conflict's
+    /// details after resolution.
+    async fn renamed_copy(client: &DbusClient, conflict_id: &str) -> Option<String> {
+        let json = match client.get_conflict_details(conflict_id).await {
+            Ok(json) => json,
+            Err(e) => {
+                eprintln!("Could not read the renamed copy of {conflict_id}: {e}");
+                return None;
+            }
+        };
+        let details: serde_json::Value = serde_json::from_str(&json).ok()?;
+        details
+            .get("renamed_copy")?
+            .as_str()
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+    }
+
     fn show_toast(&self, message: &str) {
         if let Some(ref overlay) = *self.imp().toast_overlay.borrow() {
             overlay.add_toast(adw::Toast::new(message));
         }
     }
 }
+
+/// Toast on the PreferencesDialog around `origin` naming the renamed local
+/// copy, with "Show in Files" opening its folder with the file selected.
+fn show_renamed_copy_toast(origin: &gtk4::Widget, path: String) {
+    let Some(preferences) = origin
+        .ancestor(adw::PreferencesDialog::static_type())
+        .and_downcast::<adw::PreferencesDialog>()
+    else {
+        return;
+    };
+
+    let name = std::path::Path::new(&path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.clone());
+    let toast = adw::Toast::builder()
+        .title(&gettext("Your local version was kept as \"{}\"").replace("{}", &name))
+        .button_label(&gettext("Show in Files"))
+        .timeout(10)
+        .build();
+
+    let origin = origin.downgrade();
+    toast.connect_button_clicked(move |_| {
+        let window = origin
+            .upgrade()
+            .and_then(|w| w.root())
+            .and_downcast::<gtk4::Window>();
+        let launcher = gtk4::FileLauncher::new(Some(&gio::File::for_path(&path)));
+        launcher.open_containing_folder(
+            window.as_ref(),
+            None::<&gio::Cancellable>,
+            |result| {
+                if let Err(e) = result {
+                    eprintln!("Could not show the renamed copy in Files: {e}");
+                }
+            },
+        );
+    });
+
+    preferences.add_toast(toast);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conflicts::conflict_list::ConflictListPage;
+    use crate::test_harness::{
+        find_widgets, labels, present, run_widget_test, wait_until, MockState, TestDaemon,
+    };
+
+    /// A conflict between two versions of `report.odt`, detected at
+    /// `detected_at`.
+    fn conflict(
+        detected_at: &str,
+        (local_size, local_modified): (u64, &str),
+        (remote_size, remote_modified): (u64, &str),
+    ) -> ConflictInfo {
+        ConflictInfo {
+            id: "conflict-001".to_string(),
+            item_id: "item-report".to_string(),
+            item_path: "/Documents/report.odt".to_string(),
+            detected_at: detected_at.to_string(),
+            local_hash: "abc".to_string(),
+            local_size,
+            local_modified: local_modified.to_string(),
+            remote_hash: "def".to_string(),
+            remote_size,
+            remote_modified: remote_modified.to_string(),
+            remote_modified_by: String::new(),
+        }
+    }
+
+    /// `hours` ago, in ISO 8601.
+    fn hours_ago(hours: i32) -> String {
+        let now = glib::DateTime::now_utc().unwrap();
+        now.add_hours(-hours)
+            .unwrap()
+            .format_iso8601()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn compares_versions_of_equal_size_by_time_alone() {
+        let info = conflict(
+            &hours_ago(3),
+            (2048, "2026-02-07T07:00:00Z"),
+            (2048, "2026-02-07T10:00:00Z"),
+        );
+        assert_eq!(
+            info.summarize_difference(),
+            format!(
+                "{} {}",
+                gettext("The remote version is newer by {}.")
+                    .replace("{}", &ngettext("{} hour", "{} hours", 3).replace("{}", "3")),
+                gettext("Both versions are the same size."),
+            )
+        );
+        assert_eq!(info.recommended_side(), Some(VersionSide::Remote));
+        assert_eq!(
+            info.detected_relative(),
+            ngettext("detected {} hour ago", "detected {} hours ago", 3).replace("{}", "3")
+        );
+    }
+
+    #[test]
+    fn treats_times_within_a_minute_as_the_same() {
+        let info = conflict(
+            &hours_ago(0),
+            (4096, "2026-02-07T10:00:30Z"),
+            (2048, "2026-02-07T10:00:00Z"),
+        );
+        assert!(info
+            .summarize_difference()
+            .starts_with(&gettext("Both versions were modified at the same time.")));
+        // The times can't settle it, so the larger version is suggested.
+        assert_eq!(info.recommended_side(), Some(VersionSide::Local));
+        assert_eq!(info.detected_relative(), gettext("detected just now"));
+
+        let same_size = conflict(
+            &hours_ago(0),
+            (2048, "2026-02-07T10:00:30Z"),
+            (2048, "2026-02-07T10:00:00Z"),
+        );
+        assert_eq!(same_size.recommended_side(), None);
+    }
+
+    #[test]
+    fn falls_back_when_dates_cannot_be_parsed() {
+        let info = conflict("yesterday", (1024, "soon"), (2048, "2026-02-07T10:00:00Z"));
+        assert!(info
+            .summarize_difference()
+            .starts_with(&gettext("The modification times can't be compared.")));
+        assert_eq!(info.recommended_side(), Some(VersionSide::Remote));
+        assert_eq!(info.detected_relative(), "yesterday");
+    }
+
+    #[test]
+    fn names_the_renamed_copy_after_keep_both() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let info = ConflictInfo::from_json(&daemon.state().conflicts[0]).unwrap();
+            let window = present(&gtk4::Box::new(gtk4::Orientation::Vertical, 0));
+            let preferences = adw::PreferencesDialog::new();
+            let page = ConflictListPage::new(daemon.client());
+            preferences.add(&page);
+            preferences.present(Some(&window));
+
+            let dialog =
+                ConflictDetailDialog::new(&ConflictObject::from_info(&info), daemon.client());
+            dialog.present_from(&page);
+            let keep_both = || {
+                find_widgets::<adw::ActionRow>(&dialog)
+                    .into_iter()
+                    .find(|row| row.title() == gettext("Keep Both"))
+            };
+            wait_until("the conflict dialog", || keep_both().is_some());
+            ActionRowExt::activate(&keep_both().unwrap());
+
+            let toast = gettext("Your local version was kept as \"{}\"")
+                .replace("{}", "budget (conflicted copy).xlsx");
+            wait_until("the renamed copy toast", || {
+                labels(&preferences).contains(&toast)
+            });
+            assert_eq!(daemon.state().history[0]["strategy"], "keep_both");
+
+            window.destroy();
+        });
+    }
+}
@@ -2,24 +2,67 @@
 //
 // Displays all unresolved conflicts in a list and provides:
 // - Click to open ConflictDetailDialog per conflict
+// - A search entry filtering the list by path, newest conflicts first
+// - Checkboxes to select several conflicts, with a "N selected" row that
+//   resolves just the selection with one strategy
 // - "Resolve All" button with strategy selection
-// - Real-time updates via D-Bus signals
+// - A "Recently Resolved" expander listing the last resolutions, with
+//   "Re-open" for those the daemon can revert
+// - Real-time updates via D-Bus signals, coalesced so a burst of signals
+//   triggers a single reload
+//
+// The list is a `gtk::ListView` over a `gio::ListStore` of ConflictObjects,
+// wrapped in filter and sort models, so only the visible rows are built and
+// thousands of conflicts stay responsive. Reloads replace the store contents
+// in one splice.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 
 use futures_util::future::{AbortHandle, Abortable};
 use futures_util::StreamExt;
-use gettextrs::gettext;
+use gettextrs::{gettext, ngettext};
+use gtk4::gio;
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita as adw;
 use libadwaita::prelude::*;
+use serde::Deserialize;
 
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 
 use crate::dbus_client::{DbusClient, LnxdriveConflictsProxy};
+use crate::settings::{keys, AppSettings};
+use crate::util::{self, Coalescer, SIGNAL_COALESCE_DELAY};
+
+use super::conflict_dialog::{labelled_icon, ConflictDetailDialog, ConflictInfo};
+use super::conflict_object::ConflictObject;
 
-use super::conflict_dialog::{ConflictDetailDialog, ConflictInfo};
+/// Object data key under which each bound ListItem keeps the SignalHandlerId
+/// of its CheckButton's `toggled` handler.
+const TOGGLED_HANDLER_KEY: &str = "lnxdrive-toggled-handler";
+
+/// Object data key under which each bound ListItem keeps the glib::Binding
+/// from its ConflictObject's `filename` to the row title.
+const ROW_BINDINGS_KEY: &str = "lnxdrive-row-bindings";
+
+/// Return the CheckButton, title and subtitle labels of a row built by the
+/// factory's setup closure (ListItem -> Box -> CheckButton, Image, Box).
+fn row_widgets(list_item: &gtk4::ListItem) -> Option<(gtk4::CheckButton, gtk4::Label, gtk4::Label)> {
+    let check = list_item
+        .child()
+        .and_downcast::<gtk4::Box>()?
+        .first_child()
+        .and_downcast::<gtk4::CheckButton>()?;
+    let title = check
+        .next_sibling()?
+        .next_sibling()
+        .and_downcast::<gtk4::Box>()?
+        .first_child()
+        .and_downcast::<gtk4::Label>()?;
+    let subtitle = title.next_sibling().and_downcast::<gtk4::Label>()?;
+    Some((check, title, subtitle))
+}
 
 // ---------------------------------------------------------------------------
 // ConflictListPage — adw::PreferencesPage subclass
@@ -32,18 +75,53 @@ mod imp {
 
     pub struct ConflictListPage {
         pub dbus_client: RefCell<Option<DbusClient>>,
-        pub conflicts_group: RefCell<Option<adw::PreferencesGroup>>,
-        pub empty_label: RefCell<Option<gtk4::Label>>,
+        /// Unfiltered, unsorted ConflictObjects as listed by the daemon.
+        pub store: RefCell<Option<gio::ListStore>>,
+        /// What the list view shows: `store` filtered by the search text.
+        pub filter_model: RefCell<Option<gtk4::FilterListModel>>,
+        pub filter: RefCell<Option<gtk4::StringFilter>>,
+        pub search_entry: RefCell<Option<gtk4::SearchEntry>>,
+        pub list_view: RefCell<Option<gtk4::ListView>>,
+        pub scrolled: RefCell<Option<gtk4::ScrolledWindow>>,
+        /// Compact row density; bound to the `compact-lists` setting.
+        pub compact: Cell<bool>,
+        /// "All files are in sync" row, shown instead of the list.
+        pub empty_list: RefCell<Option<gtk4::ListBox>>,
+        /// Shown when conflicts exist but none match the search.
+        pub no_match_label: RefCell<Option<gtk4::Label>>,
+        /// IDs of the conflicts whose checkboxes are ticked.
+        pub selected_ids: RefCell<HashSet<String>>,
+        /// "N selected" bar with the strategy buttons, above the list and
+        /// shown while the selection is non-empty.
+        pub selection_bar: RefCell<Option<gtk4::Box>>,
+        pub selection_label: RefCell<Option<gtk4::Label>>,
         pub signal_abort: RefCell<Option<AbortHandle>>,
+        /// "Recently Resolved" group, below the conflicts group.
+        pub history_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub history_row: RefCell<Option<adw::ExpanderRow>>,
+        pub history_rows: RefCell<Vec<adw::ActionRow>>,
     }
 
     impl Default for ConflictListPage {
         fn default() -> Self {
             Self {
                 dbus_client: RefCell::new(None),
-                conflicts_group: RefCell::new(None),
-                empty_label: RefCell::new(None),
+                store: RefCell::new(None),
+                filter_model: RefCell::new(None),
+                filter: RefCell::new(None),
+                search_entry: RefCell::new(None),
+                list_view: RefCell::new(None),
+                scrolled: RefCell::new(None),
+                compact: Cell::new(false),
+                empty_list: RefCell::new(None),
+                no_match_label: RefCell::new(None),
+                selected_ids: RefCell::new(HashSet::new()),
+                selection_bar: RefCell::new(None),
+                selection_label: RefCell::new(None),
                 signal_abort: RefCell::new(None),
+                history_group: RefCell::new(None),
+                history_row: RefCell::new(None),
+                history_rows: RefCell::new(Vec::new()),
             }
         }
     }
@@ -56,6 +134,32 @@ mod imp {
     }
 
     impl ObjectImpl for ConflictListPage {
+        fn properties() -> &'static [glib::ParamSpec] {
+            use std::sync::OnceLock;
+            static PROPERTIES: OnceLock<Vec<glib::ParamSpec>> = OnceLock::new();
+            PROPERTIES.get_or_init(|| vec![glib::ParamSpecBoolean::builder("compact").build()])
+        }
+
+        fn set_property(&self, _id: usize, value: &glib::Value, pspec: &glib::ParamSpec) {
+            match pspec.name() {
+                "compact" => {
+                    let compact: bool = value.get().unwrap_or(false);
+                    self.compact.set(compact);
+                    if let Some(ref view) = *self.list_view.borrow() {
+                        util::set_css_class(view, "compact", compact);
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        fn property(&self, _id: usize, pspec: &glib::ParamSpec) -> glib::Value {
+            match pspec.name() {
+                "compact" => self.compact.get().to_value(),
+                _ => unreachable!(),
+            }
+        }
+
         fn dispose(&self) {
             if let Some(handle) = self.signal_abort.borrow_mut().take() {
                 handle.abort();
@@ -75,6 +179,47 @@ glib::wrapper! {
 const STRATEGY_LABELS: &[&str] = &["Keep Local", "Keep Remote", "Keep Both"];
 const STRATEGY_VALUES: &[&str] = &["keep_local", "keep_remote", "keep_both"];
 
+/// How many past resolutions "Recently Resolved" asks the daemon for.
+const HISTORY_LIMIT: u32 = 20;
+
+/// Translated label for a strategy value; unknown values are shown as-is.
+fn strategy_label(strategy: &str) -> String {
+    STRATEGY_VALUES
+        .iter()
+        .position(|v| *v == strategy)
+        .map(|i| gettext(STRATEGY_LABELS[i]))
+        .unwrap_or_else(|| strategy.to_string())
+}
+
+/// One entry of the daemon's resolution history.
+#[derive(Debug, Clone, Deserialize)]
+struct ResolutionRecord {
+    id: String,
+    item_path: String,
+    strategy: String,
+    resolved_at: String,
+    /// Whether the daemon can still revert this resolution.
+    #[serde(default)]
+    reopenable: bool,
+}
+
+impl ResolutionRecord {
+    fn filename(&self) -> &str {
+        self.item_path.rsplit('/').next().unwrap_or(&self.item_path)
+    }
+
+    /// Resolution time in the user's local time zone and locale format,
+    /// or the raw timestamp if it cannot be parsed.
+    fn resolved_absolute(&self) -> String {
+        glib::DateTime::from_iso8601(&self.resolved_at, Some(&glib::TimeZone::utc()))
+            .ok()
+            .and_then(|dt| dt.to_local().ok())
+            .and_then(|dt| dt.format("%c").ok())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| self.resolved_at.clone())
+    }
+}
+
 impl ConflictListPage {
     pub fn new(dbus_client: &DbusClient) -> Self {
         let page: Self = glib::Object::builder()
@@ -87,12 +232,21 @@ impl ConflictListPage {
             .replace(Some(dbus_client.clone()));
 
         page.build_ui();
+        AppSettings::new().bind(keys::COMPACT_LISTS, &page, "compact");
         page.load_conflicts();
         page.subscribe_signals();
 
         page
     }
 
+    /// Fetch the conflicts and resolution history again, dropping the
+    /// current selection.
+    pub fn reload(&self) {
+        self.imp().selected_ids.borrow_mut().clear();
+        self.update_selection_bar();
+        self.load_conflicts();
+    }
+
     /// Subscribe to ConflictDetected and ConflictResolved D-Bus signals
     /// so the list auto-refreshes in real-time.
     fn subscribe_signals(&self) {
@@ -138,8 +292,12 @@ impl ConflictListPage {
                     resolved.map(|_| ()),
                 );
 
+                // A large sync can emit many signals per second; refresh
+                // once per burst.
+                let coalescer = Coalescer::new(SIGNAL_COALESCE_DELAY);
                 while merged.next().await.is_some() {
-                    page.load_conflicts();
+                    let page = page.clone();
+                    coalescer.trigger(move || page.load_conflicts());
                 }
             }, abort_registration).await;
         });
@@ -165,19 +323,252 @@ impl ConflictListPage {
         });
         conflicts_group.set_header_suffix(Some(&resolve_all_button));
 
-        // Empty state label
-        let empty_label = gtk4::Label::builder()
-            .label(&gettext("No unresolved conflicts"))
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .build();
+
+        let search_entry = gtk4::SearchEntry::builder()
+            .placeholder_text(&gettext("Search conflicts"))
+            .build();
+        content.append(&search_entry);
+
+        let selection_bar = self.build_selection_bar();
+        content.append(&selection_bar);
+
+        // Model: store -> filter (search text) -> sort (newest first).
+        let store = gio::ListStore::new::<ConflictObject>();
+
+        let filter = gtk4::StringFilter::builder()
+            .expression(gtk4::PropertyExpression::new(
+                ConflictObject::static_type(),
+                None::<gtk4::Expression>,
+                "path",
+            ))
+            .match_mode(gtk4::StringFilterMatchMode::Substring)
+            .ignore_case(true)
+            .build();
+        search_entry
+            .bind_property("text", &filter, "search")
+            .sync_create()
+            .build();
+        let filter_model = gtk4::FilterListModel::new(Some(store.clone()), Some(filter.clone()));
+
+        // detected_at may carry any UTC offset, so compare parsed times.
+        let sorter = gtk4::CustomSorter::new(|a, b| {
+            let detected = |item: &glib::Object| {
+                item.downcast_ref::<ConflictObject>()
+                    .and_then(|obj| obj.info().detected_datetime())
+                    .map(|dt| dt.to_unix())
+            };
+            detected(b).cmp(&detected(a)).into()
+        });
+        let sort_model = gtk4::SortListModel::new(Some(filter_model.clone()), Some(sorter));
+
+        let page = self.downgrade();
+        filter_model.connect_items_changed(move |_, _, _, _| {
+            if let Some(page) = page.upgrade() {
+                page.update_empty_state();
+            }
+        });
+
+        let factory = gtk4::SignalListItemFactory::new();
+        factory.connect_setup(|_factory, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk4::ListItem>()
+                .expect("ListItem expected");
+
+            let hbox = gtk4::Box::builder()
+                .orientation(gtk4::Orientation::Horizontal)
+                .spacing(12)
+                .css_classes(["conflict-row"])
+                .build();
+
+            let check = gtk4::CheckButton::builder()
+                .valign(gtk4::Align::Center)
+                .build();
+            let text = gtk4::Box::builder()
+                .orientation(gtk4::Orientation::Vertical)
+                .spacing(2)
+                .valign(gtk4::Align::Center)
+                .hexpand(true)
+                .build();
+            let title = gtk4::Label::builder()
+                .halign(gtk4::Align::Start)
+                .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+                .build();
+            let subtitle = gtk4::Label::builder()
+                .halign(gtk4::Align::Start)
+                .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+                .css_classes(["dim-label", "caption"])
+                .build();
+            text.append(&title);
+            text.append(&subtitle);
+
+            // Screen readers announce the file name when the checkbox gets
+            // focus.
+            check.update_relation(&[gtk4::accessible::Relation::LabelledBy(&[
+                title.upcast_ref(),
+            ])]);
+
+            hbox.append(&check);
+            hbox.append(&labelled_icon("dialog-warning-symbolic", &gettext("Conflict")));
+            hbox.append(&text);
+            // Auto-mirrors under RTL (see module docs).
+            hbox.append(&labelled_icon("go-next-symbolic", &gettext("Show details")));
+
+            list_item.set_child(Some(&hbox));
+        });
+
+        let page = self.downgrade();
+        factory.connect_bind(move |_factory, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk4::ListItem>()
+                .expect("ListItem expected");
+            let object = list_item
+                .item()
+                .and_downcast::<ConflictObject>()
+                .expect("Item must be ConflictObject");
+            let conflict = object.info();
+            let Some((check, title, subtitle)) = row_widgets(list_item) else {
+                return;
+            };
+            let Some(page) = page.upgrade() else {
+                return;
+            };
+
+            let binding = object
+                .bind_property("filename", &title, "label")
+                .sync_create()
+                .build();
+            // SAFETY: the key is private to this module and always holds a
+            // glib::Binding.
+            unsafe {
+                list_item.set_data(ROW_BINDINGS_KEY, binding);
+            }
+            subtitle.set_label(&format!(
+                "{} — {}",
+                conflict.detected_relative(),
+                conflict.item_path
+            ));
+            if let Some(row) = list_item.child() {
+                row.set_tooltip_text(Some(&conflict.detected_absolute()));
+            }
+            check.set_active(page.imp().selected_ids.borrow().contains(&conflict.id));
+
+            // Stored on the list item so unbind can disconnect it before the
+            // row is recycled for another conflict.
+            let page_ref = page.clone();
+            let id = conflict.id.clone();
+            let handler_id = check.connect_toggled(move |check| {
+                page_ref.set_conflict_selected(&id, check.is_active());
+            });
+            // SAFETY: the key is private to this module and always holds a
+            // SignalHandlerId.
+            unsafe {
+                list_item.set_data(TOGGLED_HANDLER_KEY, handler_id);
+            }
+        });
+
+        factory.connect_unbind(|_factory, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk4::ListItem>()
+                .expect("ListItem expected");
+            // SAFETY: see connect_bind.
+            let handler_id =
+                unsafe { list_item.steal_data::<glib::SignalHandlerId>(TOGGLED_HANDLER_KEY) };
+            if let (Some(handler_id), Some((check, _, _))) = (handler_id, row_widgets(list_item)) {
+                check.disconnect(handler_id);
+            }
+            let binding = unsafe { list_item.steal_data::<glib::Binding>(ROW_BINDINGS_KEY) };
+            if let Some(binding) = binding {
+                binding.unbind();
+            }
+        });
+
+        let list_view = gtk4::ListView::builder()
+            .model(&gtk4::NoSelection::new(Some(sort_model)))
+            .factory(&factory)
+            .single_click_activate(true)
+            .build();
+        list_view.add_css_class("boxed-list");
+        list_view.update_property(&[gtk4::accessible::Property::Label(&gettext(
+            "Unresolved conflicts",
+        ))]);
+
+        let page = self.clone();
+        list_view.connect_activate(move |view, position| {
+            let conflict = view
+                .model()
+                .and_then(|model| model.item(position))
+                .and_downcast::<ConflictObject>();
+            let client = page.imp().dbus_client.borrow().clone();
+            if let (Some(conflict), Some(client)) = (conflict, client) {
+                let dialog = ConflictDetailDialog::new(&conflict, &client);
+                // Present on the nearest toplevel
+                dialog.present_from(&page);
+            }
+        });
+
+        // The list scrolls on its own so only the rows in view are built.
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .propagate_natural_height(true)
+            .max_content_height(480)
+            .child(&list_view)
+            .build();
+        content.append(&scrolled);
+
+        let no_match_label = gtk4::Label::builder()
+            .label(&gettext("No conflicts match the search"))
             .css_classes(["dim-label"])
             .margin_top(12)
             .margin_bottom(12)
+            .visible(false)
+            .build();
+        content.append(&no_match_label);
+
+        let empty_row = adw::ActionRow::builder()
+            .title(&gettext("No unresolved conflicts"))
+            .subtitle(&gettext("All files are in sync"))
             .build();
+        empty_row.add_prefix(&labelled_icon("emblem-ok-symbolic", &gettext("In sync")));
+        let empty_list = gtk4::ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        empty_list.append(&empty_row);
+        content.append(&empty_list);
+
+        conflicts_group.add(&content);
 
-        imp.conflicts_group
-            .replace(Some(conflicts_group.clone()));
-        imp.empty_label.replace(Some(empty_label));
+        imp.store.replace(Some(store));
+        imp.filter_model.replace(Some(filter_model));
+        imp.filter.replace(Some(filter));
+        imp.search_entry.replace(Some(search_entry));
+        imp.list_view.replace(Some(list_view));
+        imp.scrolled.replace(Some(scrolled));
+        imp.no_match_label.replace(Some(no_match_label));
+        imp.empty_list.replace(Some(empty_list));
 
         self.add(&conflicts_group);
+        self.update_selection_bar();
+        self.update_empty_state();
+
+        // -- Recently Resolved group ------------------------------------------
+        let history_group = adw::PreferencesGroup::new();
+        let history_row = adw::ExpanderRow::builder()
+            .title(&gettext("Recently Resolved"))
+            .subtitle(&gettext("Check how past conflicts were resolved"))
+            .build();
+        history_group.add(&history_row);
+        // Shown once the daemon reports at least one resolution.
+        history_group.set_visible(false);
+
+        imp.history_row.replace(Some(history_row));
+        imp.history_group.replace(Some(history_group.clone()));
+        self.add(&history_group);
     }
 
     /// Fetch the conflict list from the daemon and populate the UI.
@@ -199,104 +590,315 @@ impl ConflictListPage {
                     page.populate_list(&[]);
                 }
             }
+
+            // Older daemons lack the history method; the group then stays
+            // hidden.
+            match client.get_resolution_history(HISTORY_LIMIT).await {
+                Ok(json_str) => {
+                    let records: Vec<ResolutionRecord> =
+                        serde_json::from_str(&json_str).unwrap_or_default();
+                    page.populate_history(&records);
+                }
+                Err(e) => {
+                    eprintln!("Could not load resolution history: {e}");
+                    page.populate_history(&[]);
+                }
+            }
+        });
+    }
+
+    /// Fill the "Recently Resolved" expander, newest first.
+    fn populate_history(&self, records: &[ResolutionRecord]) {
+        let imp = self.imp();
+        let (group, expander) = match (
+            imp.history_group.borrow().clone(),
+            imp.history_row.borrow().clone(),
+        ) {
+            (Some(g), Some(e)) => (g, e),
+            _ => return,
+        };
+
+        for row in imp.history_rows.borrow_mut().drain(..) {
+            expander.remove(&row);
+        }
+        group.set_visible(!records.is_empty());
+
+        let mut rows = Vec::new();
+        for record in records.iter().take(HISTORY_LIMIT as usize) {
+            let row = adw::ActionRow::builder()
+                .title(record.filename())
+                .subtitle(&format!(
+                    "{} — {}",
+                    strategy_label(&record.strategy),
+                    record.resolved_absolute()
+                ))
+                .tooltip_text(&record.item_path)
+                .build();
+
+            if record.reopenable {
+                let reopen_button = gtk4::Button::builder()
+                    .label(&gettext("Re-open"))
+                    .tooltip_text(&gettext("Undo this resolution"))
+                    .valign(gtk4::Align::Center)
+                    .css_classes(["flat"])
+                    .build();
+                let page = self.clone();
+                let id = record.id.clone();
+                reopen_button.connect_clicked(move |_| {
+                    page.reopen_conflict(&id);
+                });
+                row.add_suffix(&reopen_button);
+            }
+
+            expander.add_row(&row);
+            rows.push(row);
+        }
+        *imp.history_rows.borrow_mut() = rows;
+    }
+
+    /// Ask the daemon to revert a resolution, then reload both lists.
+    fn reopen_conflict(&self, id: &str) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let id = id.to_string();
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.reopen_conflict(&id).await {
+                Ok(true) => page.load_conflicts(),
+                Ok(false) => {
+                    page.show_toast(&gettext("This resolution can no longer be undone"));
+                    page.load_conflicts();
+                }
+                Err(e) => {
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Could not re-open conflict"),
+                        e,
+                    ));
+                }
+            }
         });
     }
 
     fn populate_list(&self, conflicts: &[ConflictInfo]) {
         let imp = self.imp();
-        let group = match imp.conflicts_group.borrow().clone() {
-            Some(g) => g,
+        let store = match imp.store.borrow().clone() {
+            Some(s) => s,
             None => return,
         };
 
         // Update page title with conflict count
         let count = conflicts.len();
         if count > 0 {
-            self.set_title(&format!("{} ({})", gettext("Conflicts"), count));
+            self.set_title(
+                &ngettext("Conflicts ({})", "Conflicts ({})", count as u32)
+                    .replace("{}", &count.to_string()),
+            );
         } else {
             self.set_title(&gettext("Conflicts"));
         }
 
-        // Rebuild the group each time. For small conflict counts (<100)
-        // this is perfectly fine.
-        self.remove(&group);
+        // Forget selected conflicts that are gone (resolved elsewhere).
+        imp.selected_ids
+            .borrow_mut()
+            .retain(|id| conflicts.iter().any(|c| &c.id == id));
+        self.update_selection_bar();
 
-        let new_group = adw::PreferencesGroup::builder()
-            .title(&gettext("Unresolved Conflicts"))
-            .build();
+        let objects: Vec<ConflictObject> = conflicts.iter().map(ConflictObject::from_info).collect();
+        store.splice(0, store.n_items(), &objects);
+        self.update_empty_state();
+    }
 
-        let resolve_all_button = gtk4::Button::builder()
-            .label(&gettext("Resolve All"))
-            .css_classes(["flat"])
+    /// Show the list, the "in sync" row or the "no match" label, depending
+    /// on whether there are conflicts and whether any match the search.
+    fn update_empty_state(&self) {
+        let imp = self.imp();
+        let total = imp.store.borrow().as_ref().map_or(0, |s| s.n_items());
+        let shown = imp
+            .filter_model
+            .borrow()
+            .as_ref()
+            .map_or(0, |m| m.n_items());
+
+        if let Some(ref entry) = *imp.search_entry.borrow() {
+            entry.set_visible(total > 0);
+        }
+        if let Some(ref scrolled) = *imp.scrolled.borrow() {
+            scrolled.set_visible(shown > 0);
+        }
+        if let Some(ref label) = *imp.no_match_label.borrow() {
+            label.set_visible(total > 0 && shown == 0);
+        }
+        if let Some(ref list) = *imp.empty_list.borrow() {
+            list.set_visible(total == 0);
+        }
+    }
+
+    /// Build the "N selected" bar with one button per strategy.
+    fn build_selection_bar(&self) -> gtk4::Box {
+        let imp = self.imp();
+        let bar = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
             .build();
 
-        let page = self.clone();
-        resolve_all_button.connect_clicked(move |_| {
-            page.show_resolve_all_dialog();
-        });
-        new_group.set_header_suffix(Some(&resolve_all_button));
+        let label = gtk4::Label::builder()
+            .halign(gtk4::Align::Start)
+            .hexpand(true)
+            .css_classes(["heading"])
+            .build();
+        bar.append(&label);
 
-        if conflicts.is_empty() {
-            let empty_row = adw::ActionRow::builder()
-                .title(&gettext("No unresolved conflicts"))
-                .subtitle(&gettext("All files are in sync"))
+        for (i, strategy_label) in STRATEGY_LABELS.iter().enumerate() {
+            let button = gtk4::Button::builder()
+                .label(&gettext(*strategy_label))
                 .build();
-            empty_row.add_prefix(&gtk4::Image::from_icon_name("emblem-ok-symbolic"));
-            new_group.add(&empty_row);
-        } else {
-            for conflict in conflicts {
-                let row = adw::ActionRow::builder()
-                    .title(conflict.filename())
-                    .subtitle(&conflict.item_path)
-                    .activatable(true)
-                    .build();
-                row.add_prefix(&gtk4::Image::from_icon_name(
-                    "dialog-warning-symbolic",
-                ));
-                row.add_suffix(&gtk4::Image::from_icon_name(
-                    "go-next-symbolic",
-                ));
-
-                // Connect click to open detail dialog
-                let client = imp.dbus_client.borrow().clone();
-                let conflict_clone = conflict.clone();
-                let page_ref = self.clone();
-                row.connect_activated(move |_| {
-                    if let Some(ref client) = client {
-                        let dialog =
-                            ConflictDetailDialog::new(&conflict_clone, client);
-                        // Present on the nearest toplevel
-                        dialog.present(Some(&page_ref));
-                    }
-                });
+            let page = self.clone();
+            let strategy = STRATEGY_VALUES[i];
+            button.connect_clicked(move |_| {
+                page.resolve_selected(strategy);
+            });
+            bar.append(&button);
+        }
+
+        imp.selection_label.replace(Some(label));
+        imp.selection_bar.replace(Some(bar.clone()));
+        bar
+    }
 
-                new_group.add(&row);
+    fn set_conflict_selected(&self, id: &str, selected: bool) {
+        {
+            let mut ids = self.imp().selected_ids.borrow_mut();
+            if selected {
+                ids.insert(id.to_string());
+            } else {
+                ids.remove(id);
             }
         }
+        self.update_selection_bar();
+    }
 
-        imp.conflicts_group.replace(Some(new_group.clone()));
-        self.add(&new_group);
+    /// Show the selection count, or hide the bar when nothing is selected.
+    fn update_selection_bar(&self) {
+        let imp = self.imp();
+        let count = imp.selected_ids.borrow().len();
+        if let Some(ref label) = *imp.selection_label.borrow() {
+            label.set_label(
+                &ngettext("{} selected", "{} selected", count as u32)
+                    .replace("{}", &count.to_string()),
+            );
+        }
+        if let Some(ref bar) = *imp.selection_bar.borrow() {
+            bar.set_visible(count > 0);
+        }
     }
 
-    fn show_resolve_all_dialog(&self) {
+    /// Resolve the selected conflicts with `strategy`, then reload. Several
+    /// go to the daemon as one ResolveMany() call naming their ids, even when
+    /// every listed conflict is selected, so one detected since the last
+    /// reload is never resolved along with them. A daemon without
+    /// ResolveMany gets one Resolve() call per id instead.
+    fn resolve_selected(&self, strategy: &'static str) {
         let imp = self.imp();
         let client = match imp.dbus_client.borrow().clone() {
             Some(c) => c,
             None => return,
         };
+        let ids: Vec<String> = imp.selected_ids.borrow_mut().drain().collect();
+        self.update_selection_bar();
+        if ids.is_empty() {
+            return;
+        }
 
-        // Build a simple strategy chooser dialog
-        let dialog = adw::AlertDialog::builder()
-            .heading(&gettext("Resolve All Conflicts"))
-            .body(&gettext("Choose a strategy to apply to all unresolved conflicts."))
-            .build();
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let result = if ids.len() == 1 {
+                client
+                    .resolve_conflict(&ids[0], strategy)
+                    .await
+                    .map(u32::from)
+            } else {
+                match client.resolve_many_conflicts(&ids, strategy).await {
+                    // Older daemons lack ResolveMany; resolve one by one.
+                    Err(e) if e.is_unknown_method() => {
+                        Ok(Self::resolve_each(&client, &ids, strategy).await)
+                    }
+                    result => result,
+                }
+            };
+            let resolved = match result {
+                Ok(n) => n,
+                Err(e) => {
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to resolve conflicts"),
+                        e,
+                    ));
+                    page.load_conflicts();
+                    return;
+                }
+            };
+            let failed = (ids.len() as u32).saturating_sub(resolved);
 
-        dialog.add_response("cancel", &gettext("Cancel"));
+            page.load_conflicts();
+            let label = strategy_label(strategy);
+            let mut message = ngettext(
+                "{count} conflict resolved with {strategy}",
+                "{count} conflicts resolved with {strategy}",
+                resolved,
+            )
+            .replace("{count}", &resolved.to_string())
+            .replace("{strategy}", &label);
+            if failed > 0 {
+                message = format!(
+                    "{}. {}",
+                    message,
+                    ngettext(
+                        "{} could not be resolved",
+                        "{} could not be resolved",
+                        failed,
+                    )
+                    .replace("{}", &failed.to_string())
+                );
+            }
+            page.show_toast(&message);
+        });
+    }
+
+    /// Resolve `ids` with one Resolve call each, for daemons without
+    /// ResolveMany. Returns how many were resolved.
+    async fn resolve_each(client: &DbusClient, ids: &[String], strategy: &str) -> u32 {
+        let mut resolved: u32 = 0;
+        for id in ids {
+            match client.resolve_conflict(id, strategy).await {
+                Ok(true) => resolved += 1,
+                Ok(false) => {}
+                Err(e) => eprintln!("Could not resolve conflict {id}: {e}"),
+            }
+        }
+        resolved
+    }
+
+    fn show_resolve_all_dialog(&self) {
+        let imp = self.imp();
+        let client = match imp.dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        // Build a simple strategy chooser dialog. No strategy is the default:
+        // Enter and Escape both cancel, so a strategy is only ever applied by
+        // choosing its button.
+        let dialog = util::confirmation_dialog(
+            &gettext("Resolve All Conflicts"),
+            &gettext("Choose a strategy to apply to all unresolved conflicts."),
+        );
         for (i, label) in STRATEGY_LABELS.iter().enumerate() {
             dialog.add_response(STRATEGY_VALUES[i], &gettext(*label));
         }
-        dialog.set_default_response(Some("cancel"));
-        dialog.set_close_response("cancel");
 
         let page = self.clone();
         dialog.connect_response(None, move |_, response| {
@@ -311,12 +913,16 @@ impl ConflictListPage {
                 match client_clone.resolve_all_conflicts(&strategy).await {
                     Ok(count) => {
                         page_clone.load_conflicts();
-                        page_clone.show_toast(&format!(
-                            "{} {} {}",
-                            count,
-                            gettext("conflicts resolved with"),
-                            gettext(&strategy),
-                        ));
+                        let label = strategy_label(&strategy);
+                        page_clone.show_toast(
+                            &ngettext(
+                                "{count} conflict resolved with {strategy}",
+                                "{count} conflicts resolved with {strategy}",
+                                count,
+                            )
+                            .replace("{count}", &count.to_string())
+                            .replace("{strategy}", &label),
+                        );
                     }
                     Err(e) => {
                         page_clone.show_toast(&format!(
@@ -345,3 +951,156 @@ impl ConflictListPage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::{
+        find_widgets, present, run_widget_test, wait_until, MockState, TestDaemon, SYNC_ROOT,
+    };
+
+    /// File names shown in the conflict rows, top to bottom.
+    fn conflict_rows(page: &ConflictListPage) -> Vec<String> {
+        find_widgets::<gtk4::Box>(page)
+            .iter()
+            .filter(|row| row.has_css_class("conflict-row"))
+            .filter_map(|row| find_widgets::<gtk4::Label>(row).first().map(|l| l.label()))
+            .map(|label| label.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn lists_conflicts_newest_first() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let page = ConflictListPage::new(daemon.client());
+            let window = present(&page);
+
+            wait_until("the conflict rows", || !conflict_rows(&page).is_empty());
+            assert_eq!(conflict_rows(&page), ["team-notes.docx", "budget.xlsx"]);
+            assert_eq!(page.title(), "Conflicts (2)");
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn reopens_a_resolved_conflict() {
+        run_widget_test(|| {
+            let mut state = MockState::default();
+            state.resolve("conflict-001", "keep_local");
+            let daemon = TestDaemon::start(state);
+            let page = ConflictListPage::new(daemon.client());
+            let window = present(&page);
+            // Expanded, so the Re-open buttons are on screen.
+            let expander = page.imp().history_row.borrow().clone().unwrap();
+            expander.set_expanded(true);
+
+            wait_until("the resolution history", || {
+                !page.imp().history_rows.borrow().is_empty()
+            });
+            assert_eq!(conflict_rows(&page), ["team-notes.docx"]);
+            let row = page.imp().history_rows.borrow()[0].clone();
+            assert_eq!(row.title(), "budget.xlsx");
+            let subtitle = row.subtitle().unwrap_or_default();
+            assert!(
+                subtitle.starts_with(&strategy_label("keep_local")),
+                "{subtitle}"
+            );
+
+            let reopen = |page: &ConflictListPage| {
+                find_widgets::<gtk4::Button>(page)
+                    .into_iter()
+                    .find(|b| b.label().is_some_and(|l| l == gettext("Re-open")))
+            };
+            reopen(&page).expect("a Re-open button").emit_clicked();
+
+            wait_until("the re-opened conflict", || conflict_rows(&page).len() == 2);
+            assert!(conflict_rows(&page).contains(&"budget.xlsx".to_string()));
+            wait_until("the history to update", || reopen(&page).is_none());
+
+            window.destroy();
+        });
+    }
+
+    /// Tick every conflict row, add a conflict on the daemon that the page
+    /// has not listed yet and resolve the selection with Keep Remote.
+    fn resolve_all_listed(daemon: &TestDaemon, page: &ConflictListPage) {
+        wait_until("the conflict rows", || conflict_rows(page).len() == 2);
+        for row in find_widgets::<gtk4::Box>(page) {
+            if row.has_css_class("conflict-row") {
+                row.first_child()
+                    .and_downcast::<gtk4::CheckButton>()
+                    .expect("A conflict row starts with its CheckButton")
+                    .set_active(true);
+            }
+        }
+        let mut detected = daemon.state().conflicts[0].clone();
+        detected["id"] = "conflict-003".into();
+        detected["item_path"] = format!("{SYNC_ROOT}/notes.txt").into();
+        daemon.state().conflicts.push(detected);
+
+        let bar = page.imp().selection_bar.borrow().clone().unwrap();
+        find_widgets::<gtk4::Button>(&bar)
+            .into_iter()
+            .find(|b| b.label().is_some_and(|l| l == gettext("Keep Remote")))
+            .expect("a Keep Remote button")
+            .emit_clicked();
+        wait_until("the selection to be resolved", || {
+            conflict_rows(page) == ["notes.txt"]
+        });
+    }
+
+    /// The ids of each call, sorted, since the page sends a set.
+    fn resolve_calls(daemon: &TestDaemon) -> Vec<(&'static str, Vec<String>)> {
+        let mut calls = daemon.state().resolve_calls.clone();
+        for (_, ids) in &mut calls {
+            ids.sort();
+        }
+        calls.sort();
+        calls
+    }
+
+    #[test]
+    fn resolves_the_selection_by_its_ids() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let page = ConflictListPage::new(daemon.client());
+            let window = present(&page);
+
+            resolve_all_listed(&daemon, &page);
+            assert_eq!(
+                resolve_calls(&daemon),
+                [(
+                    "ResolveMany",
+                    vec!["conflict-001".to_string(), "conflict-002".to_string()]
+                )]
+            );
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn resolves_one_by_one_without_resolve_many() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState {
+                resolve_many_supported: false,
+                ..MockState::default()
+            });
+            let page = ConflictListPage::new(daemon.client());
+            let window = present(&page);
+
+            resolve_all_listed(&daemon, &page);
+            assert_eq!(
+                resolve_calls(&daemon),
+                [
+                    ("Resolve", vec!["conflict-001".to_string()]),
+                    ("Resolve", vec!["conflict-002".to_string()]),
+                ]
+            );
+
+            window.destroy();
+        });
+    }
+}
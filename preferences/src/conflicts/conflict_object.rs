@@ -0,0 +1,104 @@
+// ConflictObject — glib::Object wrapper around ConflictInfo
+//
+// Lets conflicts live in a `gio::ListStore` for model-driven lists, and
+// exposes the fields views display as read-only properties so widgets can
+// bind to them and filters can match them through `gtk::PropertyExpression`.
+// The full ConflictInfo stays available through `info()`.
+
+use std::cell::RefCell;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
+
+use super::conflict_dialog::ConflictInfo;
+
+mod imp {
+    use super::*;
+    use gtk4::subclass::prelude::*;
+
+    #[derive(Default)]
+    pub struct ConflictObject {
+        pub info: RefCell<Option<ConflictInfo>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ConflictObject {
+        const NAME: &'static str = "LnxdriveConflictObject";
+        type Type = super::ConflictObject;
+        type ParentType = glib::Object;
+    }
+
+    impl ObjectImpl for ConflictObject {
+        fn properties() -> &'static [glib::ParamSpec] {
+            use std::sync::OnceLock;
+            static PROPERTIES: OnceLock<Vec<glib::ParamSpec>> = OnceLock::new();
+            PROPERTIES.get_or_init(|| {
+                vec![
+                    glib::ParamSpecString::builder("id").read_only().build(),
+                    glib::ParamSpecString::builder("filename").read_only().build(),
+                    glib::ParamSpecString::builder("path").read_only().build(),
+                    // ISO 8601, as sent by the daemon.
+                    glib::ParamSpecString::builder("detected-at")
+                        .read_only()
+                        .build(),
+                    glib::ParamSpecUInt64::builder("local-size")
+                        .read_only()
+                        .build(),
+                    glib::ParamSpecUInt64::builder("remote-size")
+                        .read_only()
+                        .build(),
+                ]
+            })
+        }
+
+        fn property(&self, _id: usize, pspec: &glib::ParamSpec) -> glib::Value {
+            let info = self.info.borrow();
+            let Some(info) = info.as_ref() else {
+                return pspec.default_value().clone();
+            };
+            match pspec.name() {
+                "id" => info.id.to_value(),
+                "filename" => info.filename().to_value(),
+                "path" => info.item_path.to_value(),
+                "detected-at" => info.detected_at.to_value(),
+                "local-size" => info.local_size.to_value(),
+                "remote-size" => info.remote_size.to_value(),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct ConflictObject(ObjectSubclass<imp::ConflictObject>);
+}
+
+impl ConflictObject {
+    pub fn from_info(info: &ConflictInfo) -> Self {
+        let obj: Self = glib::Object::new();
+        obj.imp().info.replace(Some(info.clone()));
+        obj
+    }
+
+    /// The wrapped conflict.
+    pub fn info(&self) -> ConflictInfo {
+        self.imp()
+            .info
+            .borrow()
+            .clone()
+            .expect("ConflictObject is always created from a ConflictInfo")
+    }
+
+    pub fn id(&self) -> String {
+        self.property("id")
+    }
+
+    pub fn filename(&self) -> String {
+        self.property("filename")
+    }
+
+    pub fn path(&self) -> String {
+        self.property("path")
+    }
+}
@@ -3,8 +3,16 @@
 // Provides UI components for conflict detection and resolution:
 // - ConflictDetailDialog: side-by-side details with resolution options
 // - ConflictListPage: lists all unresolved conflicts with batch actions
+// - ConflictObject: glib::Object wrapper of a conflict for list models and
+//   property bindings
+//
+// Right-to-left layouts: horizontal icons use the standard names
+// ("go-next-symbolic") whose "-rtl" variants GTK selects automatically from
+// the widget's text direction — do not swap them by hand, or they flip twice.
+// Vertical icons (go-up/go-down for upload/download) need no mirroring.
 
 pub mod conflict_dialog;
 pub mod conflict_list;
+pub mod conflict_object;
 
 pub use conflict_list::ConflictListPage;
@@ -3,6 +3,8 @@
 // Provides a high-level async wrapper around the LNXDrive daemon's D-Bus interfaces.
 // Uses zbus with the default async-io runtime so that futures are compatible with
 // glib::MainContext::spawn_local() — do NOT use tokio for D-Bus operations.
+// Every call is raced against a glib timeout so a hung daemon can't leave the
+// UI spinning forever.
 //
 // D-Bus coordinates:
 //   Bus name:    com.enigmora.LNXDrive
@@ -20,10 +22,22 @@
 //   - CloudOnly = 'cloud-only' (D-Bus string) = 'placeholder' (user-facing term)
 //   - UnpinFile = unpin + dehydrate (makes file cloud-only, frees local space)
 //   - PinFile   = hydrate + pin (downloads file, keeps local)
+//   - Sync root = one remote drive or document library mapped to a local
+//                 folder. Business accounts may have several; each has its
+//                 own selected-folders set, addressed by `root_id`. Daemons
+//                 from before multiple roots only have the root-less
+//                 Get/SetSelectedFolders and GetRemoteFolderTree, which act
+//                 on their single root; `DEFAULT_ROOT_ID` falls back to them.
 
 use std::collections::HashMap;
 use std::fmt;
+use std::future::Future;
+use std::time::Duration;
 
+use futures_util::future::{self, Either};
+use futures_util::{Stream, StreamExt};
+use gtk4::glib;
+use serde::Deserialize;
 use zbus::zvariant::OwnedValue;
 use zbus::{proxy, Connection};
 
@@ -31,20 +45,93 @@ use zbus::{proxy, Connection};
 // Error type
 // ---------------------------------------------------------------------------
 
+/// Well-known bus name owned by the daemon.
+pub const BUS_NAME: &str = "com.enigmora.LNXDrive";
+
+/// Prefix of the D-Bus error names the daemon replies with.
+const DAEMON_ERROR_PREFIX: &str = "com.enigmora.LNXDrive.Error.";
+
+/// Classification of application-level daemon errors, so the UI can react
+/// to specific failures (e.g. start re-authentication on `NotAuthenticated`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonErrorKind {
+    /// `com.enigmora.LNXDrive.Error.NotAuthenticated` — the token is missing
+    /// or expired.
+    NotAuthenticated,
+    /// `com.enigmora.LNXDrive.Error.QuotaExceeded` — no space left in the cloud.
+    QuotaExceeded,
+    /// `com.enigmora.LNXDrive.Error.NetworkUnavailable` — the daemon is offline.
+    NetworkUnavailable,
+    /// The daemon did not reply in time.
+    TimedOut,
+    /// The daemon replied with data we could not parse.
+    InvalidReply,
+    /// Any other daemon error name.
+    Other,
+}
+
+impl DaemonErrorKind {
+    /// Map a D-Bus error name such as
+    /// `com.enigmora.LNXDrive.Error.QuotaExceeded` to its kind.
+    fn from_error_name(name: &str) -> Self {
+        match name.strip_prefix(DAEMON_ERROR_PREFIX) {
+            Some("NotAuthenticated") => Self::NotAuthenticated,
+            Some("QuotaExceeded") => Self::QuotaExceeded,
+            Some("NetworkUnavailable") => Self::NetworkUnavailable,
+            _ => Self::Other,
+        }
+    }
+}
+
 /// Errors that can occur when communicating with the LNXDrive daemon over D-Bus.
 #[derive(Debug)]
 pub enum DbusError {
     /// The D-Bus session bus could not be connected to, or a method call failed.
     Zbus(zbus::Error),
-    /// The daemon returned an application-level error message.
-    Daemon(String),
+    /// The daemon returned an application-level error. `message` is meant
+    /// for display.
+    Daemon {
+        kind: DaemonErrorKind,
+        message: String,
+    },
+}
+
+impl DbusError {
+    fn daemon(kind: DaemonErrorKind, message: impl Into<String>) -> Self {
+        Self::Daemon {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// The daemon error kind, or `None` for transport-level failures.
+    pub fn kind(&self) -> Option<DaemonErrorKind> {
+        match self {
+            Self::Zbus(_) => None,
+            Self::Daemon { kind, .. } => Some(*kind),
+        }
+    }
+
+    /// Whether the daemon does not implement the called method, as older
+    /// daemons do for methods added since.
+    pub fn is_unknown_method(&self) -> bool {
+        match self {
+            Self::Zbus(zbus::Error::MethodError(name, _, _)) => {
+                name.as_str() == "org.freedesktop.DBus.Error.UnknownMethod"
+            }
+            Self::Zbus(zbus::Error::FDO(e)) => {
+                matches!(**e, zbus::fdo::Error::UnknownMethod(_))
+            }
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for DbusError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Zbus(e) => write!(f, "D-Bus error: {e}"),
-            Self::Daemon(msg) => write!(f, "Daemon error: {msg}"),
+            Self::Daemon { message, .. } => write!(f, "Daemon error: {message}"),
         }
     }
 }
@@ -53,13 +140,23 @@ impl std::error::Error for DbusError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Zbus(e) => Some(e),
-            Self::Daemon(_) => None,
+            Self::Daemon { .. } => None,
         }
     }
 }
 
 impl From<zbus::Error> for DbusError {
     fn from(e: zbus::Error) -> Self {
+        // Errors raised by the daemon itself carry a name in our namespace;
+        // everything else (no such method, bus disconnected, ...) stays a
+        // transport error.
+        if let zbus::Error::MethodError(ref name, ref message, _) = e {
+            if name.as_str().starts_with(DAEMON_ERROR_PREFIX) {
+                let kind = DaemonErrorKind::from_error_name(name.as_str());
+                let message = message.clone().unwrap_or_else(|| name.to_string());
+                return Self::daemon(kind, message);
+            }
+        }
         Self::Zbus(e)
     }
 }
@@ -106,10 +203,26 @@ trait LnxdriveSettings {
     /// Replace the full configuration with the supplied YAML string.
     async fn set_config(&self, yaml: &str) -> zbus::Result<()>;
 
-    /// Return the list of folder paths selected for sync.
+    /// Return the configured sync roots as a JSON array of
+    /// `{"id", "name", "local_path"}` objects.
+    async fn get_sync_roots(&self) -> zbus::Result<String>;
+
+    /// Return the folders selected for sync under `root_id`: each folder's
+    /// tree `id`, or its path when the tree gives it no id.
+    async fn get_root_selected_folders(&self, root_id: &str) -> zbus::Result<Vec<String>>;
+
+    /// Set the folders selected for sync under `root_id`, identified as in
+    /// `get_root_selected_folders`.
+    async fn set_root_selected_folders(
+        &self,
+        root_id: &str,
+        folders: &[String],
+    ) -> zbus::Result<()>;
+
+    /// Single-root form of `get_root_selected_folders`.
     async fn get_selected_folders(&self) -> zbus::Result<Vec<String>>;
 
-    /// Set the list of folder paths selected for sync.
+    /// Single-root form of `set_root_selected_folders`.
     async fn set_selected_folders(&self, folders: &[String]) -> zbus::Result<()>;
 
     /// Return the list of exclusion glob patterns.
@@ -118,10 +231,55 @@ trait LnxdriveSettings {
     /// Set the list of exclusion glob patterns.
     async fn set_exclusion_patterns(&self, patterns: &[String]) -> zbus::Result<()>;
 
-    /// Return the remote folder tree as a JSON string.
+    /// Return up to `limit` remote paths that `pattern` would exclude.
+    async fn test_exclusion_pattern(&self, pattern: &str, limit: u32) -> zbus::Result<Vec<String>>;
+
+    /// Return the remote folder tree of `root_id` as a JSON string.
+    async fn get_root_remote_folder_tree(&self, root_id: &str) -> zbus::Result<String>;
+
+    /// Single-root form of `get_root_remote_folder_tree`.
     async fn get_remote_folder_tree(&self) -> zbus::Result<String>;
 }
 
+/// com.enigmora.LNXDrive.Files — per-file status and hydration. Paths are
+/// absolute local paths under a sync root.
+#[proxy(
+    interface = "com.enigmora.LNXDrive.Files",
+    default_service = "com.enigmora.LNXDrive",
+    default_path = "/com/enigmora/LNXDrive"
+)]
+trait LnxdriveFiles {
+    /// Return how much of each path is stored locally, keyed by path:
+    /// "cloud-only", "partial" (folders only) or "hydrated".
+    async fn get_hydration_states(&self, paths: &[String]) -> zbus::Result<HashMap<String, String>>;
+
+    /// Return the bytes each path takes on local disk, keyed by path. Only
+    /// downloaded content counts; placeholders take no space.
+    async fn get_folder_sizes(&self, paths: &[String]) -> zbus::Result<HashMap<String, u64>>;
+
+    /// Download `path` (recursively for folders) and keep it local.
+    async fn pin_file(&self, path: &str) -> zbus::Result<()>;
+
+    /// Make `path` (recursively for folders) cloud-only, freeing local space.
+    async fn unpin_file(&self, path: &str) -> zbus::Result<()>;
+
+    /// Make every downloaded file under every sync root cloud-only. Reports
+    /// progress with Sync.SyncProgress and returns the bytes freed.
+    async fn free_up_all_space(&self) -> zbus::Result<u64>;
+
+    /// Whether the folder at `path` is shared and with whom, as JSON
+    /// `{"shared": bool, "collaborators": [{"name", "email"}]}`.
+    async fn get_shared_info(&self, path: &str) -> zbus::Result<String>;
+
+    /// Return items deleted in the cloud and then removed locally, as a JSON
+    /// array of objects with id, path, deleted_at (ISO 8601) and restorable.
+    async fn list_deleted(&self) -> zbus::Result<String>;
+
+    /// Restore a deleted item in the cloud and on disk. Returns false if it
+    /// can no longer be restored.
+    async fn restore_deleted(&self, id: &str) -> zbus::Result<bool>;
+}
+
 /// com.enigmora.LNXDrive.Status — account and quota information
 #[proxy(
     interface = "com.enigmora.LNXDrive.Status",
@@ -134,6 +292,43 @@ trait LnxdriveStatus {
 
     /// Return a dict of account metadata (display_name, email, etc.).
     async fn get_account_info(&self) -> zbus::Result<HashMap<String, OwnedValue>>;
+
+    /// Return transfer byte counters keyed by "session_uploaded",
+    /// "session_downloaded", "total_uploaded" and "total_downloaded".
+    async fn get_transfer_stats(&self) -> zbus::Result<HashMap<String, u64>>;
+
+    /// Reset the session transfer counters to zero.
+    async fn reset_transfer_stats(&self) -> zbus::Result<()>;
+
+    /// Return the in-flight transfers as a JSON array of objects with
+    /// path, direction ("upload"/"download"), bytes_done, bytes_total, speed.
+    async fn get_active_transfers(&self) -> zbus::Result<String>;
+
+    /// Return the most recent daemon-level error as (message, ISO 8601
+    /// timestamp); both are empty when there is none.
+    async fn get_last_error(&self) -> zbus::Result<(String, String)>;
+
+    /// Acknowledge the last error; GetLastError then returns empty strings.
+    async fn clear_last_error(&self) -> zbus::Result<()>;
+
+    /// Make a lightweight Graph API request and return (success,
+    /// round-trip milliseconds, error message if it failed).
+    async fn ping_remote(&self) -> zbus::Result<(bool, u32, String)>;
+}
+
+/// com.enigmora.LNXDrive.Manager — daemon lifecycle and diagnostics
+#[proxy(
+    interface = "com.enigmora.LNXDrive.Manager",
+    default_service = "com.enigmora.LNXDrive",
+    default_path = "/com/enigmora/LNXDrive"
+)]
+trait LnxdriveManager {
+    /// The last `lines` lines of the daemon log, oldest first.
+    async fn get_recent_logs(&self, lines: u32) -> zbus::Result<Vec<String>>;
+
+    /// Daemon version string.
+    #[zbus(property)]
+    fn version(&self) -> zbus::Result<String>;
 }
 
 /// com.enigmora.LNXDrive.Sync — sync control
@@ -142,7 +337,7 @@ trait LnxdriveStatus {
     default_service = "com.enigmora.LNXDrive",
     default_path = "/com/enigmora/LNXDrive"
 )]
-trait LnxdriveSync {
+pub trait LnxdriveSync {
     /// Trigger an immediate sync cycle.
     async fn sync_now(&self) -> zbus::Result<()>;
 
@@ -151,6 +346,28 @@ trait LnxdriveSync {
 
     /// Resume sync.
     async fn resume(&self) -> zbus::Result<()>;
+
+    /// Return items that failed to sync as a JSON array of objects with
+    /// path, message, timestamp, kind ("transient"/"permanent") and
+    /// failed_permanently (the daemon stopped retrying the item).
+    async fn get_sync_errors(&self) -> zbus::Result<String>;
+
+    /// Retry syncing a single failed item.
+    async fn retry_item(&self, path: &str) -> zbus::Result<()>;
+
+    /// Re-scan all local and remote state and repair drift, emitting
+    /// SyncProgress along the way. Returns the number of items repaired.
+    async fn verify_integrity(&self) -> zbus::Result<u32>;
+
+    /// Emitted per file while the daemon works through a batch: the file
+    /// and its 1-based position among `total`.
+    #[zbus(signal)]
+    fn sync_progress(&self, file: &str, current: u32, total: u32) -> zbus::Result<()>;
+
+    /// Global sync state: "idle", "syncing", "paused", "rate_limited" (the
+    /// API is throttling the daemon), or "error".
+    #[zbus(property)]
+    fn sync_status(&self) -> zbus::Result<String>;
 }
 
 /// com.enigmora.LNXDrive.Conflicts — conflict detection and resolution
@@ -163,7 +380,9 @@ pub trait LnxdriveConflicts {
     /// List all unresolved conflicts as a JSON array.
     async fn list(&self) -> zbus::Result<String>;
 
-    /// Get details for a specific conflict by ID. Returns JSON.
+    /// Get details for a specific conflict by ID. Returns JSON. After a
+    /// "keep_both" resolution it includes `renamed_copy`, the path the local
+    /// version was moved to.
     async fn get_details(&self, id: &str) -> zbus::Result<String>;
 
     /// Resolve a conflict with the given strategy ("keep_local", "keep_remote", "keep_both").
@@ -174,6 +393,20 @@ pub trait LnxdriveConflicts {
     /// Returns the number of conflicts resolved.
     async fn resolve_all(&self, strategy: &str) -> zbus::Result<u32>;
 
+    /// Resolve the given conflicts with one strategy, applied atomically.
+    /// Returns the number of conflicts resolved; IDs that are unknown or
+    /// already resolved are skipped.
+    async fn resolve_many(&self, ids: &[String], strategy: &str) -> zbus::Result<u32>;
+
+    /// The most recent resolutions, newest first, as a JSON array of
+    /// `{id, item_path, strategy, resolved_at, reopenable}`. At most `limit`
+    /// entries.
+    async fn get_resolution_history(&self, limit: u32) -> zbus::Result<String>;
+
+    /// Undo a resolution so the conflict is unresolved again. Only valid for
+    /// history entries marked `reopenable`. Returns true on success.
+    async fn reopen(&self, id: &str) -> zbus::Result<bool>;
+
     /// Emitted when a new conflict is detected.
     #[zbus(signal)]
     fn conflict_detected(&self, conflict_json: &str) -> zbus::Result<()>;
@@ -183,15 +416,215 @@ pub trait LnxdriveConflicts {
     fn conflict_resolved(&self, conflict_id: &str, strategy: &str) -> zbus::Result<()>;
 }
 
+// ---------------------------------------------------------------------------
+// Typed results
+// ---------------------------------------------------------------------------
+
+/// Bytes transferred, for the current daemon session and since install.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransferStats {
+    pub session_uploaded: u64,
+    pub session_downloaded: u64,
+    pub total_uploaded: u64,
+    pub total_downloaded: u64,
+}
+
+impl TransferStats {
+    /// Build from the daemon's counter dict. Missing keys count as zero.
+    fn from_map(map: &HashMap<String, u64>) -> Self {
+        let get = |key: &str| map.get(key).copied().unwrap_or(0);
+        Self {
+            session_uploaded: get("session_uploaded"),
+            session_downloaded: get("session_downloaded"),
+            total_uploaded: get("total_uploaded"),
+            total_downloaded: get("total_downloaded"),
+        }
+    }
+}
+
+/// A background failure reported by `GetLastError`, such as a failed token
+/// refresh or API rate limiting. For rate limiting the message carries the
+/// server's `Retry-After: <seconds>`, counted from `timestamp`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastError {
+    pub message: String,
+    /// ISO 8601, as sent by the daemon.
+    pub timestamp: String,
+}
+
+impl LastError {
+    /// When the error occurred in the user's local time zone and locale
+    /// format, or the raw timestamp if it cannot be parsed.
+    pub fn occurred_absolute(&self) -> String {
+        glib::DateTime::from_iso8601(&self.timestamp, Some(&glib::TimeZone::utc()))
+            .ok()
+            .and_then(|dt| dt.to_local().ok())
+            .and_then(|dt| dt.format("%c").ok())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| self.timestamp.clone())
+    }
+
+    /// When a rate-limited daemon will retry, from the `Retry-After` in the
+    /// message. `None` for other errors or an unparsable timestamp.
+    pub fn retry_after(&self) -> Option<glib::DateTime> {
+        const MARKER: &str = "retry-after:";
+        let lower = self.message.to_ascii_lowercase();
+        let rest = &self.message[lower.find(MARKER)? + MARKER.len()..];
+        let digits: String = rest
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        let seconds: f64 = digits.parse().ok()?;
+        glib::DateTime::from_iso8601(&self.timestamp, Some(&glib::TimeZone::utc()))
+            .ok()?
+            .add_seconds(seconds)
+            .ok()
+    }
+}
+
+/// Account metadata returned by `GetAccountInfo`.
+#[derive(Debug, Clone)]
+pub struct AccountInfo {
+    pub email: Option<String>,
+    pub display_name: Option<String>,
+    /// Free-text account type as reported by the daemon (e.g. "Personal",
+    /// "Business"). Defaults to "Personal" when the daemon omits it.
+    pub account_type: String,
+    /// When the daemon last fetched the account info and quota from
+    /// OneDrive (ISO 8601). Only sent while it is serving cached values,
+    /// e.g. when offline.
+    pub as_of: Option<String>,
+}
+
+impl AccountInfo {
+    fn from_map(map: &HashMap<String, OwnedValue>) -> Self {
+        let get = |key: &str| {
+            map.get(key)
+                .and_then(|v| String::try_from(v.clone()).ok())
+                .filter(|s| !s.is_empty())
+        };
+        Self {
+            email: get("email"),
+            display_name: get("display_name"),
+            account_type: get("account_type").unwrap_or_else(|| "Personal".to_string()),
+            as_of: get("as_of"),
+        }
+    }
+
+    /// `as_of` as a DateTime; `None` when the values are live or the
+    /// timestamp cannot be parsed.
+    pub fn as_of_datetime(&self) -> Option<glib::DateTime> {
+        let as_of = self.as_of.as_deref()?;
+        glib::DateTime::from_iso8601(as_of, Some(&glib::TimeZone::utc())).ok()
+    }
+
+    /// Whether this is a OneDrive for Business / SharePoint account. Business
+    /// accounts have organisation-managed quotas and expose SharePoint sites
+    /// alongside regular folders.
+    pub fn is_business(&self) -> bool {
+        let account_type = self.account_type.to_lowercase();
+        account_type.contains("business") || account_type.contains("sharepoint")
+    }
+}
+
+/// Who a folder is shared with, from `GetSharedInfo`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SharedInfo {
+    #[serde(default)]
+    pub shared: bool,
+    #[serde(default)]
+    pub collaborators: Vec<Collaborator>,
+}
+
+/// A person a folder is shared with.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Collaborator {
+    pub name: String,
+    #[serde(default)]
+    pub email: String,
+}
+
+/// How much of a path's content is stored locally, from `GetHydrationStates`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HydrationState {
+    /// Only a placeholder exists locally; the content is online-only.
+    /// 'cloud-only' on D-Bus.
+    Placeholder,
+    /// A folder holding both placeholders and downloaded files.
+    Partial,
+    /// Fully downloaded.
+    Hydrated,
+    /// Not under a sync root, or a state this client doesn't know.
+    #[default]
+    Unknown,
+}
+
+impl HydrationState {
+    pub fn from_dbus(value: &str) -> Self {
+        match value {
+            "cloud-only" => Self::Placeholder,
+            "partial" => Self::Partial,
+            "hydrated" => Self::Hydrated,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn as_dbus(&self) -> &'static str {
+        match self {
+            Self::Placeholder => "cloud-only",
+            Self::Partial => "partial",
+            Self::Hydrated => "hydrated",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Root ID the daemon assigns when it migrates a single-root configuration
+/// (top-level `sync_root` + `selected_folders`) to the multi-root layout.
+pub const DEFAULT_ROOT_ID: &str = "default";
+
+/// A remote drive or document library synced to a local folder.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncRoot {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub local_path: String,
+}
+
+impl SyncRoot {
+    /// The single root of a configuration that predates multiple roots.
+    pub fn legacy() -> Self {
+        Self {
+            id: DEFAULT_ROOT_ID.to_string(),
+            name: String::new(),
+            local_path: String::new(),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // High-level client
 // ---------------------------------------------------------------------------
 
+/// Default time to wait for a daemon reply.
+pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Time to wait for long-running operations such as a full sync.
+pub const LONG_CALL_TIMEOUT: Duration = Duration::from_secs(120);
+
 /// A convenience wrapper that holds a D-Bus connection and exposes typed async
 /// methods for every daemon operation.
 #[derive(Clone)]
 pub struct DbusClient {
     connection: Connection,
+    /// How long to wait for a reply before failing with "timed out".
+    pub timeout: Duration,
+    /// Timeout for every operation that makes the daemon do real work, such
+    /// as transferring or checking files, before it replies.
+    pub long_timeout: Duration,
 }
 
 impl DbusClient {
@@ -199,34 +632,78 @@ impl DbusClient {
     /// glib MainContext (e.g. via `glib::MainContext::default().spawn_local()`).
     pub async fn new() -> Result<Self, DbusError> {
         let connection = Connection::session().await?;
-        Ok(Self { connection })
+        Ok(Self::from_connection(connection))
+    }
+
+    /// Wrap an existing connection, e.g. to a private bus running the mock
+    /// daemon (`tests/mock-dbus-daemon.py --bus-address`).
+    pub fn from_connection(connection: Connection) -> Self {
+        Self {
+            connection,
+            timeout: DEFAULT_CALL_TIMEOUT,
+            long_timeout: LONG_CALL_TIMEOUT,
+        }
+    }
+
+    /// Await a proxy call, failing with `DaemonErrorKind::TimedOut` if
+    /// the daemon has not answered within `timeout`. Proxy creation belongs
+    /// inside `call` too, since it may round-trip to the daemon.
+    async fn call<T>(
+        &self,
+        timeout: Duration,
+        call: impl Future<Output = zbus::Result<T>>,
+    ) -> Result<T, DbusError> {
+        let call = std::pin::pin!(call);
+        match future::select(call, glib::timeout_future(timeout)).await {
+            Either::Left((result, _)) => Ok(result?),
+            Either::Right(_) => Err(DbusError::daemon(DaemonErrorKind::TimedOut, "timed out")),
+        }
+    }
+
+    /// Connect to the message bus at `address`, e.g.
+    /// `unix:path=/run/user/1000/lnxdrive-bus`.
+    pub async fn from_address(address: &str) -> Result<Self, DbusError> {
+        let connection = zbus::connection::Builder::address(address)?.build().await?;
+        Ok(Self::from_connection(connection))
     }
 
     // -- Auth ---------------------------------------------------------------
 
     /// Check whether the user is currently authenticated with the daemon.
     pub async fn is_authenticated(&self) -> Result<bool, DbusError> {
-        let proxy = LnxdriveAuthProxy::new(&self.connection).await?;
-        Ok(proxy.is_authenticated().await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveAuthProxy::new(&self.connection).await?;
+            proxy.is_authenticated().await
+        })
+        .await
     }
 
     /// Start the OAuth2 flow. Returns `(auth_url, state)`.
     /// The caller should open `auth_url` in the default browser.
     pub async fn start_auth(&self) -> Result<(String, String), DbusError> {
-        let proxy = LnxdriveAuthProxy::new(&self.connection).await?;
-        Ok(proxy.start_auth().await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveAuthProxy::new(&self.connection).await?;
+            proxy.start_auth().await
+        })
+        .await
     }
 
     /// Complete an auth flow manually (used by CLI or GOA integration).
     pub async fn complete_auth(&self, code: &str, state: &str) -> Result<bool, DbusError> {
-        let proxy = LnxdriveAuthProxy::new(&self.connection).await?;
-        Ok(proxy.complete_auth(code, state).await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveAuthProxy::new(&self.connection).await?;
+            proxy.complete_auth(code, state).await
+        })
+        .await
     }
 
     /// Log out the current user.
     pub async fn logout(&self) -> Result<(), DbusError> {
-        let proxy = LnxdriveAuthProxy::new(&self.connection).await?;
-        Ok(proxy.logout().await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveAuthProxy::new(&self.connection).await?;
+            proxy.logout().await
+        })
+        .await
     }
 
     /// Get a clone of the underlying D-Bus connection.
@@ -239,98 +716,480 @@ impl DbusClient {
         &self.connection
     }
 
+    // -- Daemon presence ----------------------------------------------------
+
+    /// Whether the daemon currently owns its bus name.
+    pub async fn daemon_running(&self) -> Result<bool, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = zbus::fdo::DBusProxy::new(&self.connection).await?;
+            let name = zbus::names::BusName::try_from(BUS_NAME)?;
+            Ok(proxy.name_has_owner(name).await?)
+        })
+        .await
+    }
+
+    /// Follow NameOwnerChanged for the daemon's bus name: yields `true` when
+    /// the daemon (re)appears on the bus and `false` when it goes away.
+    pub async fn receive_daemon_presence(
+        &self,
+    ) -> Result<impl Stream<Item = bool> + 'static, DbusError> {
+        let proxy = zbus::fdo::DBusProxy::new(&self.connection).await?;
+        let changes = proxy
+            .receive_name_owner_changed_with_args(&[(0, BUS_NAME)])
+            .await?;
+        Ok(changes.filter_map(|signal| {
+            future::ready(signal.args().ok().map(|args| args.new_owner().is_some()))
+        }))
+    }
+
     // -- Settings -----------------------------------------------------------
 
     /// Return the full configuration as YAML.
     pub async fn get_config(&self) -> Result<String, DbusError> {
-        let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
-        Ok(proxy.get_config().await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+            proxy.get_config().await
+        })
+        .await
     }
 
     /// Replace the configuration with the given YAML string.
     pub async fn set_config(&self, yaml: &str) -> Result<(), DbusError> {
-        let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
-        Ok(proxy.set_config(yaml).await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+            proxy.set_config(yaml).await
+        })
+        .await
+    }
+
+    /// List the configured sync roots. A daemon still on a single-root
+    /// configuration reports no roots, or lacks GetSyncRoots altogether;
+    /// that is presented as one root with `DEFAULT_ROOT_ID`, so callers
+    /// always get at least one entry.
+    pub async fn list_sync_roots(&self) -> Result<Vec<SyncRoot>, DbusError> {
+        let result = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+                proxy.get_sync_roots().await
+            })
+            .await;
+        let json = match result {
+            Err(e) if e.is_unknown_method() => return Ok(vec![SyncRoot::legacy()]),
+            result => result?,
+        };
+        let roots: Vec<SyncRoot> = serde_json::from_str(&json)
+            .map_err(|e| {
+                DbusError::daemon(
+                    DaemonErrorKind::InvalidReply,
+                    format!("Invalid sync roots JSON: {e}"),
+                )
+            })?;
+        if roots.is_empty() {
+            Ok(vec![SyncRoot::legacy()])
+        } else {
+            Ok(roots)
+        }
     }
 
-    /// Get the list of folders selected for sync.
-    pub async fn get_selected_folders(&self) -> Result<Vec<String>, DbusError> {
-        let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
-        Ok(proxy.get_selected_folders().await?)
+    /// Get the list of folders selected for sync under `root_id`.
+    pub async fn get_selected_folders(&self, root_id: &str) -> Result<Vec<String>, DbusError> {
+        let result = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+                proxy.get_root_selected_folders(root_id).await
+            })
+            .await;
+        match result {
+            Err(e) if e.is_unknown_method() && root_id == DEFAULT_ROOT_ID => {
+                self.call(self.timeout, async {
+                    let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+                    proxy.get_selected_folders().await
+                })
+                .await
+            }
+            result => result,
+        }
     }
 
-    /// Set the list of folders selected for sync.
-    pub async fn set_selected_folders(&self, folders: &[String]) -> Result<(), DbusError> {
-        let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
-        Ok(proxy.set_selected_folders(folders).await?)
+    /// Set the list of folders selected for sync under `root_id`.
+    pub async fn set_selected_folders(
+        &self,
+        root_id: &str,
+        folders: &[String],
+    ) -> Result<(), DbusError> {
+        let result = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+                proxy.set_root_selected_folders(root_id, folders).await
+            })
+            .await;
+        match result {
+            Err(e) if e.is_unknown_method() && root_id == DEFAULT_ROOT_ID => {
+                self.call(self.timeout, async {
+                    let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+                    proxy.set_selected_folders(folders).await
+                })
+                .await
+            }
+            result => result,
+        }
     }
 
     /// Get the list of exclusion glob patterns.
     pub async fn get_exclusion_patterns(&self) -> Result<Vec<String>, DbusError> {
-        let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
-        Ok(proxy.get_exclusion_patterns().await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+            proxy.get_exclusion_patterns().await
+        })
+        .await
     }
 
     /// Set the list of exclusion glob patterns.
     pub async fn set_exclusion_patterns(&self, patterns: &[String]) -> Result<(), DbusError> {
-        let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
-        Ok(proxy.set_exclusion_patterns(patterns).await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+            proxy.set_exclusion_patterns(patterns).await
+        })
+        .await
+    }
+
+    /// Return up to `limit` remote paths that `pattern` would exclude, for
+    /// previewing a glob before it is added.
+    pub async fn test_exclusion_pattern(
+        &self,
+        pattern: &str,
+        limit: u32,
+    ) -> Result<Vec<String>, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+            proxy.test_exclusion_pattern(pattern, limit).await
+        })
+        .await
+    }
+
+    /// Return the remote folder tree of `root_id` as a JSON string.
+    pub async fn get_remote_folder_tree(&self, root_id: &str) -> Result<String, DbusError> {
+        let result = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+                proxy.get_root_remote_folder_tree(root_id).await
+            })
+            .await;
+        match result {
+            Err(e) if e.is_unknown_method() && root_id == DEFAULT_ROOT_ID => {
+                self.call(self.timeout, async {
+                    let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
+                    proxy.get_remote_folder_tree().await
+                })
+                .await
+            }
+            result => result,
+        }
+    }
+
+    // -- Files --------------------------------------------------------------
+
+    /// Get the hydration state of several local paths at once, keyed by
+    /// path.
+    pub async fn get_hydration_states(
+        &self,
+        paths: &[String],
+    ) -> Result<HashMap<String, HydrationState>, DbusError> {
+        let states = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveFilesProxy::new(&self.connection).await?;
+                proxy.get_hydration_states(paths).await
+            })
+            .await?;
+        Ok(states
+            .into_iter()
+            .map(|(path, state)| (path, HydrationState::from_dbus(&state)))
+            .collect())
+    }
+
+    /// Get the local disk usage of several paths, keyed by path. For a
+    /// folder this is what unpinning it would free.
+    pub async fn get_folder_sizes(
+        &self,
+        paths: &[String],
+    ) -> Result<HashMap<String, u64>, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveFilesProxy::new(&self.connection).await?;
+            proxy.get_folder_sizes(paths).await
+        })
+        .await
+    }
+
+    /// Make `path` available offline (PinFile: hydrate + pin).
+    pub async fn pin_path(&self, path: &str) -> Result<(), DbusError> {
+        self.call(self.long_timeout, async {
+            let proxy = LnxdriveFilesProxy::new(&self.connection).await?;
+            proxy.pin_file(path).await
+        })
+        .await
     }
 
-    /// Return the remote folder tree as a JSON string.
-    pub async fn get_remote_folder_tree(&self) -> Result<String, DbusError> {
-        let proxy = LnxdriveSettingsProxy::new(&self.connection).await?;
-        Ok(proxy.get_remote_folder_tree().await?)
+    /// Make `path` online-only (UnpinFile: unpin + dehydrate). Like
+    /// pinning, this walks the whole subtree.
+    pub async fn unpin_path(&self, path: &str) -> Result<(), DbusError> {
+        self.call(self.long_timeout, async {
+            let proxy = LnxdriveFilesProxy::new(&self.connection).await?;
+            proxy.unpin_file(path).await
+        })
+        .await
+    }
+
+    /// Get the sharing state of the local folder `path`.
+    pub async fn get_shared_info(&self, path: &str) -> Result<SharedInfo, DbusError> {
+        let json = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveFilesProxy::new(&self.connection).await?;
+                proxy.get_shared_info(path).await
+            })
+            .await?;
+        serde_json::from_str(&json).map_err(|e| {
+            DbusError::daemon(
+                DaemonErrorKind::InvalidReply,
+                format!("Invalid shared info JSON: {e}"),
+            )
+        })
+    }
+
+    /// Return the recently deleted items. Returns a JSON array string.
+    pub async fn list_deleted(&self) -> Result<String, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveFilesProxy::new(&self.connection).await?;
+            proxy.list_deleted().await
+        })
+        .await
+    }
+
+    /// Restore a deleted item. Returns false if it is gone for good.
+    pub async fn restore_deleted(&self, id: &str) -> Result<bool, DbusError> {
+        self.call(self.long_timeout, async {
+            let proxy = LnxdriveFilesProxy::new(&self.connection).await?;
+            proxy.restore_deleted(id).await
+        })
+        .await
+    }
+
+    /// Make everything downloaded online-only. Returns the bytes freed.
+    pub async fn free_up_all_space(&self) -> Result<u64, DbusError> {
+        self.call(self.long_timeout, async {
+            let proxy = LnxdriveFilesProxy::new(&self.connection).await?;
+            proxy.free_up_all_space().await
+        })
+        .await
     }
 
     // -- Status -------------------------------------------------------------
 
     /// Return `(used_bytes, total_bytes)` quota.
     pub async fn get_quota(&self) -> Result<(u64, u64), DbusError> {
-        let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
-        Ok(proxy.get_quota().await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
+            proxy.get_quota().await
+        })
+        .await
     }
 
-    /// Return account metadata as key-value pairs.
-    pub async fn get_account_info(
-        &self,
-    ) -> Result<HashMap<String, OwnedValue>, DbusError> {
-        let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
-        Ok(proxy.get_account_info().await?)
+    /// Return account metadata (email, display name, account type).
+    pub async fn get_account_info(&self) -> Result<AccountInfo, DbusError> {
+        let map = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
+                proxy.get_account_info().await
+            })
+            .await?;
+        Ok(AccountInfo::from_map(&map))
+    }
+
+    /// Return upload/download byte counters.
+    pub async fn get_transfer_stats(&self) -> Result<TransferStats, DbusError> {
+        let map = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
+                proxy.get_transfer_stats().await
+            })
+            .await?;
+        Ok(TransferStats::from_map(&map))
+    }
+
+    /// Reset the session transfer counters.
+    pub async fn reset_transfer_stats(&self) -> Result<(), DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
+            proxy.reset_transfer_stats().await
+        })
+        .await
+    }
+
+    /// Return the in-flight transfers. Returns a JSON array string.
+    pub async fn get_active_transfers(&self) -> Result<String, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
+            proxy.get_active_transfers().await
+        })
+        .await
+    }
+
+    /// Return the daemon's last unacknowledged error, or `None` if there is
+    /// none.
+    pub async fn get_last_error(&self) -> Result<Option<LastError>, DbusError> {
+        let (message, timestamp) = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
+                proxy.get_last_error().await
+            })
+            .await?;
+        Ok((!message.is_empty()).then_some(LastError { message, timestamp }))
+    }
+
+    /// Acknowledge the daemon's last error.
+    pub async fn clear_last_error(&self) -> Result<(), DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
+            proxy.clear_last_error().await
+        })
+        .await
+    }
+
+    /// Check that OneDrive answers with the current credentials. The inner
+    /// result is the round trip in milliseconds, or the daemon's description
+    /// of why the request failed; the outer one is about reaching the daemon.
+    pub async fn ping_remote(&self) -> Result<Result<u32, String>, DbusError> {
+        let (success, latency_ms, error) = self
+            .call(self.timeout, async {
+                let proxy = LnxdriveStatusProxy::new(&self.connection).await?;
+                proxy.ping_remote().await
+            })
+            .await?;
+        Ok(if success { Ok(latency_ms) } else { Err(error) })
+    }
+
+    // -- Manager ------------------------------------------------------------
+
+    /// Return the daemon's version string.
+    pub async fn daemon_version(&self) -> Result<String, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveManagerProxy::new(&self.connection).await?;
+            proxy.version().await
+        })
+        .await
+    }
+
+    /// Return the last `lines` lines of the daemon log. They may contain
+    /// secrets; redact before showing or saving them.
+    pub async fn get_recent_logs(&self, lines: u32) -> Result<Vec<String>, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveManagerProxy::new(&self.connection).await?;
+            proxy.get_recent_logs(lines).await
+        })
+        .await
     }
 
     // -- Sync ---------------------------------------------------------------
 
     /// Trigger an immediate sync cycle.
     pub async fn sync_now(&self) -> Result<(), DbusError> {
-        let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
-        Ok(proxy.sync_now().await?)
+        self.call(self.long_timeout, async {
+            let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
+            proxy.sync_now().await
+        })
+        .await
     }
 
     /// Pause synchronization.
     pub async fn pause(&self) -> Result<(), DbusError> {
-        let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
-        Ok(proxy.pause().await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
+            proxy.pause().await
+        })
+        .await
     }
 
     /// Resume synchronization.
     pub async fn resume(&self) -> Result<(), DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
+            proxy.resume().await
+        })
+        .await
+    }
+
+    /// Return items that failed to sync. Returns a JSON array string.
+    pub async fn get_sync_errors(&self) -> Result<String, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
+            proxy.get_sync_errors().await
+        })
+        .await
+    }
+
+    /// Retry syncing a failed item.
+    pub async fn retry_item(&self, path: &str) -> Result<(), DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
+            proxy.retry_item(path).await
+        })
+        .await
+    }
+
+    /// Re-scan everything and repair drift. Returns the number of items
+    /// repaired. A large tree can outlast `long_timeout`; the daemon then
+    /// finishes on its own.
+    pub async fn verify_integrity(&self) -> Result<u32, DbusError> {
+        self.call(self.long_timeout, async {
+            let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
+            proxy.verify_integrity().await
+        })
+        .await
+    }
+
+    /// Return the daemon's global sync state (e.g. "idle", "syncing").
+    pub async fn get_sync_status(&self) -> Result<String, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
+            proxy.sync_status().await
+        })
+        .await
+    }
+
+    /// Follow SyncProgress: yields `(file, current, total)` for each file
+    /// the daemon processes.
+    pub async fn receive_sync_progress(
+        &self,
+    ) -> Result<impl Stream<Item = (String, u32, u32)> + 'static, DbusError> {
         let proxy = LnxdriveSyncProxy::new(&self.connection).await?;
-        Ok(proxy.resume().await?)
+        let progress = proxy.receive_sync_progress().await?;
+        Ok(progress.filter_map(|signal| {
+            future::ready(
+                signal
+                    .args()
+                    .ok()
+                    .map(|args| (args.file().to_string(), *args.current(), *args.total())),
+            )
+        }))
     }
 
     // -- Conflicts ----------------------------------------------------------
 
     /// List all unresolved conflicts. Returns a JSON array string.
     pub async fn list_conflicts(&self) -> Result<String, DbusError> {
-        let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
-        Ok(proxy.list().await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
+            proxy.list().await
+        })
+        .await
     }
 
     /// Get details for a specific conflict by ID. Returns JSON string.
     pub async fn get_conflict_details(&self, id: &str) -> Result<String, DbusError> {
-        let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
-        Ok(proxy.get_details(id).await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
+            proxy.get_details(id).await
+        })
+        .await
     }
 
     /// Resolve a conflict with the given strategy. Returns true on success.
@@ -339,14 +1198,52 @@ impl DbusClient {
         id: &str,
         strategy: &str,
     ) -> Result<bool, DbusError> {
-        let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
-        Ok(proxy.resolve(id, strategy).await?)
+        self.call(self.timeout, async {
+            let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
+            proxy.resolve(id, strategy).await
+        })
+        .await
     }
 
     /// Resolve all unresolved conflicts with the given strategy.
     /// Returns the number of conflicts resolved.
     pub async fn resolve_all_conflicts(&self, strategy: &str) -> Result<u32, DbusError> {
-        let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
-        Ok(proxy.resolve_all(strategy).await?)
+        self.call(self.long_timeout, async {
+            let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
+            proxy.resolve_all(strategy).await
+        })
+        .await
+    }
+
+    /// Resolve the conflicts in `ids` with the given strategy in one call.
+    /// Returns the number of conflicts resolved.
+    pub async fn resolve_many_conflicts(
+        &self,
+        ids: &[String],
+        strategy: &str,
+    ) -> Result<u32, DbusError> {
+        self.call(self.long_timeout, async {
+            let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
+            proxy.resolve_many(ids, strategy).await
+        })
+        .await
+    }
+
+    /// Get up to `limit` recent resolutions, newest first. Returns JSON.
+    pub async fn get_resolution_history(&self, limit: u32) -> Result<String, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
+            proxy.get_resolution_history(limit).await
+        })
+        .await
+    }
+
+    /// Revert a resolution; the conflict is listed as unresolved again.
+    pub async fn reopen_conflict(&self, id: &str) -> Result<bool, DbusError> {
+        self.call(self.timeout, async {
+            let proxy = LnxdriveConflictsProxy::new(&self.connection).await?;
+            proxy.reopen(id).await
+        })
+        .await
     }
 }
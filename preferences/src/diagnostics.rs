@@ -0,0 +1,218 @@
+// Diagnostics report for "Report a Problem"
+//
+// Gathers what maintainers ask for in a bug report into one plain-text
+// block: app, toolkit and daemon versions, the sync status and conflict
+// count, the daemon configuration and the tail of the daemon log. Each part
+// is read on its own, so a half-working daemon still yields a report that
+// names what could not be read. The config and log are redacted as they are
+// added, before the text is shown, copied or saved.
+//
+// The report itself is deliberately untranslated, like the conflict details,
+// so any maintainer can read it.
+
+use gettextrs::gettext;
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use crate::config::{redact_config, redact_log_line};
+use crate::conflicts::conflict_dialog::ConflictInfo;
+use crate::dbus_client::{DbusClient, DbusError};
+
+/// Number of daemon log lines included in the report.
+const LOG_LINES: u32 = 200;
+
+/// Suggested file name when saving the report.
+const REPORT_FILE_NAME: &str = "lnxdrive-report.txt";
+
+/// Assemble the report. Never fails: parts the daemon can't provide are
+/// marked unavailable with the error.
+pub async fn collect(client: &DbusClient) -> String {
+    let unavailable = |e: DbusError| format!("unavailable ({e})");
+
+    let daemon_version = client.daemon_version().await.unwrap_or_else(unavailable);
+    let sync_status = client.get_sync_status().await.unwrap_or_else(unavailable);
+    // A reply that doesn't parse says nothing about the count, so it is
+    // reported as such rather than as 0.
+    let conflicts = match client.list_conflicts().await {
+        Ok(json) => match serde_json::from_str::<Vec<serde_json::Value>>(&json) {
+            Ok(arr) => arr
+                .iter()
+                .filter_map(ConflictInfo::from_json)
+                .count()
+                .to_string(),
+            Err(e) => format!("unavailable (malformed reply: {e})"),
+        },
+        Err(e) => unavailable(e),
+    };
+    let config = client
+        .get_config()
+        .await
+        .map(|yaml| redact_config(&yaml))
+        .unwrap_or_else(unavailable);
+    let log = client
+        .get_recent_logs(LOG_LINES)
+        .await
+        .map(|lines| {
+            lines
+                .iter()
+                .map(|line| redact_log_line(line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_else(unavailable);
+
+    let system = glib::os_info("PRETTY_NAME")
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    format!(
+        "LNXDrive problem report\n\
+         App version: {}\n\
+         Daemon version: {daemon_version}\n\
+         System: {system}\n\
+         GTK: {}.{}.{}, libadwaita: {}.{}.{}\n\
+         Sync status: {sync_status}\n\
+         Unresolved conflicts: {conflicts}\n\
+         \n\
+         == Configuration (secrets redacted) ==\n\
+         {}\n\
+         \n\
+         == Daemon log, last {LOG_LINES} lines (secrets redacted) ==\n\
+         {log}\n",
+        env!("CARGO_PKG_VERSION"),
+        gtk4::major_version(),
+        gtk4::minor_version(),
+        gtk4::micro_version(),
+        adw::major_version(),
+        adw::minor_version(),
+        adw::micro_version(),
+        config.trim_end(),
+    )
+}
+
+/// Show `report` over `parent` with Copy and Save As... responses.
+pub fn present_report(parent: &impl IsA<gtk4::Widget>, report: String) {
+    let dialog = adw::AlertDialog::builder()
+        .heading(&gettext("Report a Problem"))
+        .body(&gettext(
+            "Attach this report when you describe the problem. Passwords, tokens and other secrets have been removed; check it for anything else you do not want to share.",
+        ))
+        .build();
+
+    let text_view = gtk4::TextView::builder()
+        .editable(false)
+        .cursor_visible(false)
+        .monospace(true)
+        .wrap_mode(gtk4::WrapMode::WordChar)
+        .top_margin(8)
+        .bottom_margin(8)
+        .left_margin(8)
+        .right_margin(8)
+        .build();
+    text_view.buffer().set_text(&report);
+    text_view.update_property(&[gtk4::accessible::Property::Label(&gettext(
+        "Problem report",
+    ))]);
+    let scrolled = gtk4::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .min_content_height(240)
+        .css_classes(["card"])
+        .child(&text_view)
+        .build();
+    dialog.set_extra_child(Some(&scrolled));
+
+    dialog.add_response("close", &gettext("Close"));
+    dialog.add_response("copy", &gettext("Copy"));
+    dialog.add_response("save", &gettext("Save As..."));
+    dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("save"));
+    dialog.set_close_response("close");
+
+    let parent_widget = parent.clone().upcast::<gtk4::Widget>();
+    dialog.connect_response(None, move |dialog, response| match response {
+        "copy" => dialog.clipboard().set_text(&report),
+        "save" => save_report(&parent_widget, report.clone()),
+        _ => {}
+    });
+
+    adw::prelude::AdwDialogExt::present(&dialog, Some(parent));
+}
+
+/// Ask where to save the report and write it there.
+fn save_report(parent: &gtk4::Widget, report: String) {
+    let file_dialog = gtk4::FileDialog::builder()
+        .title(&gettext("Save Report"))
+        .initial_name(REPORT_FILE_NAME)
+        .modal(true)
+        .build();
+
+    let parent = parent.clone();
+    glib::MainContext::default().spawn_local(async move {
+        let window = parent.root().and_downcast::<gtk4::Window>();
+        let Ok(file) = file_dialog.save_future(window.as_ref()).await else {
+            return;
+        };
+        if let Err((_, e)) = file
+            .replace_contents_future(
+                report.into_bytes(),
+                None,
+                false,
+                gio::FileCreateFlags::REPLACE_DESTINATION,
+            )
+            .await
+        {
+            eprintln!("Could not save the problem report: {e}");
+            let error = adw::AlertDialog::builder()
+                .heading(&gettext("Could Not Save the Report"))
+                .body(e.message())
+                .build();
+            error.add_response("close", &gettext("Close"));
+            adw::prelude::AdwDialogExt::present(&error, Some(&parent));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::{
+        find_widgets, present, run_widget_test, wait_until, MockState, TestDaemon,
+    };
+
+    #[test]
+    fn shows_the_redacted_log_tail() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let window = present(&gtk4::Box::new(gtk4::Orientation::Vertical, 0));
+            let report = glib::MainContext::default().block_on(collect(daemon.client()));
+            present_report(&window.content().unwrap(), report);
+
+            let shown = || {
+                let view = find_widgets::<gtk4::TextView>(&window).pop()?;
+                let buffer = view.buffer();
+                Some(
+                    buffer
+                        .text(&buffer.start_iter(), &buffer.end_iter(), false)
+                        .to_string(),
+                )
+            };
+            wait_until("the report", || shown().is_some());
+            let shown = shown().unwrap();
+            assert!(shown.contains("Unresolved conflicts: 2"), "{shown}");
+            assert!(
+                shown.contains("sync: cycle completed (12 files, 0 errors)"),
+                "{shown}"
+            );
+            assert!(
+                shown.contains("auth: refresh_token: \"<redacted>\""),
+                "{shown}"
+            );
+            assert!(!shown.contains("mock-refresh-token"), "{shown}");
+
+            window.destroy();
+        });
+    }
+}
@@ -4,10 +4,22 @@
 // and runs the GTK main loop.
 
 mod app;
+mod auth_flow;
+mod autostart;
+mod cli;
+mod config;
 mod conflicts;
 mod dbus_client;
+mod diagnostics;
+mod network;
+mod notifications;
 mod onboarding;
 mod preferences;
+mod service;
+mod settings;
+#[cfg(test)]
+mod test_harness;
+mod util;
 mod window;
 
 use gettextrs::{bindtextdomain, setlocale, textdomain, LocaleCategory};
@@ -0,0 +1,107 @@
+// Connection-type policy for syncing
+//
+// `sync_connection_types` in the daemon config lists the kinds of network
+// the daemon may sync over: "wifi", "ethernet" and "mobile". The app writes
+// the setting and applies it too: the window pauses sync on mobile data and
+// resumes it once the connection is allowed again. gio::NetworkMonitor says
+// whether the connection is metered, which mobile data is; since a metered
+// Wi-Fi network is still Wi-Fi, NetworkManager is then asked for the primary
+// connection's type. Where it can't be asked (another network daemon, or a
+// sandbox without the system bus) the type of a metered connection is
+// reported as unknown.
+
+use std::sync::OnceLock;
+
+use gtk4::gio;
+use gtk4::prelude::*;
+use zbus::{proxy, Connection};
+
+use crate::config;
+
+/// Config key holding the allowed connection types.
+pub const CONNECTION_TYPES_KEY: &str = "sync_connection_types";
+
+/// Connection types written when syncing is not restricted.
+const ALL_CONNECTION_TYPES: &[&str] = &["wifi", "ethernet", "mobile"];
+
+/// Connection types written for "Only Sync on Wi-Fi or Ethernet".
+const WIFI_ETHERNET: &[&str] = &["wifi", "ethernet"];
+
+/// Whether `yaml` restricts syncing to Wi-Fi and ethernet. A config without
+/// the key places no restriction.
+pub fn wifi_ethernet_only(yaml: &str) -> bool {
+    config::top_level_list(yaml, CONNECTION_TYPES_KEY)
+        .is_some_and(|types| !types.iter().any(|t| t == "mobile" || t == "any"))
+}
+
+/// A one-key partial config for SetConfig() that restricts syncing to Wi-Fi
+/// and ethernet, or lifts the restriction.
+pub fn connection_types_setting(wifi_ethernet_only: bool) -> String {
+    let types = if wifi_ethernet_only {
+        WIFI_ETHERNET
+    } else {
+        ALL_CONNECTION_TYPES
+    };
+    format!("{CONNECTION_TYPES_KEY}: [{}]\n", types.join(", "))
+}
+
+/// NetworkManager primary connection types the daemon counts as "mobile".
+const MOBILE_CONNECTION_TYPES: &[&str] = &["gsm", "cdma", "bluetooth", "wimax"];
+
+/// org.freedesktop.NetworkManager — only the primary connection's type.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager"
+)]
+trait NetworkManager {
+    /// Type of the connection carrying the default route, e.g.
+    /// "802-11-wireless"; empty while there is none.
+    #[zbus(property)]
+    fn primary_connection_type(&self) -> zbus::Result<String>;
+}
+
+/// The system bus, connected on first use and kept for later checks.
+async fn system_bus() -> zbus::Result<Connection> {
+    static SYSTEM_BUS: OnceLock<Connection> = OnceLock::new();
+    if let Some(connection) = SYSTEM_BUS.get() {
+        return Ok(connection.clone());
+    }
+    let connection = Connection::system().await?;
+    Ok(SYSTEM_BUS.get_or_init(|| connection).clone())
+}
+
+/// The current connection, as far as the restriction is concerned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionType {
+    /// Not metered, or Wi-Fi, ethernet or another allowed type.
+    #[default]
+    Allowed,
+    /// Mobile broadband or a phone tethered over Bluetooth.
+    Mobile,
+    /// Metered, but NetworkManager couldn't say whether it is mobile data
+    /// or a metered Wi-Fi network.
+    Unknown,
+}
+
+/// The type of the system's primary connection. Connections
+/// gio::NetworkMonitor doesn't report as metered are allowed; for metered
+/// ones NetworkManager has the last word.
+pub async fn connection_type() -> ConnectionType {
+    if !gio::NetworkMonitor::default().is_network_metered() {
+        return ConnectionType::Allowed;
+    }
+    let primary_type = async {
+        let proxy = NetworkManagerProxy::new(&system_bus().await?).await?;
+        proxy.primary_connection_type().await
+    };
+    match primary_type.await {
+        Ok(kind) if MOBILE_CONNECTION_TYPES.contains(&kind.as_str()) => ConnectionType::Mobile,
+        Ok(kind) if !kind.is_empty() => ConnectionType::Allowed,
+        Ok(_) => ConnectionType::Unknown,
+        Err(e) => {
+            eprintln!("Could not read the connection type from NetworkManager: {e}");
+            ConnectionType::Unknown
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// Desktop notifications for sync events
+//
+// GNOME has no tray icons, so the app surfaces sync state through
+// gio::Notification (delivered via the notifications portal when sandboxed).
+// The application follows SyncStatus and ConflictDetected for as long as it
+// runs, in the background too, and turns transitions into notifications:
+// conflicts and errors at the "important" level, sync started and finished
+// as well at "all". Each kind reuses one notification ID, so a new one
+// replaces the last instead of piling up. Clicking a notification activates
+// the app, which presents the window.
+
+use gettextrs::gettext;
+use gtk4::gio;
+
+use crate::conflicts::conflict_dialog::ConflictInfo;
+use crate::settings::NotificationLevel;
+
+/// ID for sync started/finished, so "complete" replaces "started".
+const SYNC_ID: &str = "sync";
+const ERROR_ID: &str = "sync-error";
+const CONFLICT_ID: &str = "conflict";
+
+/// A notification worth sending for a change between two SyncStatus values,
+/// or `None`. `error` is the daemon's last error message, if any.
+pub fn for_status_change(
+    previous: &str,
+    current: &str,
+    error: Option<&str>,
+    level: NotificationLevel,
+) -> Option<(&'static str, gio::Notification)> {
+    if previous == current || level == NotificationLevel::None {
+        return None;
+    }
+
+    match current {
+        "error" => {
+            let notification = gio::Notification::new(&gettext("Sync Stopped"));
+            notification.set_body(Some(
+                error.unwrap_or(&gettext("LNXDrive ran into an error. Open it for details.")),
+            ));
+            notification.set_priority(gio::NotificationPriority::High);
+            Some((ERROR_ID, notification))
+        }
+        "syncing" if level == NotificationLevel::All => {
+            let notification = gio::Notification::new(&gettext("Syncing OneDrive"));
+            notification.set_priority(gio::NotificationPriority::Low);
+            Some((SYNC_ID, notification))
+        }
+        "idle" if previous == "syncing" && level == NotificationLevel::All => {
+            let notification = gio::Notification::new(&gettext("Sync Complete"));
+            notification.set_body(Some(&gettext("Your OneDrive files are up to date.")));
+            notification.set_priority(gio::NotificationPriority::Low);
+            Some((SYNC_ID, notification))
+        }
+        _ => None,
+    }
+}
+
+/// A notification for a ConflictDetected signal carrying `conflict_json`.
+pub fn for_conflict(
+    conflict_json: &str,
+    level: NotificationLevel,
+) -> Option<(&'static str, gio::Notification)> {
+    if level == NotificationLevel::None {
+        return None;
+    }
+
+    let notification = gio::Notification::new(&gettext("Sync Conflict"));
+    let body = serde_json::from_str(conflict_json)
+        .ok()
+        .and_then(|value| ConflictInfo::from_json(&value))
+        .map(|conflict| {
+            gettext("{} was changed in two places. Choose which version to keep.")
+                .replace("{}", conflict.filename())
+        })
+        .unwrap_or_else(|| {
+            gettext("A file was changed in two places. Choose which version to keep.")
+        });
+    notification.set_body(Some(&body));
+    Some((CONFLICT_ID, notification))
+}
@@ -1,13 +1,19 @@
 // Auth Page — first step of the onboarding wizard
 //
 // Shows a "Sign in to OneDrive" status page with a sign-in button.
-// On click: calls StartAuth() over D-Bus, opens the auth URL in the default
-// browser, switches to a waiting state with a spinner, and subscribes to the
-// AuthStateChanged signal.  On success, pushes the FolderPage.
+// On click: switches to a waiting state with a spinner and runs the shared
+// sign-in flow (`auth_flow::sign_in`: StartAuth() over D-Bus, open the auth
+// URL in the default browser, wait for AuthStateChanged). On success, pushes
+// the FolderPage.
+// If the browser cannot be launched (headless session, no default browser),
+// the auth URL is shown with a "Copy Link" button so it can be opened on
+// another device while the page keeps waiting for the signal. When it did
+// open, "Open Sign-In Page Again" relaunches the same URL in case the tab
+// was closed. If the URL names the organisation or email domain, the
+// waiting state says which one is being signed in to.
 
 use std::cell::RefCell;
 
-use futures_util::StreamExt;
 use gettextrs::gettext;
 use gtk4::glib;
 use gtk4::prelude::*;
@@ -16,7 +22,7 @@ use libadwaita::prelude::*;
 
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 
-use crate::dbus_client::LnxdriveAuthProxy;
+use crate::auth_flow;
 
 use super::folder_page::FolderPage;
 use super::OnboardingView;
@@ -33,6 +39,15 @@ mod imp {
         pub spinner: RefCell<Option<gtk4::Spinner>>,
         pub cancel_button: RefCell<Option<gtk4::Button>>,
         pub error_banner: RefCell<Option<adw::Banner>>,
+        /// Fallback shown when the browser fails to launch.
+        pub link_box: RefCell<Option<gtk4::Box>>,
+        pub link_entry: RefCell<Option<gtk4::Entry>>,
+        pub copy_button: RefCell<Option<gtk4::Button>>,
+        /// URL of the sign-in attempt in progress, for reopening it.
+        pub auth_url: RefCell<Option<String>>,
+        pub reopen_button: RefCell<Option<gtk4::Button>>,
+        /// "Signing in to contoso.com", when the domain is known.
+        pub domain_label: RefCell<Option<gtk4::Label>>,
         pub content_box: RefCell<Option<gtk4::Box>>,
     }
 
@@ -45,6 +60,12 @@ mod imp {
                 spinner: RefCell::new(None),
                 cancel_button: RefCell::new(None),
                 error_banner: RefCell::new(None),
+                link_box: RefCell::new(None),
+                link_entry: RefCell::new(None),
+                copy_button: RefCell::new(None),
+                auth_url: RefCell::new(None),
+                reopen_button: RefCell::new(None),
+                domain_label: RefCell::new(None),
                 content_box: RefCell::new(None),
             }
         }
@@ -124,6 +145,60 @@ impl AuthPage {
             .visible(false)
             .build();
 
+        let domain_label = gtk4::Label::builder()
+            .css_classes(["dim-label"])
+            .visible(false)
+            .build();
+        imp.domain_label.replace(Some(domain_label.clone()));
+
+        // Relaunches the browser on the same URL (hidden initially)
+        let reopen_button = gtk4::Button::builder()
+            .label(&gettext("Open Sign-In Page Again"))
+            .halign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .visible(false)
+            .build();
+        imp.reopen_button.replace(Some(reopen_button.clone()));
+
+        // Browser-launch fallback: read-only auth URL + "Copy Link" (hidden initially)
+        let link_label = gtk4::Label::builder()
+            .label(&gettext("Open this link in a browser on any device to sign in:"))
+            .wrap(true)
+            .justify(gtk4::Justification::Center)
+            .build();
+
+        let link_entry = gtk4::Entry::builder()
+            .editable(false)
+            .hexpand(true)
+            .build();
+        link_entry.update_property(&[gtk4::accessible::Property::Label(&gettext(
+            "Sign-in link",
+        ))]);
+        imp.link_entry.replace(Some(link_entry.clone()));
+
+        let copy_button = gtk4::Button::builder()
+            .label(&gettext("Copy Link"))
+            .build();
+        imp.copy_button.replace(Some(copy_button.clone()));
+
+        let link_row = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .css_classes(["linked"])
+            .build();
+        link_row.append(&link_entry);
+        link_row.append(&copy_button);
+
+        let link_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(6)
+            .width_request(360)
+            .visible(false)
+            .build();
+        link_box.append(&link_label);
+        link_box.append(&link_row);
+        imp.link_box.replace(Some(link_box.clone()));
+
         // Status page
         let status_page = adw::StatusPage::builder()
             .icon_name("dialog-password-symbolic")
@@ -142,6 +217,9 @@ impl AuthPage {
         button_box.append(&sign_in_button);
         button_box.append(&spinner);
         button_box.append(&waiting_label.clone());
+        button_box.append(&domain_label);
+        button_box.append(&reopen_button);
+        button_box.append(&link_box);
         button_box.append(&cancel_button);
 
         status_page.set_child(Some(&button_box));
@@ -175,6 +253,19 @@ impl AuthPage {
         cancel_button.connect_clicked(move |_| {
             page.on_cancel_clicked(&waiting_label_clone2);
         });
+
+        // Connect "Open Sign-In Page Again"
+        let page = self.clone();
+        reopen_button.connect_clicked(move |_| {
+            page.reopen_auth_url();
+        });
+
+        // Connect "Copy Link" button
+        let entry = link_entry;
+        copy_button.connect_clicked(move |btn| {
+            btn.clipboard().set_text(&entry.text());
+            btn.set_label(&gettext("Copied"));
+        });
     }
 
     /// Called when the user clicks "Sign In".
@@ -199,101 +290,31 @@ impl AuthPage {
         let wl = waiting_label.clone();
 
         glib::MainContext::default().spawn_local(async move {
-            // 1. Call StartAuth() to get the browser URL
-            match dbus_client.start_auth().await {
-                Ok((auth_url, _state)) => {
-                    // 2. Open the URL in the default browser
-                    let launcher = gtk4::UriLauncher::new(&auth_url);
-
-                    if let Some(win) = ov.parent_window() {
-                        if let Err(e) = launcher.launch_future(Some(&win)).await {
-                            page.show_error(&format!(
-                                "{}: {}",
-                                gettext("Could not open browser"),
-                                e
-                            ));
-                            page.set_waiting_state(false, &wl);
-                            return;
-                        }
+            let parent = ov.parent_window().map(|w| w.upcast::<gtk4::Window>());
+            let link_page = page.clone();
+            let result = auth_flow::sign_in(&dbus_client, parent.as_ref(), move |auth_url, opened| {
+                link_page.show_auth_url(auth_url, opened);
+            })
+            .await;
+
+            match result {
+                Ok(()) => {
+                    // Fetch account info for state
+                    if let Ok(info) = dbus_client.get_account_info().await {
+                        let mut ob_state = ov.state_mut();
+                        ob_state.account_email = info.email;
+                        ob_state.account_name = info.display_name;
                     }
 
-                    // 3. Subscribe to AuthStateChanged signal.
-                    // Clone the connection so the proxy doesn't borrow dbus_client,
-                    // allowing us to call other methods on dbus_client while the
-                    // signal stream is active.
-                    let conn = dbus_client.connection().clone();
-                    match LnxdriveAuthProxy::new(&conn).await {
-                        Ok(proxy) => match proxy.receive_auth_state_changed().await {
-                            Ok(mut stream) => {
-                                while let Some(signal) = stream.next().await {
-                                    if let Ok(args) = signal.args() {
-                                        match args.state {
-                                            "authenticated" => {
-                                                // Fetch account info for state
-                                                if let Ok(info) =
-                                                    dbus_client.get_account_info().await
-                                                {
-                                                    let mut ob_state = ov.state_mut();
-                                                    ob_state.account_email = info
-                                                        .get("email")
-                                                        .and_then(|v| {
-                                                            String::try_from(v.clone()).ok()
-                                                        });
-                                                    ob_state.account_name = info
-                                                        .get("display_name")
-                                                        .and_then(|v| {
-                                                            String::try_from(v.clone()).ok()
-                                                        });
-                                                }
-
-                                                // Push the folder selection page
-                                                let folder_page = FolderPage::new(&ov);
-                                                ov.nav_view().push(&folder_page);
-                                                page.set_waiting_state(false, &wl);
-                                                return;
-                                            }
-                                            "error" => {
-                                                page.show_error(&gettext(
-                                                    "Authentication failed. Please try again.",
-                                                ));
-                                                page.set_waiting_state(false, &wl);
-                                                return;
-                                            }
-                                            _ => {
-                                                // Other transient states; keep waiting.
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                page.show_error(&format!(
-                                    "{}: {}",
-                                    gettext("Could not listen for auth events"),
-                                    e
-                                ));
-                                page.set_waiting_state(false, &wl);
-                            }
-                        },
-                        Err(e) => {
-                            page.show_error(&format!(
-                                "{}: {}",
-                                gettext("D-Bus proxy error"),
-                                e
-                            ));
-                            page.set_waiting_state(false, &wl);
-                        }
-                    }
+                    // Push the folder selection page
+                    let folder_page = FolderPage::new(&ov);
+                    ov.nav_view().push(&folder_page);
                 }
                 Err(e) => {
-                    page.show_error(&format!(
-                        "{}: {}",
-                        gettext("Could not start authentication"),
-                        e
-                    ));
-                    page.set_waiting_state(false, &wl);
+                    page.show_error(&e.to_string());
                 }
             }
+            page.set_waiting_state(false, &wl);
         });
     }
 
@@ -312,6 +333,77 @@ impl AuthPage {
         if let Some(ref cancel) = *imp.cancel_button.borrow() {
             cancel.set_visible(waiting);
         }
+        if !waiting {
+            imp.auth_url.replace(None);
+            if let Some(ref link_box) = *imp.link_box.borrow() {
+                link_box.set_visible(false);
+            }
+            if let Some(ref button) = *imp.reopen_button.borrow() {
+                button.set_visible(false);
+            }
+            if let Some(ref label) = *imp.domain_label.borrow() {
+                label.set_visible(false);
+            }
+        }
+    }
+
+    /// Remember the URL of the attempt and name its domain. If the browser
+    /// opened it, offer to open it again; otherwise reveal it with "Copy
+    /// Link".
+    fn show_auth_url(&self, auth_url: &str, opened: bool) {
+        let imp = self.imp();
+        imp.auth_url.replace(Some(auth_url.to_string()));
+
+        if let Some(ref label) = *imp.domain_label.borrow() {
+            match auth_flow::auth_domain(auth_url) {
+                Some(domain) => {
+                    label.set_label(&gettext("Signing in to {}").replace("{}", &domain));
+                    label.set_visible(true);
+                }
+                None => label.set_visible(false),
+            }
+        }
+
+        if let Some(ref button) = *imp.reopen_button.borrow() {
+            button.set_visible(opened);
+        }
+        if opened {
+            return;
+        }
+
+        if let Some(ref entry) = *imp.link_entry.borrow() {
+            entry.set_text(auth_url);
+        }
+        // Reset the button label from a previous attempt.
+        if let Some(ref button) = *imp.copy_button.borrow() {
+            button.set_label(&gettext("Copy Link"));
+        }
+        if let Some(ref link_box) = *imp.link_box.borrow() {
+            link_box.set_visible(true);
+        }
+    }
+
+    /// Launch the browser on the current attempt's URL again.
+    fn reopen_auth_url(&self) {
+        let Some(auth_url) = self.imp().auth_url.borrow().clone() else {
+            return;
+        };
+        let parent = self
+            .imp()
+            .onboarding_view
+            .borrow()
+            .as_ref()
+            .and_then(|ov| ov.parent_window())
+            .map(|w| w.upcast::<gtk4::Window>());
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let launcher = gtk4::UriLauncher::new(&auth_url);
+            if let Err(e) = launcher.launch_future(parent.as_ref()).await {
+                eprintln!("Could not open browser: {}", e);
+                page.show_auth_url(&auth_url, false);
+            }
+        });
     }
 
     /// Cancel the ongoing authentication attempt and reset the wizard.
@@ -2,9 +2,15 @@
 //
 // Shows a summary (account email, sync folder) and a "Start Syncing" button.
 // On click: writes configuration to the daemon and triggers the first sync.
+// The button then gives way to a progress bar fed by SyncProgress for a few
+// seconds, so it is clear the first sync is under way (and may be large)
+// before the preferences take over. If either call fails the button comes
+// back for another try.
 
 use std::cell::RefCell;
 
+use futures_util::future::{AbortHandle, Abortable};
+use futures_util::{Stream, StreamExt};
 use gettextrs::gettext;
 use gtk4::glib;
 use gtk4::prelude::*;
@@ -15,6 +21,10 @@ use gtk4::subclass::prelude::ObjectSubclassIsExt;
 
 use super::OnboardingView;
 
+/// How long the first sync's progress is shown before switching to the
+/// preferences.
+const PROGRESS_PREVIEW_SECONDS: u32 = 4;
+
 mod imp {
     use super::*;
     use gtk4::subclass::prelude::*;
@@ -22,12 +32,19 @@ mod imp {
 
     pub struct ConfirmPage {
         pub onboarding_view: RefCell<Option<OnboardingView>>,
+        pub start_button: RefCell<Option<gtk4::Button>>,
+        /// Label and bar replacing the button once the first sync started.
+        pub progress_box: RefCell<Option<gtk4::Box>>,
+        pub progress_bar: RefCell<Option<gtk4::ProgressBar>>,
     }
 
     impl Default for ConfirmPage {
         fn default() -> Self {
             Self {
                 onboarding_view: RefCell::new(None),
+                start_button: RefCell::new(None),
+                progress_box: RefCell::new(None),
+                progress_bar: RefCell::new(None),
             }
         }
     }
@@ -107,6 +124,29 @@ impl ConfirmPage {
             .css_classes(["suggested-action", "pill"])
             .build();
 
+        imp.start_button.replace(Some(start_button.clone()));
+
+        let progress_label = gtk4::Label::builder()
+            .label(&gettext(
+                "Your first sync has started. It can take a while for a large OneDrive and continues in the background.",
+            ))
+            .wrap(true)
+            .justify(gtk4::Justification::Center)
+            .build();
+        let progress_bar = gtk4::ProgressBar::builder()
+            .show_text(true)
+            .text(&gettext("Preparing..."))
+            .build();
+        let progress_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .visible(false)
+            .build();
+        progress_box.append(&progress_label);
+        progress_box.append(&progress_bar);
+        imp.progress_bar.replace(Some(progress_bar));
+        imp.progress_box.replace(Some(progress_box.clone()));
+
         let button_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
             .spacing(12)
@@ -114,6 +154,7 @@ impl ConfirmPage {
             .margin_top(24)
             .build();
         button_box.append(&start_button);
+        button_box.append(&progress_box);
 
         // Status page with check icon
         let status_page = adw::StatusPage::builder()
@@ -141,8 +182,7 @@ impl ConfirmPage {
 
         // Connect "Start Syncing" click
         let page = self.clone();
-        start_button.connect_clicked(move |btn| {
-            btn.set_sensitive(false);
+        start_button.connect_clicked(move |_| {
             page.on_start_syncing();
         });
     }
@@ -163,7 +203,10 @@ impl ConfirmPage {
 
         let sync_root = ov.state().sync_root.clone().unwrap_or_default();
         let parent_window = ov.parent_window();
+        let initial_page = ov.initial_page();
 
+        self.set_start_sensitive(false);
+        let page = self.clone();
         glib::MainContext::default().spawn_local(async move {
             // Build a minimal YAML config pointing at the chosen sync root.
             let config_yaml = format!("sync_root: \"{}\"\n", sync_root);
@@ -179,9 +222,13 @@ impl ConfirmPage {
                     // otherwise fall back to showing the error in the window.
                     show_toast_on_window(win, &toast);
                 }
+                page.set_start_sensitive(true);
                 return;
             }
 
+            // Subscribe before starting so the first files are not missed.
+            let progress = dbus_client.receive_sync_progress().await;
+
             if let Err(e) = dbus_client.sync_now().await {
                 if let Some(ref win) = parent_window {
                     let toast = adw::Toast::new(&format!(
@@ -191,20 +238,73 @@ impl ConfirmPage {
                     ));
                     show_toast_on_window(win, &toast);
                 }
+                page.set_start_sensitive(true);
                 return;
             }
 
-            // Success — switch to the preferences view.
+            match progress {
+                Ok(progress) => page.preview_progress(progress).await,
+                Err(e) => eprintln!("Could not subscribe to SyncProgress: {e}"),
+            }
+
+            // Success — switch to the preferences view, which reports when
+            // this first sync finishes.
             if let Some(ref win) = parent_window {
-                win.show_preferences(&dbus_client, None);
+                win.expect_initial_sync();
+                win.show_preferences(&dbus_client, initial_page.as_deref());
             }
         });
     }
+
+    fn set_start_sensitive(&self, sensitive: bool) {
+        if let Some(ref button) = *self.imp().start_button.borrow() {
+            button.set_sensitive(sensitive);
+        }
+    }
+
+    /// Swap the button for the progress bar and follow `progress` for
+    /// `PROGRESS_PREVIEW_SECONDS`.
+    async fn preview_progress(
+        &self,
+        mut progress: impl Stream<Item = (String, u32, u32)> + Unpin + 'static,
+    ) {
+        let imp = self.imp();
+        if let Some(ref button) = *imp.start_button.borrow() {
+            button.set_visible(false);
+        }
+        if let Some(ref progress_box) = *imp.progress_box.borrow() {
+            progress_box.set_visible(true);
+        }
+        let Some(bar) = imp.progress_bar.borrow().clone() else {
+            return;
+        };
+
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        glib::MainContext::default().spawn_local(async move {
+            let _ = Abortable::new(async move {
+                while let Some((file, current, total)) = progress.next().await {
+                    if total > 0 {
+                        bar.set_fraction(f64::from(current) / f64::from(total));
+                    }
+                    bar.set_text(Some(&file));
+                }
+            }, abort_registration).await;
+        });
+
+        glib::timeout_future_seconds(PROGRESS_PREVIEW_SECONDS).await;
+        abort_handle.abort();
+    }
 }
 
 /// Helper: show a toast on the window. We wrap the window content in a
-/// ToastOverlay if needed, then add the toast.
+/// ToastOverlay if needed, then add the toast. An overlay from an earlier
+/// failed attempt is reused, so retrying doesn't nest another one.
 fn show_toast_on_window(window: &crate::window::LnxdriveWindow, toast: &adw::Toast) {
+    if let Some(overlay) = window.content().and_downcast::<adw::ToastOverlay>() {
+        overlay.add_toast(toast.clone());
+        return;
+    }
+
     let overlay = adw::ToastOverlay::new();
     if let Some(child) = window.content() {
         window.set_content(None::<&gtk4::Widget>);
@@ -1,11 +1,14 @@
 // Folder Page — second step of the onboarding wizard
 //
-// Lets the user choose the local sync root (defaults to ~/OneDrive).
+// Lets the user choose the local sync root (defaults to ~/OneDrive), either
+// with the folder chooser or by dropping a folder from Files onto the path
+// row. Every candidate goes through `validate_sync_folder`; a rejected one
+// leaves the selection unchanged and explains why below the row.
 // "Continue" validates the path and pushes the ConfirmPage.
 // "Back" pops back to the AuthPage.
 
 use std::cell::RefCell;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use gettextrs::gettext;
 use gtk4::glib;
@@ -27,6 +30,8 @@ mod imp {
         pub onboarding_view: RefCell<Option<OnboardingView>>,
         pub selected_path: RefCell<PathBuf>,
         pub path_row: RefCell<Option<adw::ActionRow>>,
+        /// Why the last chosen or dropped folder was rejected.
+        pub error_label: RefCell<Option<gtk4::Label>>,
     }
 
     impl Default for FolderPage {
@@ -36,6 +41,7 @@ mod imp {
                 onboarding_view: RefCell::new(None),
                 selected_path: RefCell::new(default_path),
                 path_row: RefCell::new(None),
+                error_label: RefCell::new(None),
             }
         }
     }
@@ -90,11 +96,26 @@ impl FolderPage {
             .valign(gtk4::Align::Center)
             .css_classes(["flat"])
             .build();
+        choose_button.update_property(&[gtk4::accessible::Property::Label(&gettext(
+            "Choose Folder...",
+        ))]);
         path_row.add_suffix(&choose_button);
         path_row.set_activatable_widget(Some(&choose_button));
 
         imp.path_row.replace(Some(path_row.clone()));
 
+        // Accept a folder dragged from Files.
+        let drop_target =
+            gtk4::DropTarget::new(gtk4::gio::File::static_type(), gtk4::gdk::DragAction::COPY);
+        let page = self.clone();
+        drop_target.connect_drop(move |_, value, _, _| {
+            match value.get::<gtk4::gio::File>().ok().and_then(|f| f.path()) {
+                Some(path) => page.set_selected_path(path),
+                None => false,
+            }
+        });
+        path_row.add_controller(drop_target);
+
         let prefs_group = adw::PreferencesGroup::builder()
             .title(&gettext("Sync Location"))
             .description(&gettext(
@@ -103,6 +124,14 @@ impl FolderPage {
             .build();
         prefs_group.add(&path_row);
 
+        let error_label = gtk4::Label::builder()
+            .halign(gtk4::Align::Start)
+            .wrap(true)
+            .css_classes(["error", "caption"])
+            .visible(false)
+            .build();
+        imp.error_label.replace(Some(error_label.clone()));
+
         // Action buttons
         let continue_button = gtk4::Button::builder()
             .label(&gettext("Continue"))
@@ -129,6 +158,7 @@ impl FolderPage {
             .valign(gtk4::Align::Center)
             .build();
         content.append(&prefs_group);
+        content.append(&error_label);
         content.append(&button_box);
 
         // Clamp for responsive width
@@ -158,19 +188,7 @@ impl FolderPage {
 
     /// Open a folder chooser dialog.
     fn on_choose_folder(&self) {
-        let dialog = gtk4::FileDialog::builder()
-            .title(&gettext("Choose Sync Folder"))
-            .modal(true)
-            .build();
-
-        // Set initial folder to current selection
         let current = self.imp().selected_path.borrow().clone();
-        if current.exists() {
-            let file = gtk4::gio::File::for_path(&current);
-            dialog.set_initial_folder(Some(&file));
-        }
-
-        let page = self.clone();
         let parent_win: Option<gtk4::Window> = self
             .imp()
             .onboarding_view
@@ -179,28 +197,38 @@ impl FolderPage {
             .and_then(|ov| ov.parent_window())
             .map(|w| w.upcast::<gtk4::Window>());
 
-        dialog.select_folder(
-            parent_win.as_ref(),
-            None::<&gtk4::gio::Cancellable>,
-            move |result| {
-                if let Ok(file) = result {
-                    if let Some(path) = file.path() {
-                        page.set_selected_path(path);
-                    }
-                }
-                // User cancelled — do nothing.
-            },
-        );
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            if let Some(path) = choose_sync_folder(parent_win.as_ref(), &current).await {
+                page.set_selected_path(path);
+            }
+            // User cancelled — do nothing.
+        });
     }
 
-    /// Update the selected path and refresh the UI.
-    fn set_selected_path(&self, path: PathBuf) {
+    /// Validate `path` and, if it is usable, make it the selection.
+    /// Returns whether it was accepted.
+    fn set_selected_path(&self, path: PathBuf) -> bool {
+        if let Err(reason) = validate_sync_folder(&path) {
+            self.show_error(Some(&reason));
+            return false;
+        }
+        self.show_error(None);
+
         let display = path.display().to_string();
         *self.imp().selected_path.borrow_mut() = path;
 
         if let Some(ref row) = *self.imp().path_row.borrow() {
             row.set_subtitle(&display);
         }
+        true
+    }
+
+    fn show_error(&self, message: Option<&str>) {
+        if let Some(ref label) = *self.imp().error_label.borrow() {
+            label.set_label(message.unwrap_or(""));
+            label.set_visible(message.is_some());
+        }
     }
 
     /// Validate and proceed to the confirm page.
@@ -208,6 +236,12 @@ impl FolderPage {
         let imp = self.imp();
         let path = imp.selected_path.borrow().clone();
 
+        // The folder may have changed since it was chosen.
+        if let Err(reason) = validate_sync_folder(&path) {
+            self.show_error(Some(&reason));
+            return;
+        }
+
         // Store in onboarding state
         if let Some(ref ov) = *imp.onboarding_view.borrow() {
             {
@@ -220,3 +254,50 @@ impl FolderPage {
         }
     }
 }
+
+/// Show the "Choose Sync Folder" chooser, starting at `current` if it
+/// exists. Returns `None` if the user cancelled. Shared with the Sync page
+/// and the window's missing-folder banner.
+pub async fn choose_sync_folder(parent: Option<&gtk4::Window>, current: &Path) -> Option<PathBuf> {
+    let dialog = gtk4::FileDialog::builder()
+        .title(&gettext("Choose Sync Folder"))
+        .modal(true)
+        .build();
+
+    if current.is_dir() {
+        let file = gtk4::gio::File::for_path(current);
+        dialog.set_initial_folder(Some(&file));
+    }
+
+    dialog.select_folder_future(parent).await.ok()?.path()
+}
+
+/// Check that `path` can be used as the sync root: an existing directory
+/// the user can write to, or a path the daemon can create (its parent
+/// exists). Also used when the sync folder is changed from preferences.
+pub fn validate_sync_folder(path: &Path) -> Result<(), String> {
+    let no_permission = || gettext("You don't have permission to write to this folder.");
+    match std::fs::metadata(path) {
+        Ok(meta) if !meta.is_dir() => Err(gettext("Please choose a folder, not a file.")),
+        Ok(_) if !can_write(path) => Err(no_permission()),
+        Ok(_) => Ok(()),
+        Err(_) => match path.parent() {
+            Some(parent) if parent.is_dir() && !can_write(parent) => Err(no_permission()),
+            Some(parent) if parent.is_dir() => Ok(()),
+            _ => Err(gettext("This folder does not exist.")),
+        },
+    }
+}
+
+/// Whether the current user may write to `path`, as gio reports it (which
+/// accounts for ownership and group membership, unlike the mode bits alone).
+fn can_write(path: &Path) -> bool {
+    let attribute = gtk4::gio::FILE_ATTRIBUTE_ACCESS_CAN_WRITE;
+    gtk4::gio::File::for_path(path)
+        .query_info(
+            attribute,
+            gtk4::gio::FileQueryInfoFlags::NONE,
+            gtk4::gio::Cancellable::NONE,
+        )
+        .is_ok_and(|info| info.boolean(attribute))
+}
@@ -2,7 +2,9 @@
 //
 // A three-step wizard: AuthPage -> FolderPage -> ConfirmPage.
 // Holds transient state (account info, chosen sync root) that is discarded
-// on cancel and committed to the daemon on "Start Syncing".
+// on cancel and committed to the daemon on "Start Syncing". A preferences
+// page requested with `--page` before sign-in is kept aside (it survives a
+// cancel) and opened once the wizard finishes.
 //
 // NavigationView is not subclassable in libadwaita-rs 0.7, so we use
 // composition: OnboardingView is a Bin whose child is a NavigationView.
@@ -52,6 +54,8 @@ mod imp {
         pub dbus_client: RefCell<Option<DbusClient>>,
         pub state: RefCell<OnboardingState>,
         pub parent_window: RefCell<Option<LnxdriveWindow>>,
+        /// Preferences page to open when onboarding completes.
+        pub initial_page: RefCell<Option<String>>,
     }
 
     impl Default for OnboardingView {
@@ -61,6 +65,7 @@ mod imp {
                 dbus_client: RefCell::new(None),
                 state: RefCell::new(OnboardingState::default()),
                 parent_window: RefCell::new(None),
+                initial_page: RefCell::new(None),
             }
         }
     }
@@ -90,13 +95,19 @@ glib::wrapper! {
 
 impl OnboardingView {
     /// Create the onboarding wizard and push the first page (auth).
-    pub fn new(dbus_client: DbusClient, parent_window: LnxdriveWindow) -> Self {
+    /// `initial_page` is the preferences page to show once it completes.
+    pub fn new(
+        dbus_client: DbusClient,
+        parent_window: LnxdriveWindow,
+        initial_page: Option<&str>,
+    ) -> Self {
         let view: Self = glib::Object::builder().build();
 
         {
             let imp = view.imp();
             *imp.dbus_client.borrow_mut() = Some(dbus_client);
             *imp.parent_window.borrow_mut() = Some(parent_window);
+            *imp.initial_page.borrow_mut() = initial_page.map(str::to_string);
         }
 
         let auth_page = AuthPage::new(&view);
@@ -130,6 +141,11 @@ impl OnboardingView {
         self.imp().parent_window.borrow().clone()
     }
 
+    /// The preferences page requested before onboarding, if any.
+    pub fn initial_page(&self) -> Option<String> {
+        self.imp().initial_page.borrow().clone()
+    }
+
     /// Cancel onboarding: reset all transient state and pop to the first page (FR-033).
     pub fn on_cancel(&self) {
         *self.imp().state.borrow_mut() = OnboardingState::default();
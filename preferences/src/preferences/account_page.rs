@@ -1,12 +1,28 @@
 // Account Page — adw::PreferencesPage subclass
 //
 // Displays OneDrive account information (email, display name), storage quota
-// with a LevelBar, and a "Sign Out" button that logs out and returns to
-// onboarding.
-
-use std::cell::RefCell;
-
-use gettextrs::gettext;
+// with a LevelBar, transfer statistics (refreshed periodically), and a
+// "Sign Out" button that logs out and returns to onboarding.
+//
+// The account info and quota are fetched together in one task, and the
+// page is updated once both have settled. When the daemon answers from its
+// cache (it sends an `as_of` time, e.g. while offline) a caption under the
+// quota says how old the numbers are. Until then a spinner stands in for
+// the Account and Storage groups. They appear when the account info loaded;
+// if it could not be, a single error state with "Retry" replaces them.
+//
+// A "Daemon" row follows NameOwnerChanged for the daemon's bus name and
+// reports "Connected" or "Daemon not running"; its "Restart" button restarts
+// the lnxdrive systemd user service and shows "Reconnecting..." until the
+// daemon is back on the bus. Below it, "Test Connection" asks the daemon to
+// reach OneDrive and reports the round trip or the error in a toast, telling
+// a local daemon problem apart from a network or sign-in one.
+
+use std::cell::{Cell, RefCell};
+
+use futures_util::future::{AbortHandle, Abortable};
+use futures_util::StreamExt;
+use gettextrs::{gettext, ngettext};
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita as adw;
@@ -14,7 +30,63 @@ use libadwaita::prelude::*;
 
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 
-use crate::dbus_client::DbusClient;
+use crate::dbus_client::{AccountInfo, DaemonErrorKind, DbusClient, DbusError, TransferStats};
+use crate::service;
+use crate::util;
+
+/// How often the Activity group re-reads the transfer counters.
+const STATS_REFRESH_SECS: u32 = 5;
+
+/// What the Account and Storage groups show.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OverviewState {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// User-facing explanation of why the account info could not be loaded.
+fn load_error_description(e: &DbusError) -> String {
+    match e.kind() {
+        Some(DaemonErrorKind::NetworkUnavailable) => {
+            gettext("LNXDrive is offline. Check your internet connection and try again.")
+        }
+        Some(DaemonErrorKind::TimedOut) => {
+            gettext("The LNXDrive daemon did not respond in time.")
+        }
+        None => gettext("Could not reach the LNXDrive daemon. Make sure it is running."),
+        Some(_) => gettext("The LNXDrive daemon could not provide the account details."),
+    }
+}
+
+/// How long ago cached account details were fetched, e.g. "Last updated 3
+/// hours ago".
+fn last_updated_caption(as_of: &glib::DateTime) -> String {
+    let Ok(now) = glib::DateTime::now_utc() else {
+        return String::new();
+    };
+
+    // Literal msgids in every arm so xgettext can extract them.
+    let secs = now.difference(as_of).as_seconds().max(0) as u32;
+    match secs {
+        0..=59 => gettext("Last updated just now"),
+        60..=3_599 => {
+            let n = secs / 60;
+            ngettext("Last updated {} minute ago", "Last updated {} minutes ago", n)
+                .replace("{}", &n.to_string())
+        }
+        3_600..=86_399 => {
+            let n = secs / 3_600;
+            ngettext("Last updated {} hour ago", "Last updated {} hours ago", n)
+                .replace("{}", &n.to_string())
+        }
+        _ => {
+            let n = secs / 86_400;
+            ngettext("Last updated {} day ago", "Last updated {} days ago", n)
+                .replace("{}", &n.to_string())
+        }
+    }
+}
 
 // ---------------------------------------------------------------------------
 // AccountPage — adw::PreferencesPage subclass
@@ -27,20 +99,66 @@ mod imp {
 
     pub struct AccountPage {
         pub dbus_client: RefCell<Option<DbusClient>>,
+        pub account_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub storage_group: RefCell<Option<adw::PreferencesGroup>>,
+        /// Spinner shown until the account info has loaded.
+        pub loading_group: RefCell<Option<adw::PreferencesGroup>>,
+        /// Error state with "Retry", shown when the account info failed.
+        pub error_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub error_status: RefCell<Option<adw::StatusPage>>,
+        /// Whether the account info and quota fetch is in flight.
+        pub overview_loading: Cell<bool>,
+        /// Whether the account info has loaded at least once.
+        pub account_loaded: Cell<bool>,
         pub email_row: RefCell<Option<adw::ActionRow>>,
         pub name_row: RefCell<Option<adw::ActionRow>>,
+        pub type_row: RefCell<Option<adw::ActionRow>>,
+        pub connection_row: RefCell<Option<adw::ActionRow>>,
+        pub restart_button: RefCell<Option<gtk4::Button>>,
+        /// True from a restart request until the daemon is back on the bus.
+        pub reconnecting: Cell<bool>,
+        /// NameOwnerChanged subscription, aborted on dispose.
+        pub presence_watch: RefCell<Option<AbortHandle>>,
+        /// Insensitive while a connection test is running.
+        pub test_connection_button: RefCell<Option<gtk4::Button>>,
         pub level_bar: RefCell<Option<gtk4::LevelBar>>,
         pub quota_label: RefCell<Option<gtk4::Label>>,
+        /// "Last updated ... ago", shown only for cached account details.
+        pub as_of_label: RefCell<Option<gtk4::Label>>,
+        pub session_up_row: RefCell<Option<adw::ActionRow>>,
+        pub session_down_row: RefCell<Option<adw::ActionRow>>,
+        pub total_up_row: RefCell<Option<adw::ActionRow>>,
+        pub total_down_row: RefCell<Option<adw::ActionRow>>,
+        pub stats_source: RefCell<Option<glib::SourceId>>,
     }
 
     impl Default for AccountPage {
         fn default() -> Self {
             Self {
                 dbus_client: RefCell::new(None),
+                account_group: RefCell::new(None),
+                storage_group: RefCell::new(None),
+                loading_group: RefCell::new(None),
+                error_group: RefCell::new(None),
+                error_status: RefCell::new(None),
+                overview_loading: Cell::new(false),
+                account_loaded: Cell::new(false),
                 email_row: RefCell::new(None),
                 name_row: RefCell::new(None),
+                type_row: RefCell::new(None),
+                connection_row: RefCell::new(None),
+                restart_button: RefCell::new(None),
+                reconnecting: Cell::new(false),
+                presence_watch: RefCell::new(None),
+                test_connection_button: RefCell::new(None),
                 level_bar: RefCell::new(None),
                 quota_label: RefCell::new(None),
+                as_of_label: RefCell::new(None),
+                session_up_row: RefCell::new(None),
+                session_down_row: RefCell::new(None),
+                total_up_row: RefCell::new(None),
+                total_down_row: RefCell::new(None),
+                stats_source: RefCell::new(None),
             }
         }
     }
@@ -52,7 +170,16 @@ mod imp {
         type ParentType = adw::PreferencesPage;
     }
 
-    impl ObjectImpl for AccountPage {}
+    impl ObjectImpl for AccountPage {
+        fn dispose(&self) {
+            if let Some(source_id) = self.stats_source.borrow_mut().take() {
+                source_id.remove();
+            }
+            if let Some(handle) = self.presence_watch.borrow_mut().take() {
+                handle.abort();
+            }
+        }
+    }
     impl WidgetImpl for AccountPage {}
     impl PreferencesPageImpl for AccountPage {}
 }
@@ -75,12 +202,23 @@ impl AccountPage {
             .replace(Some(dbus_client.clone()));
 
         page.build_ui();
-        page.load_account_info();
-        page.load_quota();
+        page.load_overview();
+        page.load_transfer_stats();
+        page.start_stats_refresh();
+        page.watch_daemon_presence();
 
         page
     }
 
+    /// Fetch the account overview and transfer statistics again. The
+    /// spinner replaces the previous account details until they arrive; a
+    /// fetch already in flight is not repeated.
+    pub fn reload(&self) {
+        self.imp().account_loaded.set(false);
+        self.load_overview();
+        self.load_transfer_stats();
+    }
+
     fn build_ui(&self) {
         let imp = self.imp();
 
@@ -92,18 +230,65 @@ impl AccountPage {
 
         let email_row = adw::ActionRow::builder()
             .title(&gettext("Email"))
-            .subtitle(&gettext("Loading..."))
             .build();
         imp.email_row.replace(Some(email_row.clone()));
 
         let name_row = adw::ActionRow::builder()
             .title(&gettext("Display Name"))
-            .subtitle(&gettext("Loading..."))
             .build();
         imp.name_row.replace(Some(name_row.clone()));
 
+        let type_row = adw::ActionRow::builder()
+            .title(&gettext("Account Type"))
+            .build();
+        imp.type_row.replace(Some(type_row.clone()));
+
+        let restart_button = gtk4::Button::builder()
+            .label(&gettext("Restart"))
+            .tooltip_text(&gettext("Restart the LNXDrive daemon"))
+            .valign(gtk4::Align::Center)
+            .visible(false)
+            .build();
+        imp.restart_button.replace(Some(restart_button.clone()));
+
+        let connection_row = adw::ActionRow::builder()
+            .title(&gettext("Daemon"))
+            .subtitle(&gettext("Loading..."))
+            .build();
+        connection_row.add_suffix(&restart_button);
+        imp.connection_row.replace(Some(connection_row.clone()));
+
+        let page = self.clone();
+        restart_button.connect_clicked(move |_| {
+            page.restart_daemon();
+        });
+
         account_group.add(&email_row);
         account_group.add(&name_row);
+        account_group.add(&type_row);
+
+        // Kept out of the Account group so "Restart" stays reachable when
+        // the account info cannot be loaded.
+        let daemon_group = adw::PreferencesGroup::new();
+        daemon_group.add(&connection_row);
+
+        let test_connection_button = gtk4::Button::builder()
+            .label(&gettext("Test Connection"))
+            .valign(gtk4::Align::Center)
+            .build();
+        imp.test_connection_button.replace(Some(test_connection_button.clone()));
+
+        let remote_row = adw::ActionRow::builder()
+            .title(&gettext("OneDrive"))
+            .subtitle(&gettext("Check that OneDrive can be reached with your account"))
+            .build();
+        remote_row.add_suffix(&test_connection_button);
+        daemon_group.add(&remote_row);
+
+        let page = self.clone();
+        test_connection_button.connect_clicked(move |_| {
+            page.test_connection();
+        });
 
         // -- Storage group ---------------------------------------------------
 
@@ -120,6 +305,7 @@ impl AccountPage {
             .margin_top(8)
             .margin_bottom(4)
             .build();
+        level_bar.update_property(&[gtk4::accessible::Property::Label(&gettext("Storage used"))]);
         imp.level_bar.replace(Some(level_bar.clone()));
 
         let quota_label = gtk4::Label::builder()
@@ -132,12 +318,23 @@ impl AccountPage {
             .build();
         imp.quota_label.replace(Some(quota_label.clone()));
 
+        let as_of_label = gtk4::Label::builder()
+            .css_classes(["dim-label", "caption"])
+            .margin_start(12)
+            .margin_end(12)
+            .margin_bottom(8)
+            .halign(gtk4::Align::Start)
+            .visible(false)
+            .build();
+        imp.as_of_label.replace(Some(as_of_label.clone()));
+
         // Wrap the level bar and label inside a Box, then add to the group.
         let storage_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
             .build();
         storage_box.append(&level_bar);
         storage_box.append(&quota_label);
+        storage_box.append(&as_of_label);
 
         // Use a ListBox row-like wrapper via a generic widget in the group.
         // PreferencesGroup expects rows but we can use a raw gtk::ListBoxRow.
@@ -148,6 +345,46 @@ impl AccountPage {
             .build();
         storage_group.add(&storage_row);
 
+        // -- Activity group --------------------------------------------------
+
+        let activity_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Activity"))
+            .description(&gettext("Data transferred by LNXDrive"))
+            .build();
+
+        let reset_button = gtk4::Button::builder()
+            .label(&gettext("Reset Session"))
+            .tooltip_text(&gettext("Reset the session counters"))
+            .css_classes(["flat"])
+            .build();
+        activity_group.set_header_suffix(Some(&reset_button));
+
+        let stat_row = |title: &str| {
+            adw::ActionRow::builder()
+                .title(title)
+                .subtitle(&gettext("Loading..."))
+                .build()
+        };
+        let session_up_row = stat_row(&gettext("Uploaded This Session"));
+        let session_down_row = stat_row(&gettext("Downloaded This Session"));
+        let total_up_row = stat_row(&gettext("Uploaded Since Install"));
+        let total_down_row = stat_row(&gettext("Downloaded Since Install"));
+
+        activity_group.add(&session_up_row);
+        activity_group.add(&session_down_row);
+        activity_group.add(&total_up_row);
+        activity_group.add(&total_down_row);
+
+        imp.session_up_row.replace(Some(session_up_row));
+        imp.session_down_row.replace(Some(session_down_row));
+        imp.total_up_row.replace(Some(total_up_row));
+        imp.total_down_row.replace(Some(total_down_row));
+
+        let page = self.clone();
+        reset_button.connect_clicked(move |_| {
+            page.reset_transfer_stats();
+        });
+
         // -- Session group ---------------------------------------------------
 
         let session_group = adw::PreferencesGroup::builder()
@@ -175,75 +412,329 @@ impl AccountPage {
             page.on_sign_out();
         });
 
+        // -- Loading and error states ----------------------------------------
+
+        let loading_group = adw::PreferencesGroup::new();
+        let spinner = gtk4::Spinner::builder()
+            .spinning(true)
+            .width_request(32)
+            .height_request(32)
+            .halign(gtk4::Align::Center)
+            .margin_top(48)
+            .margin_bottom(48)
+            .build();
+        spinner.update_property(&[gtk4::accessible::Property::Label(&gettext(
+            "Loading account details",
+        ))]);
+        loading_group.add(&spinner);
+
+        let error_group = adw::PreferencesGroup::new();
+        let retry_button = gtk4::Button::builder()
+            .label(&gettext("Retry"))
+            .halign(gtk4::Align::Center)
+            .css_classes(["pill"])
+            .build();
+        let error_status = adw::StatusPage::builder()
+            .icon_name("dialog-error-symbolic")
+            .title(&gettext("Could Not Load Account"))
+            .child(&retry_button)
+            .css_classes(["compact"])
+            .build();
+        error_group.add(&error_status);
+
+        let page = self.clone();
+        retry_button.connect_clicked(move |_| {
+            page.load_overview();
+        });
+
+        imp.account_group.replace(Some(account_group.clone()));
+        imp.storage_group.replace(Some(storage_group.clone()));
+        imp.loading_group.replace(Some(loading_group.clone()));
+        imp.error_group.replace(Some(error_group.clone()));
+        imp.error_status.replace(Some(error_status));
+
         // Add all groups to the page.
+        self.add(&loading_group);
+        self.add(&error_group);
         self.add(&account_group);
+        self.add(&daemon_group);
         self.add(&storage_group);
+        self.add(&activity_group);
         self.add(&session_group);
+        self.set_overview_state(OverviewState::Loading);
     }
 
-    /// Fetch account information from the daemon and populate the rows.
-    fn load_account_info(&self) {
-        let client = match self.imp().dbus_client.borrow().clone() {
+    // -- Account and quota ---------------------------------------------------
+
+    /// Fetch the account info and quota concurrently and update the page
+    /// once both have settled. Until the account info has loaded, the
+    /// spinner stands in for the Account and Storage groups.
+    fn load_overview(&self) {
+        let imp = self.imp();
+        let client = match imp.dbus_client.borrow().clone() {
             Some(c) => c,
             None => return,
         };
+        if imp.overview_loading.replace(true) {
+            return;
+        }
+        if !imp.account_loaded.get() {
+            self.set_overview_state(OverviewState::Loading);
+        }
 
         let page = self.clone();
         glib::MainContext::default().spawn_local(async move {
-            match client.get_account_info().await {
+            let (account, quota) =
+                futures_util::join!(client.get_account_info(), client.get_quota());
+            page.imp().overview_loading.set(false);
+
+            match account {
                 Ok(info) => {
-                    let email = info
-                        .get("email")
-                        .and_then(|v| String::try_from(v.clone()).ok())
-                        .unwrap_or_else(|| gettext("Unknown"));
-                    let display_name = info
-                        .get("display_name")
-                        .and_then(|v| String::try_from(v.clone()).ok())
-                        .unwrap_or_else(|| gettext("Unknown"));
-
-                    if let Some(ref row) = *page.imp().email_row.borrow() {
-                        row.set_subtitle(&email);
-                    }
-                    if let Some(ref row) = *page.imp().name_row.borrow() {
-                        row.set_subtitle(&display_name);
+                    page.show_account_info(&info);
+                    match quota {
+                        Ok((used, total)) => page.update_quota_display(used, total),
+                        Err(e) => {
+                            eprintln!("Could not load quota: {}", e);
+                            if let Some(ref label) = *page.imp().quota_label.borrow() {
+                                label.set_label(&gettext(
+                                    "Storage usage is not available right now",
+                                ));
+                            }
+                        }
                     }
+                    page.imp().account_loaded.set(true);
+                    page.set_overview_state(OverviewState::Loaded);
                 }
+                // Both calls usually fail for the same reason; the account
+                // error is the one reported.
                 Err(e) => {
-                    let error_msg = format!("{}: {}", gettext("Could not load account info"), e);
-                    if let Some(ref row) = *page.imp().email_row.borrow() {
-                        row.set_subtitle(&error_msg);
+                    eprintln!("Could not load account info: {}", e);
+                    if let Ok((used, total)) = quota {
+                        page.update_quota_display(used, total);
                     }
+                    page.show_account_error(&client, &e);
                 }
             }
         });
     }
 
-    /// Fetch quota information and update the level bar and label.
-    fn load_quota(&self) {
+    fn set_overview_state(&self, state: OverviewState) {
+        let imp = self.imp();
+        for (group, visible) in [
+            (&imp.loading_group, state == OverviewState::Loading),
+            (&imp.error_group, state == OverviewState::Failed),
+            (&imp.account_group, state == OverviewState::Loaded),
+            (&imp.storage_group, state == OverviewState::Loaded),
+        ] {
+            if let Some(ref group) = *group.borrow() {
+                group.set_visible(visible);
+            }
+        }
+    }
+
+    // -- Daemon connection ---------------------------------------------------
+
+    /// Show the current daemon state, then follow NameOwnerChanged.
+    fn watch_daemon_presence(&self) {
         let client = match self.imp().dbus_client.borrow().clone() {
             Some(c) => c,
             None => return,
         };
 
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        self.imp().presence_watch.replace(Some(abort_handle));
+
+        let page = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let _ = Abortable::new(async move {
+                let mut presence = match client.receive_daemon_presence().await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("Could not subscribe to NameOwnerChanged: {e}");
+                        return;
+                    }
+                };
+
+                let running = client.daemon_running().await.unwrap_or(false);
+                match page.upgrade() {
+                    Some(page) => page.set_daemon_running(running),
+                    None => return,
+                }
+
+                while let Some(running) = presence.next().await {
+                    let Some(page) = page.upgrade() else { break };
+                    page.set_daemon_running(running);
+                    if running {
+                        // A restarted daemon may have a different account
+                        // state; refresh what we show.
+                        page.load_overview();
+                        page.load_transfer_stats();
+                    }
+                }
+            }, abort_registration).await;
+        });
+    }
+
+    fn set_daemon_running(&self, running: bool) {
+        let imp = self.imp();
+        if running {
+            imp.reconnecting.set(false);
+        }
+
+        let subtitle = if running {
+            gettext("Connected")
+        } else if imp.reconnecting.get() {
+            gettext("Reconnecting...")
+        } else {
+            gettext("Daemon not running")
+        };
+        if let Some(ref row) = *imp.connection_row.borrow() {
+            row.set_subtitle(&subtitle);
+        }
+        if let Some(ref button) = *imp.restart_button.borrow() {
+            button.set_visible(!running && !imp.reconnecting.get());
+        }
+    }
+
+    /// Restart the daemon's systemd user service. The row switches back to
+    /// "Connected" when NameOwnerChanged reports the new owner.
+    fn restart_daemon(&self) {
+        let imp = self.imp();
+        imp.reconnecting.set(true);
+        self.set_daemon_running(false);
+
         let page = self.clone();
         glib::MainContext::default().spawn_local(async move {
-            match client.get_quota().await {
-                Ok((used, total)) => {
-                    page.update_quota_display(used, total);
+            match service::restart().await {
+                Ok(()) => {
+                    // The new owner may have been announced before
+                    // systemctl returned.
+                    let client = page.imp().dbus_client.borrow().clone();
+                    if let Some(client) = client {
+                        if client.daemon_running().await.unwrap_or(false) {
+                            page.set_daemon_running(true);
+                        }
+                    }
                 }
                 Err(e) => {
-                    if let Some(ref label) = *page.imp().quota_label.borrow() {
-                        label.set_label(&format!(
-                            "{}: {}",
-                            gettext("Could not load quota"),
-                            e
-                        ));
-                    }
+                    eprintln!("Could not restart the daemon: {}", e);
+                    page.imp().reconnecting.set(false);
+                    page.set_daemon_running(false);
+                    page.show_toast(&gettext("Could not restart the daemon"));
+                }
+            }
+        });
+    }
+
+    /// Ask the daemon to reach OneDrive and toast the outcome.
+    fn test_connection(&self) {
+        let imp = self.imp();
+        let client = match imp.dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+        if let Some(ref button) = *imp.test_connection_button.borrow() {
+            button.set_sensitive(false);
+        }
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let message = match client.ping_remote().await {
+                Ok(Ok(latency_ms)) => {
+                    gettext("Connected — {} ms").replace("{}", &latency_ms.to_string())
+                }
+                Ok(Err(error)) => {
+                    gettext("Could not reach OneDrive: {}").replace("{}", &error)
                 }
+                Err(e) => {
+                    eprintln!("Could not test the connection: {}", e);
+                    gettext("Could not test the connection, the daemon did not respond")
+                }
+            };
+            page.show_toast(&message);
+            if let Some(ref button) = *page.imp().test_connection_button.borrow() {
+                button.set_sensitive(true);
             }
         });
     }
 
+    /// Show a toast on the enclosing PreferencesDialog.
+    fn show_toast(&self, message: &str) {
+        if let Some(dialog) = self
+            .ancestor(adw::PreferencesDialog::static_type())
+            .and_then(|w| w.downcast::<adw::PreferencesDialog>().ok())
+        {
+            dialog.add_toast(adw::Toast::new(message));
+        }
+    }
+
+    /// Fill the Account rows.
+    fn show_account_info(&self, info: &AccountInfo) {
+        let imp = self.imp();
+        let email = info.email.clone().unwrap_or_else(|| gettext("Unknown"));
+        let display_name = info
+            .display_name
+            .clone()
+            .unwrap_or_else(|| gettext("Unknown"));
+
+        if let Some(ref row) = *imp.email_row.borrow() {
+            row.set_subtitle(&email);
+        }
+        if let Some(ref row) = *imp.name_row.borrow() {
+            row.set_subtitle(&display_name);
+        }
+        if let Some(ref row) = *imp.type_row.borrow() {
+            row.set_subtitle(&info.account_type);
+            // Business quotas are set (and may be pooled) by the
+            // organisation's administrator.
+            if info.is_business() {
+                row.set_tooltip_text(Some(&gettext(
+                    "Storage for this account is managed by your organisation",
+                )));
+            }
+        }
+        if let Some(ref label) = *imp.as_of_label.borrow() {
+            match info.as_of_datetime() {
+                Some(as_of) => {
+                    label.set_label(&last_updated_caption(&as_of));
+                    let absolute = as_of
+                        .to_local()
+                        .ok()
+                        .and_then(|dt| dt.format("%c").ok())
+                        .map(|s| s.to_string());
+                    label.set_tooltip_text(absolute.as_deref());
+                    label.set_visible(true);
+                }
+                None => label.set_visible(false),
+            }
+        }
+    }
+
+    /// Show the error state for a failed account info fetch, unless the
+    /// account info loaded before (the rows then keep their last values).
+    fn show_account_error(&self, client: &DbusClient, e: &DbusError) {
+        // An expired token surfaces here first; offer to sign in again
+        // rather than leaving stale rows behind.
+        if e.kind() == Some(DaemonErrorKind::NotAuthenticated) {
+            if let Some(window) = gtk4::gio::Application::default()
+                .and_then(|app| app.downcast::<gtk4::Application>().ok())
+                .and_then(|app| app.active_window())
+                .and_then(|win| win.downcast::<crate::window::LnxdriveWindow>().ok())
+            {
+                window.on_session_expired(client);
+            }
+        }
+
+        let imp = self.imp();
+        if imp.account_loaded.get() {
+            return;
+        }
+        if let Some(ref status) = *imp.error_status.borrow() {
+            status.set_description(Some(&load_error_description(e)));
+        }
+        self.set_overview_state(OverviewState::Failed);
+    }
+
     /// Update the quota level bar and label with the given byte values.
     fn update_quota_display(&self, used_bytes: u64, total_bytes: u64) {
         let imp = self.imp();
@@ -258,38 +749,96 @@ impl AccountPage {
             bar.set_value(fraction);
         }
 
-        let used_gb = used_bytes as f64 / 1_073_741_824.0;
-        let total_gb = total_bytes as f64 / 1_073_741_824.0;
-
-        let text = format!(
-            "{:.1} GB {} {:.1} GB {}",
-            used_gb,
-            gettext("of"),
-            total_gb,
-            gettext("used")
-        );
+        // Translators: {used} and {total} are sizes like "1.5 GiB", already
+        // formatted for the user's locale.
+        let text = gettext("{used} of {total} used")
+            .replace("{used}", &util::format_size(used_bytes))
+            .replace("{total}", &util::format_size(total_bytes));
 
         if let Some(ref label) = *imp.quota_label.borrow() {
             label.set_label(&text);
         }
+        if let Some(ref bar) = *imp.level_bar.borrow() {
+            bar.update_property(&[gtk4::accessible::Property::ValueText(&text)]);
+        }
+    }
+
+    /// Fetch transfer counters and update the Activity rows.
+    fn load_transfer_stats(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.get_transfer_stats().await {
+                Ok(stats) => page.update_stats_display(&stats),
+                Err(e) => {
+                    eprintln!("Could not load transfer stats: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Re-read the transfer counters every `STATS_REFRESH_SECS` while the
+    /// page is alive.
+    fn start_stats_refresh(&self) {
+        let page_weak = self.downgrade();
+        let source_id = glib::timeout_add_seconds_local(STATS_REFRESH_SECS, move || {
+            match page_weak.upgrade() {
+                Some(page) => {
+                    page.load_transfer_stats();
+                    glib::ControlFlow::Continue
+                }
+                None => glib::ControlFlow::Break,
+            }
+        });
+        self.imp().stats_source.replace(Some(source_id));
+    }
+
+    fn update_stats_display(&self, stats: &TransferStats) {
+        let imp = self.imp();
+        for (row, bytes) in [
+            (&imp.session_up_row, stats.session_uploaded),
+            (&imp.session_down_row, stats.session_downloaded),
+            (&imp.total_up_row, stats.total_uploaded),
+            (&imp.total_down_row, stats.total_downloaded),
+        ] {
+            if let Some(ref row) = *row.borrow() {
+                row.set_subtitle(&util::format_size(bytes));
+            }
+        }
+    }
+
+    /// Zero the session counters on the daemon and refresh the display.
+    fn reset_transfer_stats(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            if let Err(e) = client.reset_transfer_stats().await {
+                eprintln!("Could not reset transfer stats: {}", e);
+            }
+            page.load_transfer_stats();
+        });
     }
 
     /// Prompt the user to confirm sign-out, then log out via D-Bus and switch
     /// back to the onboarding view.
     fn on_sign_out(&self) {
         // Create a confirmation dialog.
-        let confirm = adw::AlertDialog::builder()
-            .heading(&gettext("Sign Out?"))
-            .body(&gettext(
+        let confirm = util::confirmation_dialog(
+            &gettext("Sign Out?"),
+            &gettext(
                 "You will be signed out of your OneDrive account. Syncing will stop.",
-            ))
-            .build();
-
-        confirm.add_response("cancel", &gettext("Cancel"));
+            ),
+        );
         confirm.add_response("sign-out", &gettext("Sign Out"));
         confirm.set_response_appearance("sign-out", adw::ResponseAppearance::Destructive);
-        confirm.set_default_response(Some("cancel"));
-        confirm.set_close_response("cancel");
 
         let page = self.clone();
         confirm.connect_response(None, move |_dialog, response| {
@@ -334,8 +883,84 @@ impl AccountPage {
 
             // Switch the main window to onboarding.
             if let Some(window) = app_window {
-                window.show_onboarding(client);
+                window.show_onboarding(client, None);
             }
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::{
+        action_rows, labels, present, run_widget_test, wait_until, MockState, TestDaemon,
+    };
+
+    fn has_row(page: &AccountPage, title: &str, subtitle: &str) -> bool {
+        action_rows(page).contains(&(title.to_string(), subtitle.to_string()))
+    }
+
+    /// Press "Test Connection" on a page in a preferences dialog and wait for
+    /// the toast saying `expected`.
+    fn test_connection(state: MockState, expected: &str) {
+        let daemon = TestDaemon::start(state);
+        let window = present(&gtk4::Box::new(gtk4::Orientation::Vertical, 0));
+        let preferences = adw::PreferencesDialog::new();
+        let page = AccountPage::new(daemon.client());
+        preferences.add(&page);
+        preferences.present(Some(&window));
+
+        let button = page.imp().test_connection_button.borrow().clone().unwrap();
+        button.emit_clicked();
+        wait_until("the connection test", || {
+            labels(&preferences).iter().any(|l| l == expected)
+        });
+        assert!(button.is_sensitive());
+
+        window.destroy();
+    }
+
+    #[test]
+    fn shows_the_account_and_its_quota() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let page = AccountPage::new(daemon.client());
+            let window = present(&page);
+
+            wait_until("the account details", || {
+                has_row(&page, "Email", "user@example.com")
+            });
+            assert!(has_row(&page, "Display Name", "Test User"));
+            assert!(has_row(&page, "Account Type", "Personal"));
+
+            let (used, total) = daemon.state().quota;
+            let quota = gettext("{used} of {total} used")
+                .replace("{used}", &util::format_size(used))
+                .replace("{total}", &util::format_size(total));
+            assert!(labels(&page).contains(&quota));
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn tests_the_connection() {
+        run_widget_test(|| {
+            let expected = gettext("Connected — {} ms").replace("{}", "42");
+            test_connection(MockState::default(), &expected);
+        });
+    }
+
+    #[test]
+    fn says_why_the_connection_test_failed() {
+        run_widget_test(|| {
+            let state = MockState {
+                ping: Err("Network is unreachable".to_string()),
+                ..MockState::default()
+            };
+            let expected =
+                gettext("Could not reach OneDrive: {}").replace("{}", "Network is unreachable");
+            test_connection(state, &expected);
+        });
+    }
+}
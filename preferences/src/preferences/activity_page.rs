@@ -0,0 +1,649 @@
+// Activity Page — adw::PreferencesPage subclass
+//
+// Lists the daemon's in-flight uploads and downloads with a per-file progress
+// bar. The list is polled once per second; rows are updated in place, added
+// for new transfers and dropped when a transfer completes. Only the first
+// MAX_TRANSFER_ROWS transfers get a row; the rest are summarised. When the
+// daemon can't report its transfers, the last list stays up with a warning
+// row instead of claiming everything is up to date.
+//
+// A "Problems" group below lists items that failed to sync (transfer or
+// permission errors, as opposed to content conflicts) with per-item and
+// "Retry All" actions. Items the daemon has stopped retrying are shown in red
+// as "Won't retry automatically", so a file it gave up on doesn't look like a
+// stuck sync.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use gettextrs::{gettext, ngettext};
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
+
+use crate::conflicts::conflict_dialog::labelled_icon;
+use crate::dbus_client::DbusClient;
+use crate::util;
+
+/// How often the transfer list is refreshed.
+const REFRESH_INTERVAL_MS: u64 = 1000;
+
+/// Maximum number of transfers shown as individual rows.
+const MAX_TRANSFER_ROWS: usize = 50;
+
+// ---------------------------------------------------------------------------
+// TransferInfo — deserialized from daemon JSON
+// ---------------------------------------------------------------------------
+
+/// A single in-flight transfer as reported by `GetActiveTransfers`.
+#[derive(Debug, Clone)]
+pub struct TransferInfo {
+    pub path: String,
+    pub is_upload: bool,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// Current speed in bytes per second.
+    pub speed: u64,
+}
+
+impl TransferInfo {
+    /// Parse a single transfer JSON value.
+    pub fn from_json(val: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            path: val.get("path")?.as_str()?.to_string(),
+            is_upload: val.get("direction").and_then(|v| v.as_str()) == Some("upload"),
+            bytes_done: val.get("bytes_done").and_then(|v| v.as_u64()).unwrap_or(0),
+            bytes_total: val.get("bytes_total").and_then(|v| v.as_u64()).unwrap_or(0),
+            speed: val.get("speed").and_then(|v| v.as_u64()).unwrap_or(0),
+        })
+    }
+
+    /// Parse a JSON array string into a list of transfers. Fails on a
+    /// malformed reply rather than reporting nothing in flight.
+    pub fn from_json_array(json_str: &str) -> Result<Vec<Self>, serde_json::Error> {
+        let arr = serde_json::from_str::<Vec<serde_json::Value>>(json_str)?;
+        Ok(arr.iter().filter_map(Self::from_json).collect())
+    }
+
+    /// Return the filename (last path component).
+    pub fn filename(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    /// Completed fraction in 0.0..=1.0 (0.0 when the size is unknown).
+    pub fn fraction(&self) -> f64 {
+        if self.bytes_total > 0 {
+            (self.bytes_done as f64 / self.bytes_total as f64).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SyncErrorInfo — deserialized from daemon JSON
+// ---------------------------------------------------------------------------
+
+/// An item the daemon failed to sync, as reported by `GetSyncErrors`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncErrorInfo {
+    pub path: String,
+    pub message: String,
+    pub timestamp: String,
+    /// Transient errors (network, throttling) are expected to succeed on
+    /// retry; permanent ones (permissions, invalid names) need user action.
+    pub transient: bool,
+    /// The daemon has given up on this item and only retries it when asked.
+    pub failed_permanently: bool,
+}
+
+impl SyncErrorInfo {
+    /// Parse a single error JSON value.
+    pub fn from_json(val: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            path: val.get("path")?.as_str()?.to_string(),
+            message: val
+                .get("message")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            timestamp: val
+                .get("timestamp")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            transient: val.get("kind").and_then(|v| v.as_str()) == Some("transient"),
+            failed_permanently: val
+                .get("failed_permanently")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        })
+    }
+
+    /// Parse a JSON array string into a list of errors. Fails on a
+    /// malformed reply rather than reporting no problems.
+    pub fn from_json_array(json_str: &str) -> Result<Vec<Self>, serde_json::Error> {
+        let arr = serde_json::from_str::<Vec<serde_json::Value>>(json_str)?;
+        Ok(arr.iter().filter_map(Self::from_json).collect())
+    }
+
+    /// Return the filename (last path component).
+    pub fn filename(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ActivityPage — adw::PreferencesPage subclass
+// ---------------------------------------------------------------------------
+
+mod imp {
+    use super::*;
+    use gtk4::subclass::prelude::*;
+    use libadwaita::subclass::prelude::*;
+
+    pub struct ActivityPage {
+        pub dbus_client: RefCell<Option<DbusClient>>,
+        pub transfers_group: RefCell<Option<adw::PreferencesGroup>>,
+        /// Rows for the transfers currently shown, keyed by path.
+        pub rows: RefCell<HashMap<String, (adw::ActionRow, gtk4::ProgressBar)>>,
+        pub empty_row: RefCell<Option<adw::ActionRow>>,
+        /// Shown while the transfer list can't be read.
+        pub unavailable_row: RefCell<Option<adw::ActionRow>>,
+        pub overflow_row: RefCell<Option<adw::ActionRow>>,
+        pub problems_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub problem_rows: RefCell<Vec<adw::ActionRow>>,
+        /// Last error list shown, so the group is only rebuilt on change.
+        pub problems: RefCell<Vec<SyncErrorInfo>>,
+        pub refresh_source: RefCell<Option<glib::SourceId>>,
+    }
+
+    impl Default for ActivityPage {
+        fn default() -> Self {
+            Self {
+                dbus_client: RefCell::new(None),
+                transfers_group: RefCell::new(None),
+                rows: RefCell::new(HashMap::new()),
+                empty_row: RefCell::new(None),
+                unavailable_row: RefCell::new(None),
+                overflow_row: RefCell::new(None),
+                problems_group: RefCell::new(None),
+                problem_rows: RefCell::new(Vec::new()),
+                problems: RefCell::new(Vec::new()),
+                refresh_source: RefCell::new(None),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ActivityPage {
+        const NAME: &'static str = "LnxdriveActivityPage";
+        type Type = super::ActivityPage;
+        type ParentType = adw::PreferencesPage;
+    }
+
+    impl ObjectImpl for ActivityPage {
+        fn dispose(&self) {
+            if let Some(source_id) = self.refresh_source.borrow_mut().take() {
+                source_id.remove();
+            }
+        }
+    }
+    impl WidgetImpl for ActivityPage {}
+    impl PreferencesPageImpl for ActivityPage {}
+}
+
+glib::wrapper! {
+    pub struct ActivityPage(ObjectSubclass<imp::ActivityPage>)
+        @extends adw::PreferencesPage, gtk4::Widget,
+        @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget;
+}
+
+impl ActivityPage {
+    pub fn new(dbus_client: &DbusClient) -> Self {
+        let page: Self = glib::Object::builder()
+            .property("icon-name", "network-transmit-receive-symbolic")
+            .property("title", gettext("Activity"))
+            .build();
+
+        page.imp()
+            .dbus_client
+            .replace(Some(dbus_client.clone()));
+
+        page.build_ui();
+        page.load_transfers();
+        page.load_problems();
+        page.start_refresh();
+
+        page
+    }
+
+    fn build_ui(&self) {
+        let imp = self.imp();
+
+        let transfers_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Current Transfers"))
+            .build();
+
+        let empty_row = adw::ActionRow::builder()
+            .title(&gettext("No active transfers"))
+            .subtitle(&gettext("All files are up to date"))
+            .build();
+        empty_row.add_prefix(&labelled_icon("emblem-ok-symbolic", &gettext("Up to date")));
+        transfers_group.add(&empty_row);
+
+        let unavailable_row = adw::ActionRow::builder()
+            .title(&gettext("Transfers unavailable"))
+            .subtitle(&gettext("Could not get the current transfers from LNXDrive"))
+            .css_classes(["warning"])
+            .visible(false)
+            .build();
+        unavailable_row.add_prefix(&labelled_icon(
+            "dialog-warning-symbolic",
+            &gettext("Warning"),
+        ));
+        transfers_group.add(&unavailable_row);
+
+        let overflow_row = adw::ActionRow::builder()
+            .visible(false)
+            .build();
+        transfers_group.add(&overflow_row);
+
+        imp.empty_row.replace(Some(empty_row));
+        imp.unavailable_row.replace(Some(unavailable_row));
+        imp.overflow_row.replace(Some(overflow_row));
+        imp.transfers_group.replace(Some(transfers_group.clone()));
+
+        // -- Problems group ---------------------------------------------------
+        let problems_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Problems"))
+            .description(&gettext("Items that could not be synced"))
+            .visible(false)
+            .build();
+
+        let retry_all_button = gtk4::Button::builder()
+            .label(&gettext("Retry All"))
+            .css_classes(["flat"])
+            .build();
+        let page = self.clone();
+        retry_all_button.connect_clicked(move |_| {
+            page.retry_all();
+        });
+        problems_group.set_header_suffix(Some(&retry_all_button));
+
+        imp.problems_group.replace(Some(problems_group.clone()));
+
+        self.add(&transfers_group);
+        self.add(&problems_group);
+    }
+
+    /// Poll the transfer list every `REFRESH_INTERVAL_MS` while the page is
+    /// alive.
+    fn start_refresh(&self) {
+        let page_weak = self.downgrade();
+        let source_id = glib::timeout_add_local(
+            std::time::Duration::from_millis(REFRESH_INTERVAL_MS),
+            move || match page_weak.upgrade() {
+                Some(page) => {
+                    page.load_transfers();
+                    page.load_problems();
+                    glib::ControlFlow::Continue
+                }
+                None => glib::ControlFlow::Break,
+            },
+        );
+        self.imp().refresh_source.replace(Some(source_id));
+    }
+
+    /// Fetch the active transfers from the daemon and update the rows.
+    fn load_transfers(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.get_active_transfers().await {
+                Ok(json_str) => match TransferInfo::from_json_array(&json_str) {
+                    Ok(transfers) => page.update_transfers(&transfers),
+                    Err(e) => {
+                        eprintln!("Could not parse active transfers: {e}");
+                        page.show_transfers_unavailable(true);
+                    }
+                },
+                // The daemon is down or too old for the method; keep the
+                // last list rather than report nothing in flight.
+                Err(e) => {
+                    eprintln!("Could not load active transfers: {e}");
+                    page.show_transfers_unavailable(true);
+                }
+            }
+        });
+    }
+
+    /// Show or hide the warning that the transfer list can't be read. The
+    /// "No active transfers" row is hidden while it shows.
+    fn show_transfers_unavailable(&self, unavailable: bool) {
+        let imp = self.imp();
+        if let Some(ref row) = *imp.unavailable_row.borrow() {
+            row.set_visible(unavailable);
+        }
+        if unavailable {
+            if let Some(ref empty_row) = *imp.empty_row.borrow() {
+                empty_row.set_visible(false);
+            }
+        }
+    }
+
+    /// Reconcile the shown rows with the latest transfer list.
+    fn update_transfers(&self, transfers: &[TransferInfo]) {
+        let imp = self.imp();
+        let group = match imp.transfers_group.borrow().clone() {
+            Some(g) => g,
+            None => return,
+        };
+        self.show_transfers_unavailable(false);
+
+        let shown = &transfers[..transfers.len().min(MAX_TRANSFER_ROWS)];
+
+        // Drop rows for transfers that have finished (or fell past the cap).
+        imp.rows.borrow_mut().retain(|path, (row, _)| {
+            let keep = shown.iter().any(|t| &t.path == path);
+            if !keep {
+                group.remove(row);
+            }
+            keep
+        });
+
+        for transfer in shown {
+            let existing = imp.rows.borrow().get(&transfer.path).cloned();
+            let (row, bar) = match existing {
+                Some(pair) => pair,
+                None => {
+                    let pair = Self::create_transfer_row(transfer);
+                    group.add(&pair.0);
+                    imp.rows
+                        .borrow_mut()
+                        .insert(transfer.path.clone(), pair.clone());
+                    pair
+                }
+            };
+            row.set_subtitle(&Self::progress_text(transfer));
+            bar.set_fraction(transfer.fraction());
+        }
+
+        if let Some(ref empty_row) = *imp.empty_row.borrow() {
+            empty_row.set_visible(transfers.is_empty());
+        }
+
+        if let Some(ref overflow_row) = *imp.overflow_row.borrow() {
+            let hidden = transfers.len() - shown.len();
+            overflow_row.set_visible(hidden > 0);
+            if hidden > 0 {
+                overflow_row.set_title(
+                    &ngettext(
+                        "{} more transfer in progress",
+                        "{} more transfers in progress",
+                        hidden as u32,
+                    )
+                    .replace("{}", &hidden.to_string()),
+                );
+                // Keep the summary below the per-file rows.
+                group.remove(overflow_row);
+                group.add(overflow_row);
+            }
+        }
+    }
+
+    // -- Problems -------------------------------------------------------------
+
+    /// Fetch the failed items from the daemon and update the Problems group.
+    fn load_problems(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            // On failure the last list stays, rather than an empty one.
+            match client.get_sync_errors().await {
+                Ok(json_str) => match SyncErrorInfo::from_json_array(&json_str) {
+                    Ok(errors) => page.update_problems(errors),
+                    Err(e) => eprintln!("Could not parse sync errors: {e}"),
+                },
+                Err(e) => {
+                    eprintln!("Could not load sync errors: {e}");
+                }
+            }
+        });
+    }
+
+    fn update_problems(&self, errors: Vec<SyncErrorInfo>) {
+        let imp = self.imp();
+        if *imp.problems.borrow() == errors {
+            return;
+        }
+
+        let group = match imp.problems_group.borrow().clone() {
+            Some(g) => g,
+            None => return,
+        };
+
+        for row in imp.problem_rows.borrow_mut().drain(..) {
+            group.remove(&row);
+        }
+
+        for error in &errors {
+            let row = self.create_problem_row(error);
+            group.add(&row);
+            imp.problem_rows.borrow_mut().push(row);
+        }
+
+        group.set_visible(!errors.is_empty());
+        imp.problems.replace(errors);
+    }
+
+    fn create_problem_row(&self, error: &SyncErrorInfo) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(error.filename())
+            .subtitle(&error.message)
+            .tooltip_text(&format!("{}\n{}", error.path, error.timestamp))
+            .build();
+
+        let (icon, icon_label) = if error.failed_permanently {
+            ("action-unavailable-symbolic", gettext("Won't retry automatically"))
+        } else if error.transient {
+            ("network-error-symbolic", gettext("Temporary error"))
+        } else {
+            ("dialog-error-symbolic", gettext("Error"))
+        };
+        row.add_prefix(&labelled_icon(icon, &icon_label));
+
+        if error.failed_permanently {
+            row.add_css_class("error");
+            row.set_subtitle(&format!(
+                "{} — {}",
+                error.message,
+                gettext("Won't retry automatically")
+            ));
+        }
+
+        let retry_label = if error.failed_permanently {
+            gettext("Try Again")
+        } else {
+            gettext("Retry")
+        };
+        let retry_button = gtk4::Button::builder()
+            .label(&retry_label)
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        row.add_suffix(&retry_button);
+
+        let page = self.clone();
+        let path = error.path.clone();
+        retry_button.connect_clicked(move |btn| {
+            btn.set_sensitive(false);
+            page.retry_paths(vec![path.clone()]);
+        });
+
+        row
+    }
+
+    /// Retry every item currently listed in the Problems group.
+    fn retry_all(&self) {
+        let paths = self
+            .imp()
+            .problems
+            .borrow()
+            .iter()
+            .map(|e| e.path.clone())
+            .collect();
+        self.retry_paths(paths);
+    }
+
+    /// Ask the daemon to retry the given items, then refresh the list.
+    fn retry_paths(&self, paths: Vec<String>) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            for path in &paths {
+                if let Err(e) = client.retry_item(path).await {
+                    eprintln!("Could not retry {path}: {e}");
+                }
+            }
+            // Force a rebuild so per-row Retry buttons become sensitive again.
+            page.imp().problems.borrow_mut().clear();
+            page.load_problems();
+        });
+    }
+
+    fn create_transfer_row(transfer: &TransferInfo) -> (adw::ActionRow, gtk4::ProgressBar) {
+        let row = adw::ActionRow::builder()
+            .title(transfer.filename())
+            .build();
+
+        let (icon, icon_label) = if transfer.is_upload {
+            ("go-up-symbolic", gettext("Upload"))
+        } else {
+            ("go-down-symbolic", gettext("Download"))
+        };
+        row.add_prefix(&labelled_icon(icon, &icon_label));
+
+        let bar = gtk4::ProgressBar::builder()
+            .valign(gtk4::Align::Center)
+            .width_request(120)
+            .build();
+        row.add_suffix(&bar);
+
+        (row, bar)
+    }
+
+    /// e.g. "/Documents/report.docx — 1.2 MiB of 5.0 MiB (300 KiB/s)"
+    fn progress_text(transfer: &TransferInfo) -> String {
+        // Translators: {done} and {total} are sizes like "1.2 MiB", {speed} a size per second.
+        let progress = gettext("{done} of {total} ({speed}/s)")
+            .replace("{done}", &util::format_size(transfer.bytes_done))
+            .replace("{total}", &util::format_size(transfer.bytes_total))
+            .replace("{speed}", &util::format_size(transfer.speed));
+        format!("{} — {}", transfer.path, progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::test_harness::{
+        find_widgets, present, run_widget_test, wait_until, MockState, TestDaemon,
+    };
+
+    /// Title of each row in the Problems group.
+    fn problem_titles(page: &ActivityPage) -> Vec<String> {
+        page.imp()
+            .problem_rows
+            .borrow()
+            .iter()
+            .map(|row| row.title().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rejects_malformed_replies() {
+        assert!(TransferInfo::from_json_array("{\"path\": \"/a\"}").is_err());
+        assert!(SyncErrorInfo::from_json_array("not json").is_err());
+        assert!(TransferInfo::from_json_array("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn keeps_the_last_transfers_when_they_cannot_be_read() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState {
+                active_transfers: vec![json!({
+                    "path": "/Photos/IMG_0042.jpg",
+                    "direction": "upload",
+                    "bytes_done": 1_048_576,
+                    "bytes_total": 4_194_304,
+                    "speed": 524_288,
+                })],
+                ..MockState::default()
+            });
+            let page = ActivityPage::new(daemon.client());
+            let window = present(&page);
+
+            let imp = page.imp();
+            wait_until("the transfer row", || imp.rows.borrow().len() == 1);
+
+            daemon.state().transfers_supported = false;
+            let unavailable_row = imp.unavailable_row.borrow().clone().unwrap();
+            wait_until("the warning", || unavailable_row.is_visible());
+            assert!(imp.rows.borrow().contains_key("/Photos/IMG_0042.jpg"));
+            assert!(!imp.empty_row.borrow().as_ref().unwrap().is_visible());
+
+            daemon.state().transfers_supported = true;
+            wait_until("the warning to go", || !unavailable_row.is_visible());
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn marks_items_the_daemon_gave_up_on() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let page = ActivityPage::new(daemon.client());
+            let window = present(&page);
+
+            wait_until("the problem rows", || problem_titles(&page).len() == 2);
+            let rows = page.imp().problem_rows.borrow().clone();
+            assert_eq!(problem_titles(&page), ["holiday.mp4", "draft.docx"]);
+            assert!(rows[0].has_css_class("error"));
+            assert!(rows[0]
+                .subtitle()
+                .unwrap_or_default()
+                .ends_with(&gettext("Won't retry automatically")));
+            assert!(!rows[1].has_css_class("error"));
+
+            find_widgets::<gtk4::Button>(&rows[0])
+                .into_iter()
+                .find(|b| b.label().is_some_and(|l| l == gettext("Try Again")))
+                .expect("a Try Again button")
+                .emit_clicked();
+            wait_until("the retried item to go", || {
+                problem_titles(&page) == ["draft.docx"]
+            });
+
+            window.destroy();
+        });
+    }
+}
@@ -1,12 +1,15 @@
 // Advanced Page — adw::PreferencesPage subclass
 //
-// Contains exclusion patterns (FR-015) and bandwidth limit controls (FR-017).
-// Patterns are displayed in a ListBox with per-row delete buttons and a text
-// entry for adding new patterns. Bandwidth limits use adw::SpinRow widgets.
+// Contains exclusion patterns (FR-015) and bandwidth limit controls (FR-017),
+// followed by groups for transfers, conflicts, network, storage, startup,
+// notifications, appearance and the daemon service, and a Developer group
+// shown in developer mode. Each group is built by its own `build_*_group`.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
-use gettextrs::gettext;
+use futures_util::future::{AbortHandle, Abortable};
+use futures_util::StreamExt;
+use gettextrs::{gettext, ngettext};
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita as adw;
@@ -14,7 +17,15 @@ use libadwaita::prelude::*;
 
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 
-use crate::dbus_client::DbusClient;
+use crate::autostart;
+use crate::config::{self, redact_config};
+use crate::dbus_client::{DaemonErrorKind, DbusClient};
+use crate::network;
+use crate::service::{self, ActiveState};
+use crate::settings::{keys, AppSettings, NotificationLevel};
+use crate::util;
+
+use super::config_editor::ConfigEditorDialog;
 
 // ---------------------------------------------------------------------------
 // AdvancedPage — adw::PreferencesPage subclass
@@ -30,9 +41,67 @@ mod imp {
         pub patterns_list: RefCell<Option<gtk4::ListBox>>,
         pub patterns_store: RefCell<Vec<String>>,
         pub pattern_entry: RefCell<Option<gtk4::Entry>>,
+        /// Lists sample matches for the pattern being typed.
+        pub preview_row: RefCell<Option<adw::ExpanderRow>>,
+        pub preview_rows: RefCell<Vec<adw::ActionRow>>,
+        pub preview_source: RefCell<Option<glib::SourceId>>,
+        /// Bumped on every preview request so stale replies are dropped.
+        pub preview_generation: Cell<u32>,
+        /// Case-sensitivity and file-name-only switches for the patterns,
+        /// and the values last known to be in the daemon config.
+        pub case_sensitive_row: RefCell<Option<adw::SwitchRow>>,
+        pub match_basename_row: RefCell<Option<adw::SwitchRow>>,
+        pub case_sensitive: Cell<bool>,
+        pub match_basename: Cell<bool>,
+        /// One chip per `SUGGESTED_PATTERNS` entry, insensitive once added.
+        pub suggestion_buttons: RefCell<Vec<gtk4::Button>>,
         pub upload_row: RefCell<Option<adw::SpinRow>>,
         pub download_row: RefCell<Option<adw::SpinRow>>,
+        pub unit_row: RefCell<Option<adw::ComboRow>>,
+        /// Effective limits in KB/s — the source of truth for saving. The
+        /// spin rows only display these in the selected unit.
+        pub upload_kbps: Cell<u32>,
+        pub download_kbps: Cell<u32>,
+        /// Set while the spin rows are being updated programmatically so
+        /// their value-notify handlers don't write back rounded values.
+        pub updating_rows: Cell<bool>,
+        /// Last (upload, download) limits known to be in the daemon config.
+        /// Restored if the user backs out of a low-limit confirmation.
+        pub committed_kbps: Cell<(u32, u32)>,
         pub debounce_source: RefCell<Option<glib::SourceId>>,
+        /// Parallel transfers row, the value last known to be in the daemon
+        /// config, and its own debounce timer.
+        pub parallel_row: RefCell<Option<adw::SpinRow>>,
+        pub parallel_transfers: Cell<u32>,
+        pub parallel_source: RefCell<Option<glib::SourceId>>,
+        /// Conflict copy template entry, its preview row, and the template
+        /// last known to be in the daemon config.
+        pub conflict_template_row: RefCell<Option<adw::EntryRow>>,
+        pub conflict_preview_row: RefCell<Option<adw::ActionRow>>,
+        pub conflict_template: RefCell<String>,
+        /// "Only Sync on Wi-Fi or Ethernet" switch, and the value last
+        /// known to be in the daemon config.
+        pub connection_row: RefCell<Option<adw::SwitchRow>>,
+        pub wifi_ethernet_only: Cell<bool>,
+        /// "Free Up Space" button and the progress bar shown while the
+        /// daemon dehydrates files.
+        pub free_space_button: RefCell<Option<gtk4::Button>>,
+        pub free_space_progress: RefCell<Option<gtk4::ProgressBar>>,
+        /// "Verify Files" button and its progress bar.
+        pub verify_button: RefCell<Option<gtk4::Button>>,
+        pub verify_progress: RefCell<Option<gtk4::ProgressBar>>,
+        /// SyncProgress subscription while freeing up space or verifying.
+        /// Both report through the same signal, so only one of them runs at
+        /// a time: each disables both buttons until it returns.
+        pub progress_watch: RefCell<Option<AbortHandle>>,
+        /// systemd user service controls; hidden until the unit is found.
+        pub service_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub service_row: RefCell<Option<adw::ActionRow>>,
+        pub start_button: RefCell<Option<gtk4::Button>>,
+        pub stop_button: RefCell<Option<gtk4::Button>>,
+        pub restart_button: RefCell<Option<gtk4::Button>>,
+        /// Read-only view of the daemon config (developer mode only).
+        pub config_view: RefCell<Option<gtk4::TextView>>,
     }
 
     impl Default for AdvancedPage {
@@ -42,9 +111,42 @@ mod imp {
                 patterns_list: RefCell::new(None),
                 patterns_store: RefCell::new(Vec::new()),
                 pattern_entry: RefCell::new(None),
+                preview_row: RefCell::new(None),
+                preview_rows: RefCell::new(Vec::new()),
+                preview_source: RefCell::new(None),
+                preview_generation: Cell::new(0),
+                case_sensitive_row: RefCell::new(None),
+                match_basename_row: RefCell::new(None),
+                case_sensitive: Cell::new(DEFAULT_CASE_SENSITIVE),
+                match_basename: Cell::new(DEFAULT_MATCH_BASENAME),
+                suggestion_buttons: RefCell::new(Vec::new()),
                 upload_row: RefCell::new(None),
                 download_row: RefCell::new(None),
+                unit_row: RefCell::new(None),
+                upload_kbps: Cell::new(0),
+                download_kbps: Cell::new(0),
+                updating_rows: Cell::new(false),
+                committed_kbps: Cell::new((0, 0)),
                 debounce_source: RefCell::new(None),
+                parallel_row: RefCell::new(None),
+                parallel_transfers: Cell::new(DEFAULT_PARALLEL_TRANSFERS),
+                parallel_source: RefCell::new(None),
+                conflict_template_row: RefCell::new(None),
+                conflict_preview_row: RefCell::new(None),
+                conflict_template: RefCell::new(DEFAULT_CONFLICT_COPY_TEMPLATE.to_string()),
+                connection_row: RefCell::new(None),
+                wifi_ethernet_only: Cell::new(false),
+                free_space_button: RefCell::new(None),
+                free_space_progress: RefCell::new(None),
+                verify_button: RefCell::new(None),
+                verify_progress: RefCell::new(None),
+                progress_watch: RefCell::new(None),
+                service_group: RefCell::new(None),
+                service_row: RefCell::new(None),
+                start_button: RefCell::new(None),
+                stop_button: RefCell::new(None),
+                restart_button: RefCell::new(None),
+                config_view: RefCell::new(None),
             }
         }
     }
@@ -56,7 +158,13 @@ mod imp {
         type ParentType = adw::PreferencesPage;
     }
 
-    impl ObjectImpl for AdvancedPage {}
+    impl ObjectImpl for AdvancedPage {
+        fn dispose(&self) {
+            if let Some(handle) = self.progress_watch.borrow_mut().take() {
+                handle.abort();
+            }
+        }
+    }
     impl WidgetImpl for AdvancedPage {}
     impl PreferencesPageImpl for AdvancedPage {}
 }
@@ -67,6 +175,109 @@ glib::wrapper! {
         @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget;
 }
 
+/// A display unit for the bandwidth spin rows.
+struct BandwidthUnit {
+    label: &'static str,
+    /// Multiply a displayed value by this to get KB/s.
+    kbps_per_unit: f64,
+    step: f64,
+    digits: u32,
+}
+
+/// Units offered by the unit selector — order matches the ComboRow model.
+const BANDWIDTH_UNITS: &[BandwidthUnit] = &[
+    BandwidthUnit {
+        label: "KB/s",
+        kbps_per_unit: 1.0,
+        step: 100.0,
+        digits: 0,
+    },
+    BandwidthUnit {
+        label: "MB/s",
+        kbps_per_unit: 1024.0,
+        step: 0.5,
+        digits: 1,
+    },
+];
+
+/// Maximum number of sample matches shown in the pattern preview.
+const PREVIEW_LIMIT: usize = 20;
+
+/// Delay after the last keystroke before the preview is refreshed.
+const PREVIEW_DEBOUNCE_MS: u64 = 300;
+
+/// Config keys for how exclusion patterns match, and the values assumed
+/// when the config has none: OneDrive itself ignores case, and patterns like
+/// `*.tmp` are meant for files at any depth.
+const CASE_SENSITIVE_KEY: &str = "exclusion_case_sensitive";
+const MATCH_BASENAME_KEY: &str = "exclusion_match_basename";
+const DEFAULT_CASE_SENSITIVE: bool = false;
+const DEFAULT_MATCH_BASENAME: bool = true;
+
+/// Patterns offered under "Suggestions": temporary files, OS metadata,
+/// version control and dependency folders, and Office lock files.
+const SUGGESTED_PATTERNS: &[&str] = &[
+    "*.tmp",
+    ".DS_Store",
+    "Thumbs.db",
+    ".git/",
+    "node_modules/",
+    "~$*",
+];
+
+/// Highest accepted limit (100 MB/s), expressed in KB/s.
+const MAX_LIMIT_KBPS: u32 = 102_400;
+
+/// Config key for the number of files the daemon transfers at once.
+const PARALLEL_TRANSFERS_KEY: &str = "max_parallel_transfers";
+
+/// Range offered for parallel transfers, and the value assumed when the
+/// config has none.
+const MIN_PARALLEL_TRANSFERS: u32 = 1;
+const MAX_PARALLEL_TRANSFERS: u32 = 16;
+const DEFAULT_PARALLEL_TRANSFERS: u32 = 4;
+
+/// Config key for the name given to the local copy kept by "Keep Both", and
+/// the template assumed when the config has none. Tokens: {name} (file name
+/// without extension), {ext} (extension with its dot, or empty), {date}
+/// (YYYY-MM-DD) and {n} (a counter the daemon raises until the name is free).
+const CONFLICT_COPY_KEY: &str = "conflict_copy_template";
+const DEFAULT_CONFLICT_COPY_TEMPLATE: &str = "{name} (conflict {date}){ext}";
+
+/// File renamed in the template preview.
+const CONFLICT_COPY_SAMPLE: &str = "report.docx";
+
+/// `file_name` renamed with `template`, using `date` for {date} and 1 for
+/// {n}.
+fn conflict_copy_name(template: &str, file_name: &str, date: &str) -> String {
+    let (name, ext) = match file_name.rfind('.') {
+        Some(dot) if dot > 0 => file_name.split_at(dot),
+        _ => (file_name, ""),
+    };
+    template
+        .replace("{name}", name)
+        .replace("{ext}", ext)
+        .replace("{date}", date)
+        .replace("{n}", "1")
+}
+
+/// Why `template` can't be used, or `None` if it can.
+fn conflict_copy_template_error(template: &str) -> Option<String> {
+    if template.trim().is_empty() {
+        Some(gettext("Enter a name for the copies"))
+    } else if template.contains('/') {
+        Some(gettext("The name cannot contain a slash"))
+    } else if !template.contains("{date}") && !template.contains("{n}") {
+        Some(gettext("Include {date} or {n} so every copy gets its own name"))
+    } else {
+        None
+    }
+}
+
+/// Non-zero limits below this (in KB/s) ask for confirmation before saving,
+/// since a typo like "1" can stall sync for days.
+const LOW_LIMIT_WARNING_KBPS: u32 = 50;
+
 impl AdvancedPage {
     pub fn new(dbus_client: &DbusClient) -> Self {
         let page: Self = glib::Object::builder()
@@ -80,11 +291,39 @@ impl AdvancedPage {
 
         page.build_ui();
         page.load_exclusion_patterns();
+        page.load_exclusion_options();
         page.load_bandwidth_limits();
+        page.load_connection_types();
+        page.refresh_service_state();
 
         page
     }
 
+    /// Fetch the patterns and their options, limits, network setting and
+    /// service state again.
+    /// The pattern list is emptied first, and bandwidth or parallel
+    /// transfer changes still waiting to be saved are saved before the
+    /// config is re-read.
+    pub fn reload(&self) {
+        let imp = self.imp();
+        if let Some(source_id) = imp.debounce_source.borrow_mut().take() {
+            source_id.remove();
+            self.save_bandwidth_limits();
+        }
+        if let Some(source_id) = imp.parallel_source.borrow_mut().take() {
+            source_id.remove();
+            self.save_parallel_transfers();
+        }
+        imp.patterns_store.borrow_mut().clear();
+        self.rebuild_patterns_list();
+
+        self.load_exclusion_patterns();
+        self.load_exclusion_options();
+        self.load_bandwidth_limits();
+        self.load_connection_types();
+        self.refresh_service_state();
+    }
+
     fn build_ui(&self) {
         let imp = self.imp();
 
@@ -97,6 +336,33 @@ impl AdvancedPage {
             ))
             .build();
 
+        let case_sensitive_row = adw::SwitchRow::builder()
+            .title(&gettext("Case-Sensitive Matching"))
+            .subtitle(&gettext("Treat *.TMP and *.tmp as different patterns"))
+            .active(DEFAULT_CASE_SENSITIVE)
+            .build();
+        imp.case_sensitive_row.replace(Some(case_sensitive_row.clone()));
+        patterns_group.add(&case_sensitive_row);
+
+        let match_basename_row = adw::SwitchRow::builder()
+            .title(&gettext("Match File Names Only"))
+            .subtitle(&gettext(
+                "Compare patterns with each file or folder name instead of its full path",
+            ))
+            .active(DEFAULT_MATCH_BASENAME)
+            .build();
+        imp.match_basename_row.replace(Some(match_basename_row.clone()));
+        patterns_group.add(&match_basename_row);
+
+        let page = self.clone();
+        case_sensitive_row.connect_active_notify(move |row| {
+            page.save_exclusion_option(CASE_SENSITIVE_KEY, row.is_active());
+        });
+        let page = self.clone();
+        match_basename_row.connect_active_notify(move |row| {
+            page.save_exclusion_option(MATCH_BASENAME_KEY, row.is_active());
+        });
+
         let patterns_list = gtk4::ListBox::builder()
             .selection_mode(gtk4::SelectionMode::None)
             .css_classes(["boxed-list"])
@@ -139,6 +405,63 @@ impl AdvancedPage {
             .build();
         patterns_group.add(&add_row);
 
+        // Preview of what the pattern being typed would match.
+        let preview_row = adw::ExpanderRow::builder()
+            .title(&gettext("Matching Items"))
+            .visible(false)
+            .build();
+        imp.preview_row.replace(Some(preview_row.clone()));
+        patterns_group.add(&preview_row);
+
+        // One-click suggestions for users who don't know glob syntax.
+        let suggestions_row = adw::ExpanderRow::builder()
+            .title(&gettext("Suggestions"))
+            .subtitle(&gettext("Common files that rarely need syncing"))
+            .build();
+        let suggestions_box = gtk4::FlowBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .column_spacing(6)
+            .row_spacing(6)
+            .margin_top(8)
+            .margin_bottom(8)
+            .margin_start(8)
+            .margin_end(8)
+            .build();
+        let mut suggestion_buttons = Vec::new();
+        for &pattern in SUGGESTED_PATTERNS {
+            let button = gtk4::Button::builder()
+                .label(pattern)
+                .tooltip_text(&gettext("Exclude {}").replace("{}", pattern))
+                .css_classes(["pill", "monospace"])
+                .build();
+            let page = self.clone();
+            button.connect_clicked(move |_| {
+                page.add_pattern(pattern);
+            });
+            // Keep the chips at their natural width instead of stretching
+            // to fill the FlowBox cell.
+            let child = gtk4::FlowBoxChild::builder()
+                .focusable(false)
+                .halign(gtk4::Align::Start)
+                .child(&button)
+                .build();
+            suggestions_box.append(&child);
+            suggestion_buttons.push(button);
+        }
+        *imp.suggestion_buttons.borrow_mut() = suggestion_buttons;
+        let suggestions_child = gtk4::ListBoxRow::builder()
+            .activatable(false)
+            .selectable(false)
+            .child(&suggestions_box)
+            .build();
+        suggestions_row.add_row(&suggestions_child);
+        patterns_group.add(&suggestions_row);
+
+        let page = self.clone();
+        entry.connect_changed(move |_| {
+            page.schedule_pattern_preview();
+        });
+
         // Connect "Add" button.
         let page = self.clone();
         add_button.connect_clicked(move |_| {
@@ -160,170 +483,320 @@ impl AdvancedPage {
             ))
             .build();
 
-        let upload_row = adw::SpinRow::with_range(0.0, 100_000.0, 100.0);
-        upload_row.set_title(&gettext("Upload Limit (KB/s)"));
+        let unit_model = gtk4::StringList::new(
+            &BANDWIDTH_UNITS
+                .iter()
+                .map(|u| u.label)
+                .collect::<Vec<_>>(),
+        );
+        let unit_row = adw::ComboRow::builder()
+            .title(&gettext("Unit"))
+            .model(&unit_model)
+            .build();
+        imp.unit_row.replace(Some(unit_row.clone()));
+
+        let max_kb = MAX_LIMIT_KBPS as f64;
+        let upload_row = adw::SpinRow::with_range(0.0, max_kb, BANDWIDTH_UNITS[0].step);
+        upload_row.set_title(&gettext("Upload Limit"));
         upload_row.set_subtitle(&gettext("0 = unlimited"));
         upload_row.set_value(0.0);
-        upload_row.set_snap_to_ticks(true);
         imp.upload_row.replace(Some(upload_row.clone()));
 
-        let download_row = adw::SpinRow::with_range(0.0, 100_000.0, 100.0);
-        download_row.set_title(&gettext("Download Limit (KB/s)"));
+        let download_row = adw::SpinRow::with_range(0.0, max_kb, BANDWIDTH_UNITS[0].step);
+        download_row.set_title(&gettext("Download Limit"));
         download_row.set_subtitle(&gettext("0 = unlimited"));
         download_row.set_value(0.0);
-        download_row.set_snap_to_ticks(true);
         imp.download_row.replace(Some(download_row.clone()));
 
+        bandwidth_group.add(&unit_row);
         bandwidth_group.add(&upload_row);
         bandwidth_group.add(&download_row);
 
         // Add groups to page.
         self.add(&patterns_group);
         self.add(&bandwidth_group);
+        self.add(&self.build_transfers_group());
+        self.add(&self.build_conflicts_group());
+        self.add(&self.build_network_group());
+        self.add(&self.build_storage_group());
+        self.add(&self.build_startup_group());
+        self.add(&self.build_notifications_group());
+        self.add(&self.build_appearance_group());
+        self.add(&self.build_service_group());
+
+        if AppSettings::new().developer_mode() {
+            self.add(&self.build_developer_group());
+        }
 
         // Debounced save for bandwidth changes.
         let page = self.clone();
         upload_row.connect_value_notify(move |_| {
-            page.schedule_bandwidth_save();
+            page.on_bandwidth_value_changed();
         });
 
         let page = self.clone();
         download_row.connect_value_notify(move |_| {
-            page.schedule_bandwidth_save();
+            page.on_bandwidth_value_changed();
+        });
+
+        // Switching units only rescales the display.
+        let page = self.clone();
+        unit_row.connect_selected_notify(move |_| {
+            page.refresh_bandwidth_rows();
         });
     }
 
-    // -- Exclusion Patterns --------------------------------------------------
+    // -- Parallel transfers --------------------------------------------------
 
-    /// Load current exclusion patterns from the daemon.
-    fn load_exclusion_patterns(&self) {
-        let client = match self.imp().dbus_client.borrow().clone() {
+    /// Build the Transfers group with the parallel transfers spin row (1 to
+    /// 16), a performance knob traded against bandwidth and CPU.
+    fn build_transfers_group(&self) -> adw::PreferencesGroup {
+        let transfers_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Transfers"))
+            .build();
+
+        let parallel_row = adw::SpinRow::with_range(
+            MIN_PARALLEL_TRANSFERS as f64,
+            MAX_PARALLEL_TRANSFERS as f64,
+            1.0,
+        );
+        parallel_row.set_title(&gettext("Parallel Transfers"));
+        parallel_row.set_subtitle(&gettext(
+            "Files uploaded or downloaded at the same time. Higher values can finish sooner but use more bandwidth and CPU.",
+        ));
+        parallel_row.set_value(DEFAULT_PARALLEL_TRANSFERS as f64);
+        self.imp().parallel_row.replace(Some(parallel_row.clone()));
+        transfers_group.add(&parallel_row);
+
+        let page = self.clone();
+        parallel_row.connect_value_notify(move |_| {
+            page.schedule_parallel_save();
+        });
+
+        transfers_group
+    }
+
+    /// Set the spin row from `max_parallel_transfers` in the daemon config.
+    fn apply_parallel_transfers(&self, yaml: &str) {
+        let imp = self.imp();
+        let value = config::top_level_value(yaml, PARALLEL_TRANSFERS_KEY)
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_PARALLEL_TRANSFERS)
+            .clamp(MIN_PARALLEL_TRANSFERS, MAX_PARALLEL_TRANSFERS);
+        imp.parallel_transfers.set(value);
+        if let Some(ref row) = *imp.parallel_row.borrow() {
+            row.set_value(value as f64);
+        }
+    }
+
+    /// Schedule a debounced save (500ms) so stepping through values sends
+    /// only the last one.
+    fn schedule_parallel_save(&self) {
+        let imp = self.imp();
+
+        if let Some(source_id) = imp.parallel_source.borrow_mut().take() {
+            source_id.remove();
+        }
+
+        let page = self.clone();
+        let source_id = glib::timeout_add_local_once(
+            std::time::Duration::from_millis(500),
+            move || {
+                page.imp().parallel_source.replace(None);
+                page.save_parallel_transfers();
+            },
+        );
+
+        imp.parallel_source.replace(Some(source_id));
+    }
+
+    /// Write the spin row's value to the daemon config. Reverts the row on
+    /// failure.
+    fn save_parallel_transfers(&self) {
+        let imp = self.imp();
+        let value = match imp.parallel_row.borrow().as_ref() {
+            Some(row) => row.value() as u32,
+            None => return,
+        };
+        // Also true when the row is set from the config or reverted.
+        if value == imp.parallel_transfers.get() {
+            return;
+        }
+        let client = match imp.dbus_client.borrow().clone() {
             Some(c) => c,
             None => return,
         };
 
         let page = self.clone();
         glib::MainContext::default().spawn_local(async move {
-            match client.get_exclusion_patterns().await {
-                Ok(patterns) => {
-                    *page.imp().patterns_store.borrow_mut() = patterns;
-                    page.rebuild_patterns_list();
-                }
+            let yaml = format!("{PARALLEL_TRANSFERS_KEY}: {value}\n");
+            match client.set_config(&yaml).await {
+                Ok(()) => page.imp().parallel_transfers.set(value),
                 Err(e) => {
-                    eprintln!("Could not load exclusion patterns: {}", e);
+                    eprintln!("Could not save parallel transfers: {}", e);
+                    page.show_toast(&gettext("Could not change the number of parallel transfers"));
+                    let imp = page.imp();
+                    if let Some(ref row) = *imp.parallel_row.borrow() {
+                        row.set_value(imp.parallel_transfers.get() as f64);
+                    }
                 }
             }
         });
     }
 
-    /// Rebuild the ListBox rows from the current patterns_store.
-    fn rebuild_patterns_list(&self) {
-        let imp = self.imp();
-
-        let list_box = match imp.patterns_list.borrow().clone() {
-            Some(lb) => lb,
-            None => return,
-        };
-
-        // Remove all existing rows.
-        while let Some(child) = list_box.first_child() {
-            list_box.remove(&child);
-        }
+    // -- Conflicts -----------------------------------------------------------
 
-        let patterns = imp.patterns_store.borrow().clone();
-        for (index, pattern) in patterns.iter().enumerate() {
-            let row = self.create_pattern_row(pattern, index);
-            list_box.append(&row);
-        }
-    }
+    /// Build the Conflicts group with the copy name template and its
+    /// preview.
+    fn build_conflicts_group(&self) -> adw::PreferencesGroup {
+        let imp = self.imp();
 
-    /// Create a single row for an exclusion pattern with a delete button.
-    fn create_pattern_row(&self, pattern: &str, _index: usize) -> adw::ActionRow {
-        let row = adw::ActionRow::builder()
-            .title(pattern)
+        let conflicts_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Conflicts"))
+            .description(&gettext(
+                "Name given to your local version when a conflict is resolved with Keep Both. Use {name}, {ext}, {date} and {n}.",
+            ))
             .build();
 
-        let delete_button = gtk4::Button::builder()
-            .icon_name("edit-delete-symbolic")
-            .tooltip_text(&gettext("Remove pattern"))
-            .valign(gtk4::Align::Center)
-            .css_classes(["flat", "circular"])
+        let template_row = adw::EntryRow::builder()
+            .title(&gettext("Copy Name"))
+            .text(DEFAULT_CONFLICT_COPY_TEMPLATE)
+            .show_apply_button(true)
             .build();
+        imp.conflict_template_row.replace(Some(template_row.clone()));
+        conflicts_group.add(&template_row);
 
-        row.add_suffix(&delete_button);
+        let preview_row = adw::ActionRow::builder()
+            .title(&gettext("Preview"))
+            .subtitle_selectable(true)
+            .build();
+        imp.conflict_preview_row.replace(Some(preview_row.clone()));
+        conflicts_group.add(&preview_row);
 
         let page = self.clone();
-        let pattern_owned = pattern.to_string();
-        delete_button.connect_clicked(move |_| {
-            page.on_remove_pattern(&pattern_owned);
+        template_row.connect_changed(move |_| {
+            page.update_conflict_copy_preview();
+        });
+        let page = self.clone();
+        template_row.connect_apply(move |_| {
+            page.save_conflict_copy_template();
         });
 
-        row
+        self.update_conflict_copy_preview();
+        conflicts_group
     }
 
-    /// Add a new pattern from the entry field.
-    fn on_add_pattern(&self) {
+    /// Set the entry from `conflict_copy_template` in the daemon config.
+    fn apply_conflict_copy_template(&self, yaml: &str) {
         let imp = self.imp();
-
-        let pattern = match imp.pattern_entry.borrow().as_ref() {
-            Some(entry) => {
-                let text = entry.text().trim().to_string();
-                entry.set_text("");
-                text
-            }
-            None => return,
-        };
-
-        if pattern.is_empty() {
-            return;
-        }
-
-        // Avoid duplicates.
-        {
-            let store = imp.patterns_store.borrow();
-            if store.contains(&pattern) {
-                return;
-            }
+        let template = config::top_level_value(yaml, CONFLICT_COPY_KEY)
+            .unwrap_or_else(|| DEFAULT_CONFLICT_COPY_TEMPLATE.to_string());
+        imp.conflict_template.replace(template.clone());
+        if let Some(ref row) = *imp.conflict_template_row.borrow() {
+            row.set_text(&template);
         }
-
-        imp.patterns_store.borrow_mut().push(pattern);
-        self.rebuild_patterns_list();
-        self.save_exclusion_patterns();
     }
 
-    /// Remove a pattern by value.
-    fn on_remove_pattern(&self, pattern: &str) {
+    /// Show how the sample file would be renamed, or why the template is
+    /// not usable.
+    fn update_conflict_copy_preview(&self) {
         let imp = self.imp();
-
-        imp.patterns_store
-            .borrow_mut()
-            .retain(|p| p != pattern);
-
-        self.rebuild_patterns_list();
-        self.save_exclusion_patterns();
+        let Some(template_row) = imp.conflict_template_row.borrow().clone() else {
+            return;
+        };
+        let template = template_row.text();
+        let error = conflict_copy_template_error(&template);
+        util::set_css_class(&template_row, "error", error.is_some());
+
+        let subtitle = error.unwrap_or_else(|| {
+            let date = glib::DateTime::now_local()
+                .ok()
+                .and_then(|now| now.format("%Y-%m-%d").ok())
+                .map(|s| s.to_string())
+                .unwrap_or_default();
+            // Translators: {sample} is a file name, {copy} its renamed copy.
+            gettext("{sample} becomes {copy}")
+                .replace("{sample}", CONFLICT_COPY_SAMPLE)
+                .replace(
+                    "{copy}",
+                    &conflict_copy_name(&template, CONFLICT_COPY_SAMPLE, &date),
+                )
+        });
+        if let Some(ref row) = *imp.conflict_preview_row.borrow() {
+            row.set_subtitle(&subtitle);
+        }
     }
 
-    /// Send the current patterns to the daemon.
-    fn save_exclusion_patterns(&self) {
+    /// Write the template to the daemon config if it is valid: it needs a
+    /// {date} or {n} token, or two copies would collide. Reverts the entry
+    /// on failure.
+    fn save_conflict_copy_template(&self) {
         let imp = self.imp();
-        let patterns = imp.patterns_store.borrow().clone();
-
+        let template = match imp.conflict_template_row.borrow().as_ref() {
+            Some(row) => row.text().to_string(),
+            None => return,
+        };
+        if let Some(error) = conflict_copy_template_error(&template) {
+            self.show_toast(&error);
+            return;
+        }
+        if template == *imp.conflict_template.borrow() {
+            return;
+        }
         let client = match imp.dbus_client.borrow().clone() {
             Some(c) => c,
             None => return,
         };
 
+        let page = self.clone();
         glib::MainContext::default().spawn_local(async move {
-            if let Err(e) = client.set_exclusion_patterns(&patterns).await {
-                eprintln!("Could not save exclusion patterns: {}", e);
+            let yaml = config::string_setting(CONFLICT_COPY_KEY, &template);
+            match client.set_config(&yaml).await {
+                Ok(()) => {
+                    page.imp().conflict_template.replace(template);
+                }
+                Err(e) => {
+                    eprintln!("Could not save the conflict copy template: {}", e);
+                    page.show_toast(&gettext("Could not change the name for conflict copies"));
+                    let imp = page.imp();
+                    if let Some(ref row) = *imp.conflict_template_row.borrow() {
+                        row.set_text(&imp.conflict_template.borrow());
+                    }
+                }
             }
         });
     }
 
-    // -- Bandwidth Limits ----------------------------------------------------
+    // -- Network -------------------------------------------------------------
 
-    /// Load bandwidth limits from daemon config.
-    fn load_bandwidth_limits(&self) {
+    /// Build the Network group with the connection-type switch. The window
+    /// pauses sync on a connection it rules out and explains why.
+    fn build_network_group(&self) -> adw::PreferencesGroup {
+        let network_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Network"))
+            .build();
+
+        let connection_row = adw::SwitchRow::builder()
+            .title(&gettext("Only Sync on Wi-Fi or Ethernet"))
+            .subtitle(&gettext(
+                "Pause syncing on mobile broadband and tethered phones",
+            ))
+            .build();
+        self.imp()
+            .connection_row
+            .replace(Some(connection_row.clone()));
+        network_group.add(&connection_row);
+
+        let page = self.clone();
+        connection_row.connect_active_notify(move |row| {
+            page.save_connection_types(row.is_active());
+        });
+
+        network_group
+    }
+
+    /// Set the switch from the daemon config.
+    fn load_connection_types(&self) {
         let client = match self.imp().dbus_client.borrow().clone() {
             Some(c) => c,
             None => return,
@@ -333,82 +806,1239 @@ impl AdvancedPage {
         glib::MainContext::default().spawn_local(async move {
             match client.get_config().await {
                 Ok(yaml) => {
-                    page.apply_bandwidth_config(&yaml);
+                    let imp = page.imp();
+                    let enabled = network::wifi_ethernet_only(&yaml);
+                    imp.wifi_ethernet_only.set(enabled);
+                    if let Some(ref row) = *imp.connection_row.borrow() {
+                        row.set_active(enabled);
+                    }
                 }
                 Err(e) => {
-                    eprintln!("Could not load bandwidth config: {}", e);
+                    eprintln!("Could not load connection types: {}", e);
                 }
             }
         });
     }
 
-    /// Parse bandwidth settings from YAML and apply to spin rows.
-    fn apply_bandwidth_config(&self, yaml: &str) {
+    /// Write the allowed connection types and let the window pause or
+    /// resume for the current connection. Reverts the switch on failure.
+    fn save_connection_types(&self, enabled: bool) {
         let imp = self.imp();
+        // Also true when the switch is set from the config or reverted.
+        if enabled == imp.wifi_ethernet_only.get() {
+            return;
+        }
+        let client = match imp.dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
 
-        for line in yaml.lines() {
-            let line = line.trim();
-            if let Some((key, value)) = line.split_once(':') {
-                let key = key.trim();
-                let value = value.trim().trim_matches('"');
-
-                match key {
-                    "upload_limit_kbps" | "upload_limit" => {
-                        if let Ok(val) = value.parse::<f64>() {
-                            if let Some(ref row) = *imp.upload_row.borrow() {
-                                row.set_value(val.clamp(0.0, 100_000.0));
-                            }
-                        }
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let yaml = network::connection_types_setting(enabled);
+            match client.set_config(&yaml).await {
+                Ok(()) => {
+                    page.imp().wifi_ethernet_only.set(enabled);
+                    if let Some(window) = gtk4::gio::Application::default()
+                        .and_then(|app| app.downcast::<gtk4::Application>().ok())
+                        .and_then(|app| app.active_window())
+                        .and_then(|win| win.downcast::<crate::window::LnxdriveWindow>().ok())
+                    {
+                        window.set_wifi_ethernet_only(&client, enabled);
                     }
-                    "download_limit_kbps" | "download_limit" => {
-                        if let Ok(val) = value.parse::<f64>() {
-                            if let Some(ref row) = *imp.download_row.borrow() {
-                                row.set_value(val.clamp(0.0, 100_000.0));
-                            }
-                        }
+                }
+                Err(e) => {
+                    eprintln!("Could not save connection types: {}", e);
+                    page.show_toast(&gettext("Could not change the connection setting"));
+                    if let Some(ref row) = *page.imp().connection_row.borrow() {
+                        row.set_active(!enabled);
                     }
-                    _ => {}
                 }
             }
-        }
+        });
     }
 
-    /// Schedule a debounced bandwidth save (500ms).
-    fn schedule_bandwidth_save(&self) {
-        let imp = self.imp();
+    // -- Storage -------------------------------------------------------------
 
-        if let Some(source_id) = imp.debounce_source.borrow_mut().take() {
-            source_id.remove();
-        }
+    /// Build the Storage group with the global "Free Up Space" and "Verify
+    /// Files" actions, which share a progress bar fed by SyncProgress.
+    fn build_storage_group(&self) -> adw::PreferencesGroup {
+        let imp = self.imp();
 
-        let page = self.clone();
-        let source_id = glib::timeout_add_local_once(
-            std::time::Duration::from_millis(500),
-            move || {
-                page.save_bandwidth_limits();
-            },
-        );
+        let storage_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Storage"))
+            .build();
 
-        imp.debounce_source.replace(Some(source_id));
-    }
+        let free_space_row = adw::ActionRow::builder()
+            .title(&gettext("Free Up Space"))
+            .subtitle(&gettext(
+                "Make all downloaded files online-only. They stay in your OneDrive and are downloaded again when opened.",
+            ))
+            .build();
+        let free_space_button = gtk4::Button::builder()
+            .label(&gettext("Free Up Space..."))
+            .valign(gtk4::Align::Center)
+            .build();
+        free_space_row.add_suffix(&free_space_button);
+        storage_group.add(&free_space_row);
 
-    /// Send bandwidth limits to the daemon.
-    fn save_bandwidth_limits(&self) {
-        let imp = self.imp();
+        let free_space_progress = gtk4::ProgressBar::builder()
+            .show_text(true)
+            .margin_top(6)
+            .visible(false)
+            .build();
+        storage_group.add(&free_space_progress);
 
-        let upload = imp
-            .upload_row
+        let verify_row = adw::ActionRow::builder()
+            .title(&gettext("Verify Files"))
+            .subtitle(&gettext(
+                "Compare every file on this computer with OneDrive and repair any differences. Use this if sync seems stuck.",
+            ))
+            .build();
+        let verify_button = gtk4::Button::builder()
+            .label(&gettext("Verify..."))
+            .valign(gtk4::Align::Center)
+            .build();
+        verify_row.add_suffix(&verify_button);
+        storage_group.add(&verify_row);
+
+        let verify_progress = gtk4::ProgressBar::builder()
+            .show_text(true)
+            .margin_top(6)
+            .visible(false)
+            .build();
+        storage_group.add(&verify_progress);
+
+        let page = self.clone();
+        free_space_button.connect_clicked(move |_| {
+            page.confirm_free_up_space();
+        });
+
+        let page = self.clone();
+        verify_button.connect_clicked(move |_| {
+            page.confirm_verify_integrity();
+        });
+
+        imp.free_space_button.replace(Some(free_space_button));
+        imp.free_space_progress.replace(Some(free_space_progress));
+        imp.verify_button.replace(Some(verify_button));
+        imp.verify_progress.replace(Some(verify_progress));
+
+        storage_group
+    }
+
+    /// Show SyncProgress in `bar` until the watch is aborted.
+    fn watch_progress(&self, bar: &gtk4::ProgressBar) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        if let Some(previous) = self.imp().progress_watch.replace(Some(abort_handle)) {
+            previous.abort();
+        }
+
+        let bar = bar.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let _ = Abortable::new(async move {
+                let mut progress = match client.receive_sync_progress().await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("Could not subscribe to SyncProgress: {e}");
+                        return;
+                    }
+                };
+                while let Some((file, current, total)) = progress.next().await {
+                    let Some(bar) = bar.upgrade() else { break };
+                    if total > 0 {
+                        bar.set_fraction(f64::from(current) / f64::from(total));
+                    }
+                    bar.set_text(Some(&file));
+                }
+            }, abort_registration).await;
+        });
+    }
+
+    /// Enable or disable both "Free Up Space" and "Verify Files", so one
+    /// can't start while the other is running.
+    fn set_storage_actions_sensitive(&self, sensitive: bool) {
+        let imp = self.imp();
+        for button in [&imp.free_space_button, &imp.verify_button] {
+            if let Some(ref button) = *button.borrow() {
+                button.set_sensitive(sensitive);
+            }
+        }
+    }
+
+    fn stop_progress_watch(&self) {
+        if let Some(handle) = self.imp().progress_watch.borrow_mut().take() {
+            handle.abort();
+        }
+    }
+
+    /// Bytes currently downloaded under all sync roots, or `None` if the
+    /// daemon can't say.
+    async fn downloaded_bytes(client: &DbusClient) -> Option<u64> {
+        let mut paths: Vec<String> = client
+            .list_sync_roots()
+            .await
+            .ok()?
+            .into_iter()
+            .map(|root| root.local_path)
+            .filter(|path| !path.is_empty())
+            .collect();
+        // Single-root configurations keep the folder in the config instead.
+        if paths.is_empty() {
+            let yaml = client.get_config().await.ok()?;
+            paths.push(config::top_level_value(&yaml, "sync_root")?);
+        }
+
+        match client.get_folder_sizes(&paths).await {
+            Ok(sizes) => Some(sizes.values().sum()),
+            Err(e) => {
+                eprintln!("Could not get downloaded size: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Ask before freeing up space, stating how much disk it reclaims.
+    fn confirm_free_up_space(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let heading = match Self::downloaded_bytes(&client).await {
+                Some(0) => {
+                    page.show_toast(&gettext("There are no downloaded files to free up"));
+                    return;
+                }
+                Some(bytes) => gettext("Free Up {}?").replace("{}", &util::format_size(bytes)),
+                None => gettext("Free Up Space?"),
+            };
+
+            let confirm = util::confirmation_dialog(
+                &heading,
+                &gettext(
+                    "All downloaded files will be removed from this computer and stay available online. Files are downloaded again when opened, which needs a connection.",
+                ),
+            );
+            confirm.add_response("free", &gettext("Free Up Space"));
+            confirm.set_response_appearance("free", adw::ResponseAppearance::Destructive);
+
+            let page_confirm = page.clone();
+            confirm.connect_response(None, move |_dialog, response| {
+                if response == "free" {
+                    page_confirm.free_up_space();
+                }
+            });
+
+            adw::prelude::AdwDialogExt::present(&confirm, Some(page.upcast_ref::<gtk4::Widget>()));
+        });
+    }
+
+    /// Run FreeUpAllSpace, showing SyncProgress in the progress bar until
+    /// the call returns.
+    fn free_up_space(&self) {
+        let imp = self.imp();
+        let client = match imp.dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        self.set_storage_actions_sensitive(false);
+        if let Some(ref bar) = *imp.free_space_progress.borrow() {
+            bar.set_fraction(0.0);
+            bar.set_text(Some(&gettext("Freeing up space...")));
+            bar.set_visible(true);
+            self.watch_progress(bar);
+        }
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let result = client.free_up_all_space().await;
+
+            page.stop_progress_watch();
+            let imp = page.imp();
+            if let Some(ref bar) = *imp.free_space_progress.borrow() {
+                bar.set_visible(false);
+            }
+            page.set_storage_actions_sensitive(true);
+
+            match result {
+                Ok(freed) => {
+                    page.show_toast(
+                        &gettext("Freed up {}").replace("{}", &util::format_size(freed)),
+                    );
+                }
+                Err(e) => {
+                    eprintln!("Could not free up space: {}", e);
+                    page.show_toast(&gettext("Could not free up space"));
+                }
+            }
+        });
+    }
+
+    /// Ask before verifying, since a full re-scan can take a long time.
+    fn confirm_verify_integrity(&self) {
+        let confirm = util::confirmation_dialog(
+            &gettext("Verify All Files?"),
+            &gettext(
+                "LNXDrive will re-scan every file on this computer and in your OneDrive and repair any differences. With many files this can take a long time.",
+            ),
+        );
+        confirm.add_response("verify", &gettext("Verify"));
+        confirm.set_response_appearance("verify", adw::ResponseAppearance::Suggested);
+
+        let page = self.clone();
+        confirm.connect_response(None, move |_dialog, response| {
+            if response == "verify" {
+                page.verify_integrity();
+            }
+        });
+
+        adw::prelude::AdwDialogExt::present(&confirm, Some(self.upcast_ref::<gtk4::Widget>()));
+    }
+
+    /// Run VerifyIntegrity, showing SyncProgress in the progress bar until
+    /// the call returns.
+    fn verify_integrity(&self) {
+        let imp = self.imp();
+        let client = match imp.dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        self.set_storage_actions_sensitive(false);
+        if let Some(ref bar) = *imp.verify_progress.borrow() {
+            bar.set_fraction(0.0);
+            bar.set_text(Some(&gettext("Verifying files...")));
+            bar.set_visible(true);
+            self.watch_progress(bar);
+        }
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let result = client.verify_integrity().await;
+
+            page.stop_progress_watch();
+            let imp = page.imp();
+            if let Some(ref bar) = *imp.verify_progress.borrow() {
+                bar.set_visible(false);
+            }
+            page.set_storage_actions_sensitive(true);
+
+            match result {
+                Ok(0) => page.show_toast(&gettext("Verification complete, everything is in sync")),
+                Ok(repaired) => page.show_toast(
+                    &ngettext(
+                        "Verification complete, {} item repaired",
+                        "Verification complete, {} items repaired",
+                        repaired,
+                    )
+                    .replace("{}", &repaired.to_string()),
+                ),
+                // The daemon keeps going after we stop waiting.
+                Err(e) if e.kind() == Some(DaemonErrorKind::TimedOut) => {
+                    page.show_toast(&gettext("Verification is still running in the background"));
+                }
+                Err(e) => {
+                    eprintln!("Could not verify files: {}", e);
+                    page.show_toast(&gettext("Could not verify files"));
+                }
+            }
+        });
+    }
+
+    // -- Startup -------------------------------------------------------------
+
+    /// Build the Startup group with the login autostart switch, which
+    /// enables the daemon's systemd user unit, or its XDG autostart entry
+    /// where there is no unit.
+    fn build_startup_group(&self) -> adw::PreferencesGroup {
+        let startup_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Startup"))
+            .build();
+
+        let autostart_row = adw::SwitchRow::builder()
+            .title(&gettext("Enable Sync at Login"))
+            .subtitle(&gettext("Start LNXDrive automatically when you log in"))
+            .build();
+        startup_group.add(&autostart_row);
+
+        let row = autostart_row.clone();
+        glib::MainContext::default().spawn_local(async move {
+            row.set_active(autostart::is_enabled().await);
+        });
+
+        let page = self.clone();
+        autostart_row.connect_active_notify(move |row| {
+            let page = page.clone();
+            let row = row.clone();
+            glib::MainContext::default().spawn_local(async move {
+                let enabled = row.is_active();
+                // Also true when the state is first loaded above, and when we
+                // revert the switch after a failure below.
+                if enabled == autostart::is_enabled().await {
+                    return;
+                }
+                if let Err(e) = autostart::set_enabled(enabled).await {
+                    eprintln!("Could not update autostart: {}", e);
+                    page.show_toast(&gettext("Could not change the login setting"));
+                    row.set_active(!enabled);
+                }
+            });
+        });
+
+        startup_group
+    }
+
+    // -- Notifications -------------------------------------------------------
+
+    /// Build the Notifications group: which sync events become desktop
+    /// notifications, and whether the app keeps running to show them once
+    /// its window is closed. Stored in GSettings; the app picks changes up
+    /// live.
+    fn build_notifications_group(&self) -> adw::PreferencesGroup {
+        const LEVELS: [NotificationLevel; 3] = [
+            NotificationLevel::None,
+            NotificationLevel::Important,
+            NotificationLevel::All,
+        ];
+
+        let notifications_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Notifications"))
+            .build();
+
+        let model = gtk4::StringList::new(&[
+            &gettext("Off"),
+            &gettext("Conflicts and Errors"),
+            &gettext("All Sync Activity"),
+        ]);
+        let settings = AppSettings::new();
+        let current = settings.notification_level();
+        let level_row = adw::ComboRow::builder()
+            .title(&gettext("Show Notifications"))
+            .model(&model)
+            .selected(LEVELS.iter().position(|l| *l == current).unwrap_or(1) as u32)
+            .build();
+        notifications_group.add(&level_row);
+
+        let background_row = adw::SwitchRow::builder()
+            .title(&gettext("Run in Background"))
+            .subtitle(&gettext(
+                "Keep LNXDrive running after its window is closed, to notify you about sync events",
+            ))
+            .build();
+        settings.bind(keys::RUN_IN_BACKGROUND, &background_row, "active");
+        notifications_group.add(&background_row);
+
+        let page = self.clone();
+        level_row.connect_selected_notify(move |row| {
+            let Some(&level) = LEVELS.get(row.selected() as usize) else {
+                return;
+            };
+            if let Err(e) = settings.set_notification_level(level) {
+                eprintln!("Could not save the notification setting: {e}");
+                page.show_toast(&gettext("Could not change the notification setting"));
+            }
+        });
+
+        notifications_group
+    }
+
+    // -- Appearance ----------------------------------------------------------
+
+    /// Build the Appearance group with the list density switch. Stored in
+    /// GSettings; the folder tree and conflict list follow it live.
+    fn build_appearance_group(&self) -> adw::PreferencesGroup {
+        let appearance_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Appearance"))
+            .build();
+
+        let compact_row = adw::SwitchRow::builder()
+            .title(&gettext("Compact Lists"))
+            .subtitle(&gettext("Show more folders and conflicts at once"))
+            .build();
+        AppSettings::new().bind(keys::COMPACT_LISTS, &compact_row, "active");
+        appearance_group.add(&compact_row);
+
+        appearance_group
+    }
+
+    // -- Service -------------------------------------------------------------
+
+    /// Build the Service group: the unit's state with Start/Stop/Restart.
+    /// It stays hidden where the unit is not installed.
+    fn build_service_group(&self) -> adw::PreferencesGroup {
+        let imp = self.imp();
+
+        let service_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Service"))
+            .description(&gettext(
+                "The LNXDrive daemon runs as a systemd user service.",
+            ))
+            .visible(false)
+            .build();
+
+        let service_row = adw::ActionRow::builder()
+            .title(&gettext("LNXDrive Daemon"))
+            .subtitle(&gettext("Loading..."))
+            .build();
+
+        let button_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .valign(gtk4::Align::Center)
+            .build();
+        let action_button = |label: &str| {
+            let button = gtk4::Button::builder().label(label).build();
+            button_box.append(&button);
+            button
+        };
+        let start_button = action_button(&gettext("Start"));
+        let stop_button = action_button(&gettext("Stop"));
+        let restart_button = action_button(&gettext("Restart"));
+        service_row.add_suffix(&button_box);
+        service_group.add(&service_row);
+
+        for (button, action) in [
+            (&start_button, "start"),
+            (&stop_button, "stop"),
+            (&restart_button, "restart"),
+        ] {
+            let page = self.clone();
+            button.connect_clicked(move |_| {
+                page.run_service_action(action);
+            });
+        }
+
+        imp.service_group.replace(Some(service_group.clone()));
+        imp.service_row.replace(Some(service_row));
+        imp.start_button.replace(Some(start_button));
+        imp.stop_button.replace(Some(stop_button));
+        imp.restart_button.replace(Some(restart_button));
+
+        service_group
+    }
+
+    /// Query the unit and update the group; hides it without systemd or
+    /// without the unit.
+    fn refresh_service_state(&self) {
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let state = service::active_state().await;
+            page.show_service_state(state);
+        });
+    }
+
+    fn show_service_state(&self, state: Option<ActiveState>) {
+        let imp = self.imp();
+        if let Some(ref group) = *imp.service_group.borrow() {
+            group.set_visible(state.is_some());
+        }
+        let Some(state) = state else { return };
+
+        if let Some(ref row) = *imp.service_row.borrow() {
+            row.set_subtitle(&state.label());
+        }
+        let running = state.is_running();
+        if let Some(ref button) = *imp.start_button.borrow() {
+            button.set_sensitive(!running);
+        }
+        if let Some(ref button) = *imp.stop_button.borrow() {
+            button.set_sensitive(running);
+        }
+        if let Some(ref button) = *imp.restart_button.borrow() {
+            button.set_sensitive(running);
+        }
+    }
+
+    /// Run "start", "stop" or "restart", then re-read the unit state.
+    fn run_service_action(&self, action: &'static str) {
+        let imp = self.imp();
+        for button in [&imp.start_button, &imp.stop_button, &imp.restart_button] {
+            if let Some(ref button) = *button.borrow() {
+                button.set_sensitive(false);
+            }
+        }
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let result = match action {
+                "start" => service::start().await,
+                "stop" => service::stop().await,
+                _ => service::restart().await,
+            };
+            if let Err(e) = result {
+                eprintln!("Could not {} the daemon service: {}", action, e);
+                page.show_toast(&gettext("Could not change the daemon service"));
+            }
+            page.refresh_service_state();
+        });
+    }
+
+    // -- Developer -----------------------------------------------------------
+
+    /// Build the Developer group, shown with the `developer-mode` setting:
+    /// an expander with the daemon's config YAML (secrets redacted) in a
+    /// read-only view, a "Copy Config" button and the raw config editor.
+    fn build_developer_group(&self) -> adw::PreferencesGroup {
+        let imp = self.imp();
+
+        let developer_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Developer"))
+            .build();
+
+        let config_row = adw::ExpanderRow::builder()
+            .title(&gettext("Daemon Configuration"))
+            .subtitle(&gettext("Secrets are redacted"))
+            .build();
+
+        let copy_button = gtk4::Button::builder()
+            .label(&gettext("Copy Config"))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        config_row.add_suffix(&copy_button);
+
+        let config_view = gtk4::TextView::builder()
+            .editable(false)
+            .cursor_visible(false)
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .top_margin(8)
+            .bottom_margin(8)
+            .left_margin(8)
+            .right_margin(8)
+            .build();
+        config_view.update_property(&[gtk4::accessible::Property::Label(&gettext(
+            "Daemon configuration",
+        ))]);
+        imp.config_view.replace(Some(config_view.clone()));
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .min_content_height(240)
+            .child(&config_view)
+            .build();
+
+        let view_row = gtk4::ListBoxRow::builder()
+            .activatable(false)
+            .selectable(false)
+            .child(&scrolled)
+            .build();
+        config_row.add_row(&view_row);
+        developer_group.add(&config_row);
+
+        let edit_button = gtk4::Button::builder()
+            .label(&gettext("Edit..."))
+            .valign(gtk4::Align::Center)
+            .build();
+        let edit_row = adw::ActionRow::builder()
+            .title(&gettext("Edit Configuration"))
+            .subtitle(&gettext("Change daemon settings directly as YAML"))
+            .build();
+        edit_row.add_suffix(&edit_button);
+        edit_row.set_activatable_widget(Some(&edit_button));
+        developer_group.add(&edit_row);
+
+        // Fetch lazily: only when the expander is first opened.
+        let page = self.clone();
+        config_row.connect_expanded_notify(move |row| {
+            if row.is_expanded() {
+                page.load_config_view(false);
+            }
+        });
+
+        let page = self.clone();
+        copy_button.connect_clicked(move |_| {
+            page.load_config_view(true);
+        });
+
+        let page = self.clone();
+        edit_button.connect_clicked(move |_| {
+            page.open_config_editor();
+        });
+
+        developer_group
+    }
+
+    /// Fetch the config from the daemon, show it redacted and optionally
+    /// copy it to the clipboard.
+    fn load_config_view(&self, copy: bool) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.get_config().await {
+                Ok(yaml) => {
+                    let redacted = redact_config(&yaml);
+                    if let Some(ref view) = *page.imp().config_view.borrow() {
+                        view.buffer().set_text(&redacted);
+                    }
+                    if copy {
+                        page.clipboard().set_text(&redacted);
+                        page.show_toast(&gettext("Configuration copied to clipboard"));
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Could not load config: {}", e);
+                    if copy {
+                        page.show_toast(&gettext("Could not load the configuration"));
+                    }
+                }
+            }
+        });
+    }
+
+    /// Open the raw config editor; refresh the read-only view when it
+    /// closes, since the config may have changed.
+    fn open_config_editor(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let editor = ConfigEditorDialog::new(&client);
+        let page = self.clone();
+        editor.connect_closed(move |_| {
+            page.load_config_view(false);
+        });
+        editor.present(Some(self.upcast_ref::<gtk4::Widget>()));
+    }
+
+    /// Show a toast on the enclosing PreferencesDialog.
+    fn show_toast(&self, message: &str) {
+        if let Some(dialog) = self
+            .ancestor(adw::PreferencesDialog::static_type())
+            .and_then(|w| w.downcast::<adw::PreferencesDialog>().ok())
+        {
+            dialog.add_toast(adw::Toast::new(message));
+        }
+    }
+
+    // -- Exclusion Patterns --------------------------------------------------
+
+    /// Load current exclusion patterns from the daemon.
+    fn load_exclusion_patterns(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.get_exclusion_patterns().await {
+                Ok(patterns) => {
+                    *page.imp().patterns_store.borrow_mut() = patterns;
+                    page.rebuild_patterns_list();
+                }
+                Err(e) => {
+                    eprintln!("Could not load exclusion patterns: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Rebuild the ListBox rows from the current patterns_store.
+    fn rebuild_patterns_list(&self) {
+        let imp = self.imp();
+
+        let list_box = match imp.patterns_list.borrow().clone() {
+            Some(lb) => lb,
+            None => return,
+        };
+
+        // Remove all existing rows.
+        while let Some(child) = list_box.first_child() {
+            list_box.remove(&child);
+        }
+
+        let patterns = imp.patterns_store.borrow().clone();
+        for (index, pattern) in patterns.iter().enumerate() {
+            let row = self.create_pattern_row(pattern, index);
+            list_box.append(&row);
+        }
+
+        for (button, suggestion) in imp
+            .suggestion_buttons
+            .borrow()
+            .iter()
+            .zip(SUGGESTED_PATTERNS)
+        {
+            button.set_sensitive(!patterns.iter().any(|p| p == suggestion));
+        }
+    }
+
+    /// Create a single row for an exclusion pattern with a delete button.
+    fn create_pattern_row(&self, pattern: &str, _index: usize) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(pattern)
+            .build();
+
+        let delete_button = gtk4::Button::builder()
+            .icon_name("edit-delete-symbolic")
+            .tooltip_text(&gettext("Remove pattern"))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat", "circular"])
+            .build();
+        delete_button.update_property(&[gtk4::accessible::Property::Label(
+            &gettext("Remove pattern {}").replace("{}", pattern),
+        )]);
+
+        row.add_suffix(&delete_button);
+
+        let page = self.clone();
+        let pattern_owned = pattern.to_string();
+        delete_button.connect_clicked(move |_| {
+            page.on_remove_pattern(&pattern_owned);
+        });
+
+        row
+    }
+
+    /// Add a new pattern from the entry field.
+    fn on_add_pattern(&self) {
+        let pattern = match self.imp().pattern_entry.borrow().as_ref() {
+            Some(entry) => {
+                let text = entry.text().to_string();
+                entry.set_text("");
+                text
+            }
+            None => return,
+        };
+
+        self.add_pattern(&pattern);
+    }
+
+    /// Append `pattern` and save, unless it is blank or already listed.
+    fn add_pattern(&self, pattern: &str) {
+        let imp = self.imp();
+
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return;
+        }
+
+        // Avoid duplicates.
+        if imp.patterns_store.borrow().iter().any(|p| p == pattern) {
+            return;
+        }
+
+        imp.patterns_store.borrow_mut().push(pattern.to_string());
+        self.rebuild_patterns_list();
+        self.save_exclusion_patterns();
+    }
+
+    /// The switch for exclusion option `key` and the value last known to be
+    /// in the daemon config.
+    fn exclusion_option(&self, key: &str) -> (Option<adw::SwitchRow>, &Cell<bool>) {
+        let imp = self.imp();
+        if key == CASE_SENSITIVE_KEY {
+            (imp.case_sensitive_row.borrow().clone(), &imp.case_sensitive)
+        } else {
+            (imp.match_basename_row.borrow().clone(), &imp.match_basename)
+        }
+    }
+
+    /// Set the case-sensitivity and file-name-only switches from the daemon
+    /// config.
+    fn load_exclusion_options(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.get_config().await {
+                Ok(yaml) => {
+                    for (key, default) in [
+                        (CASE_SENSITIVE_KEY, DEFAULT_CASE_SENSITIVE),
+                        (MATCH_BASENAME_KEY, DEFAULT_MATCH_BASENAME),
+                    ] {
+                        let enabled = config::top_level_value(&yaml, key)
+                            .and_then(|v| v.parse::<bool>().ok())
+                            .unwrap_or(default);
+                        let (row, known) = page.exclusion_option(key);
+                        known.set(enabled);
+                        if let Some(row) = row {
+                            row.set_active(enabled);
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Could not load exclusion options: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Write exclusion option `key` to the daemon config and refresh the
+    /// match preview, which depends on it. Reverts the switch on failure.
+    fn save_exclusion_option(&self, key: &'static str, enabled: bool) {
+        // Also true when the switch is set from the config or reverted.
+        if enabled == self.exclusion_option(key).1.get() {
+            return;
+        }
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let yaml = format!("{key}: {enabled}\n");
+            match client.set_config(&yaml).await {
+                Ok(()) => {
+                    page.exclusion_option(key).1.set(enabled);
+                    page.update_pattern_preview();
+                }
+                Err(e) => {
+                    eprintln!("Could not save {key}: {}", e);
+                    page.show_toast(&gettext("Could not change how exclusion patterns match"));
+                    if let Some(row) = page.exclusion_option(key).0 {
+                        row.set_active(!enabled);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Debounce preview requests while the user types.
+    fn schedule_pattern_preview(&self) {
+        let imp = self.imp();
+
+        if let Some(source_id) = imp.preview_source.borrow_mut().take() {
+            source_id.remove();
+        }
+
+        let page = self.clone();
+        let source_id = glib::timeout_add_local_once(
+            std::time::Duration::from_millis(PREVIEW_DEBOUNCE_MS),
+            move || {
+                page.imp().preview_source.replace(None);
+                page.update_pattern_preview();
+            },
+        );
+
+        imp.preview_source.replace(Some(source_id));
+    }
+
+    /// Ask the daemon which paths the entry's pattern matches and show them.
+    fn update_pattern_preview(&self) {
+        let imp = self.imp();
+
+        let generation = imp.preview_generation.get().wrapping_add(1);
+        imp.preview_generation.set(generation);
+
+        let pattern = imp
+            .pattern_entry
             .borrow()
             .as_ref()
-            .map(|r| r.value() as u32)
-            .unwrap_or(0);
+            .map(|e| e.text().trim().to_string())
+            .unwrap_or_default();
+
+        if pattern.is_empty() {
+            self.show_pattern_preview(&[]);
+            if let Some(ref row) = *imp.preview_row.borrow() {
+                row.set_visible(false);
+            }
+            return;
+        }
+
+        let client = match imp.dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            // Ask for one extra match to know whether the list was capped.
+            let result = client
+                .test_exclusion_pattern(&pattern, PREVIEW_LIMIT as u32 + 1)
+                .await;
+
+            if page.imp().preview_generation.get() != generation {
+                return;
+            }
+
+            match result {
+                Ok(matches) => page.show_pattern_preview(&matches),
+                Err(e) => {
+                    eprintln!("Could not preview exclusion pattern: {}", e);
+                    if let Some(ref row) = *page.imp().preview_row.borrow() {
+                        row.set_visible(false);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Replace the preview rows with `matches` (at most `PREVIEW_LIMIT`).
+    fn show_pattern_preview(&self, matches: &[String]) {
+        let imp = self.imp();
+
+        let preview_row = match imp.preview_row.borrow().clone() {
+            Some(r) => r,
+            None => return,
+        };
+
+        for row in imp.preview_rows.borrow_mut().drain(..) {
+            preview_row.remove(&row);
+        }
+
+        let subtitle = if matches.is_empty() {
+            gettext("Nothing in your OneDrive matches this pattern")
+        } else if matches.len() > PREVIEW_LIMIT {
+            ngettext(
+                "More than {} item matches",
+                "More than {} items match",
+                PREVIEW_LIMIT as u32,
+            )
+            .replace("{}", &PREVIEW_LIMIT.to_string())
+        } else {
+            ngettext("{} item matches", "{} items match", matches.len() as u32)
+                .replace("{}", &matches.len().to_string())
+        };
+        preview_row.set_subtitle(&subtitle);
+        preview_row.set_enable_expansion(!matches.is_empty());
+
+        let mut rows = imp.preview_rows.borrow_mut();
+        for path in matches.iter().take(PREVIEW_LIMIT) {
+            let row = adw::ActionRow::builder()
+                .title(path)
+                .title_selectable(true)
+                .css_classes(["monospace"])
+                .build();
+            preview_row.add_row(&row);
+            rows.push(row);
+        }
+
+        preview_row.set_visible(true);
+    }
+
+    /// Remove a pattern by value.
+    fn on_remove_pattern(&self, pattern: &str) {
+        let imp = self.imp();
+
+        imp.patterns_store
+            .borrow_mut()
+            .retain(|p| p != pattern);
+
+        self.rebuild_patterns_list();
+        self.save_exclusion_patterns();
+    }
+
+    /// Send the current patterns to the daemon.
+    fn save_exclusion_patterns(&self) {
+        let imp = self.imp();
+        let patterns = imp.patterns_store.borrow().clone();
+
+        let client = match imp.dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        glib::MainContext::default().spawn_local(async move {
+            if let Err(e) = client.set_exclusion_patterns(&patterns).await {
+                eprintln!("Could not save exclusion patterns: {}", e);
+            }
+        });
+    }
+
+    // -- Bandwidth Limits ----------------------------------------------------
+
+    /// Load bandwidth limits from daemon config.
+    fn load_bandwidth_limits(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.get_config().await {
+                Ok(yaml) => {
+                    page.apply_bandwidth_config(&yaml);
+                    page.apply_parallel_transfers(&yaml);
+                    page.apply_conflict_copy_template(&yaml);
+                }
+                Err(e) => {
+                    eprintln!("Could not load bandwidth config: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Parse bandwidth settings from YAML and apply to spin rows.
+    fn apply_bandwidth_config(&self, yaml: &str) {
+        let imp = self.imp();
+
+        for line in yaml.lines() {
+            let line = line.trim();
+            if let Some((key, value)) = line.split_once(':') {
+                let key = key.trim();
+                let value = value.trim().trim_matches('"');
+
+                match key {
+                    "upload_limit_kbps" | "upload_limit" => {
+                        if let Ok(val) = value.parse::<f64>() {
+                            imp.upload_kbps.set((val.max(0.0) as u32).min(MAX_LIMIT_KBPS));
+                        }
+                    }
+                    "download_limit_kbps" | "download_limit" => {
+                        if let Ok(val) = value.parse::<f64>() {
+                            imp.download_kbps.set((val.max(0.0) as u32).min(MAX_LIMIT_KBPS));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        imp.committed_kbps
+            .set((imp.upload_kbps.get(), imp.download_kbps.get()));
+        self.refresh_bandwidth_rows();
+    }
 
-        let download = imp
-            .download_row
+    /// The unit currently selected in the unit ComboRow.
+    fn selected_unit(&self) -> &'static BandwidthUnit {
+        let idx = self
+            .imp()
+            .unit_row
             .borrow()
             .as_ref()
-            .map(|r| r.value() as u32)
+            .map(|r| r.selected() as usize)
             .unwrap_or(0);
+        BANDWIDTH_UNITS.get(idx).unwrap_or(&BANDWIDTH_UNITS[0])
+    }
+
+    /// Rescale both spin rows to the selected unit and show the stored
+    /// limits in it, without touching the stored values.
+    fn refresh_bandwidth_rows(&self) {
+        let imp = self.imp();
+        let unit = self.selected_unit();
+        let max = MAX_LIMIT_KBPS as f64 / unit.kbps_per_unit;
+
+        imp.updating_rows.set(true);
+        for (row, kbps) in [
+            (&imp.upload_row, imp.upload_kbps.get()),
+            (&imp.download_row, imp.download_kbps.get()),
+        ] {
+            if let Some(ref row) = *row.borrow() {
+                row.set_digits(unit.digits);
+                row.set_range(0.0, max);
+                row.adjustment().set_step_increment(unit.step);
+                row.set_value(kbps as f64 / unit.kbps_per_unit);
+            }
+        }
+        imp.updating_rows.set(false);
+    }
+
+    /// Convert the spin row values back to KB/s and schedule a save.
+    /// 0 stays 0 (unlimited) in every unit.
+    fn on_bandwidth_value_changed(&self) {
+        let imp = self.imp();
+        if imp.updating_rows.get() {
+            return;
+        }
+
+        let unit = self.selected_unit();
+        let to_kbps = |row: &RefCell<Option<adw::SpinRow>>| {
+            row.borrow()
+                .as_ref()
+                .map(|r| ((r.value() * unit.kbps_per_unit).round() as u32).min(MAX_LIMIT_KBPS))
+                .unwrap_or(0)
+        };
+        imp.upload_kbps.set(to_kbps(&imp.upload_row));
+        imp.download_kbps.set(to_kbps(&imp.download_row));
+
+        self.schedule_bandwidth_save();
+    }
+
+    /// Schedule a debounced bandwidth save (500ms).
+    fn schedule_bandwidth_save(&self) {
+        let imp = self.imp();
+
+        if let Some(source_id) = imp.debounce_source.borrow_mut().take() {
+            source_id.remove();
+        }
+
+        let page = self.clone();
+        let source_id = glib::timeout_add_local_once(
+            std::time::Duration::from_millis(500),
+            move || {
+                page.imp().debounce_source.replace(None);
+                page.save_bandwidth_limits();
+            },
+        );
+
+        imp.debounce_source.replace(Some(source_id));
+    }
+
+    /// Send bandwidth limits to the daemon.
+    /// Asks for confirmation first if either limit is suspiciously low.
+    fn save_bandwidth_limits(&self) {
+        let imp = self.imp();
+
+        let upload = imp.upload_kbps.get();
+        let download = imp.download_kbps.get();
+
+        let is_low = |kbps: u32| kbps > 0 && kbps < LOW_LIMIT_WARNING_KBPS;
+        if is_low(upload) || is_low(download) {
+            self.confirm_low_bandwidth(upload, download);
+        } else {
+            self.commit_bandwidth_limits(upload, download);
+        }
+    }
+
+    /// Ask the user to confirm a very low limit. Cancelling restores the
+    /// previously saved limits.
+    fn confirm_low_bandwidth(&self, upload: u32, download: u32) {
+        let confirm = util::confirmation_dialog(
+            &gettext("Very Low Bandwidth Limit"),
+            &gettext(
+                "This is very slow and may stall syncing for a long time. Are you sure?",
+            ),
+        );
+        confirm.add_response("apply", &gettext("Set Limit"));
+        confirm.set_response_appearance("apply", adw::ResponseAppearance::Destructive);
+
+        let page = self.clone();
+        confirm.connect_response(None, move |_dialog, response| {
+            if response == "apply" {
+                page.commit_bandwidth_limits(upload, download);
+            } else {
+                page.restore_committed_bandwidth();
+            }
+        });
+
+        adw::prelude::AdwDialogExt::present(&confirm, Some(self.upcast_ref::<gtk4::Widget>()));
+    }
+
+    /// Put the rows back to the limits the daemon last accepted.
+    fn restore_committed_bandwidth(&self) {
+        let imp = self.imp();
+        let (saved_upload, saved_download) = imp.committed_kbps.get();
+        imp.upload_kbps.set(saved_upload);
+        imp.download_kbps.set(saved_download);
+        self.refresh_bandwidth_rows();
+    }
+
+    /// Write the given limits to the daemon config. They only count as
+    /// committed once the daemon accepted them; on failure the rows go back
+    /// to the last accepted limits.
+    fn commit_bandwidth_limits(&self, upload: u32, download: u32) {
+        let imp = self.imp();
 
         let yaml = format!(
             "upload_limit_kbps: {}\ndownload_limit_kbps: {}\n",
@@ -420,10 +2050,111 @@ impl AdvancedPage {
             None => return,
         };
 
+        let page = self.clone();
         glib::MainContext::default().spawn_local(async move {
-            if let Err(e) = client.set_config(&yaml).await {
-                eprintln!("Could not save bandwidth config: {}", e);
+            match client.set_config(&yaml).await {
+                Ok(()) => page.imp().committed_kbps.set((upload, download)),
+                Err(e) => {
+                    eprintln!("Could not save bandwidth config: {}", e);
+                    page.restore_committed_bandwidth();
+                    page.show_toast(&gettext("Could not save the bandwidth limits"));
+                }
             }
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::{
+        find_widgets, labels, present, run_widget_test, wait_until, MockState, TestDaemon,
+    };
+
+    #[test]
+    fn accepts_a_template_without_the_name() {
+        let template = "Conflict {date}{ext}";
+        assert_eq!(conflict_copy_template_error(template), None);
+        assert_eq!(
+            conflict_copy_name(template, "report.docx", "2026-02-07"),
+            "Conflict 2026-02-07.docx"
+        );
+    }
+
+    #[test]
+    fn refuses_a_template_with_a_slash() {
+        assert_eq!(
+            conflict_copy_template_error("conflicts/{name} {date}{ext}"),
+            Some(gettext("The name cannot contain a slash"))
+        );
+    }
+
+    #[test]
+    fn names_copies_of_files_without_an_extension() {
+        assert_eq!(
+            conflict_copy_name(DEFAULT_CONFLICT_COPY_TEMPLATE, "Makefile", "2026-02-07"),
+            "Makefile (conflict 2026-02-07)"
+        );
+        assert_eq!(
+            conflict_copy_name("{name} ({n}){ext}", "Makefile", "2026-02-07"),
+            "Makefile (1)"
+        );
+    }
+
+    #[test]
+    fn keeps_the_whole_name_of_dotfiles() {
+        assert_eq!(
+            conflict_copy_name(DEFAULT_CONFLICT_COPY_TEMPLATE, ".bashrc", "2026-02-07"),
+            ".bashrc (conflict 2026-02-07)"
+        );
+        assert_eq!(
+            conflict_copy_name(DEFAULT_CONFLICT_COPY_TEMPLATE, ".config.yaml", "2026-02-07"),
+            ".config (conflict 2026-02-07).yaml"
+        );
+    }
+
+    #[test]
+    fn verifies_files_after_confirmation() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let window = present(&gtk4::Box::new(gtk4::Orientation::Vertical, 0));
+            let preferences = adw::PreferencesDialog::new();
+            let page = AdvancedPage::new(daemon.client());
+            preferences.add(&page);
+            preferences.set_visible_page(&page);
+            preferences.present(Some(&window));
+
+            let verify_button = page.imp().verify_button.borrow().clone().unwrap();
+            verify_button.emit_clicked();
+            let confirm = || {
+                find_widgets::<adw::AlertDialog>(&window)
+                    .into_iter()
+                    .find(|d| {
+                        d.heading()
+                            .is_some_and(|h| h == gettext("Verify All Files?"))
+                    })
+            };
+            wait_until("the confirmation", || confirm().is_some());
+            confirm()
+                .unwrap()
+                .emit_by_name::<()>("response", &[&"verify"]);
+
+            let done = ngettext(
+                "Verification complete, {} item repaired",
+                "Verification complete, {} items repaired",
+                1,
+            )
+            .replace("{}", "1");
+            wait_until("the verification to finish", || {
+                labels(&preferences).contains(&done)
+            });
+            assert!(verify_button.is_sensitive());
+            assert_eq!(
+                daemon.state().sync_errors[0]["path"],
+                "/Documents/draft.docx"
+            );
+
+            window.destroy();
+        });
+    }
+}
@@ -0,0 +1,380 @@
+// Config Editor — adw::Dialog subclass
+//
+// Raw YAML editor for the daemon configuration, opened from the Developer
+// group on the Advanced page. The buffer shows the GetConfig() text with
+// secrets redacted; on save, placeholders the user left alone get their
+// original values back before SetConfig(). Secrets are masked line by line,
+// so comments and layout are kept and every other line is saved as it was.
+// "Validate" parses the buffer, with the secrets restored, and reports the
+// first error inline; "Save" only proceeds when it is valid and the user
+// has confirmed the warning that a bad config can stop syncing.
+
+use std::cell::RefCell;
+
+use gettextrs::gettext;
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use crate::config::{redact_config_lines, restore_redacted, validate_config};
+use crate::dbus_client::DbusClient;
+use crate::util;
+
+// ---------------------------------------------------------------------------
+// ConfigEditorDialog — adw::Dialog subclass
+// ---------------------------------------------------------------------------
+
+mod imp {
+    use super::*;
+    use gtk4::subclass::prelude::*;
+    use libadwaita::subclass::prelude::*;
+
+    pub struct ConfigEditorDialog {
+        pub dbus_client: RefCell<Option<DbusClient>>,
+        /// The config as GetConfig() returned it, secrets included.
+        pub original: RefCell<String>,
+        pub text_view: RefCell<Option<gtk4::TextView>>,
+        pub status_label: RefCell<Option<gtk4::Label>>,
+        pub save_button: RefCell<Option<gtk4::Button>>,
+        pub toast_overlay: RefCell<Option<adw::ToastOverlay>>,
+    }
+
+    impl Default for ConfigEditorDialog {
+        fn default() -> Self {
+            Self {
+                dbus_client: RefCell::new(None),
+                original: RefCell::new(String::new()),
+                text_view: RefCell::new(None),
+                status_label: RefCell::new(None),
+                save_button: RefCell::new(None),
+                toast_overlay: RefCell::new(None),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ConfigEditorDialog {
+        const NAME: &'static str = "LnxdriveConfigEditorDialog";
+        type Type = super::ConfigEditorDialog;
+        type ParentType = adw::Dialog;
+    }
+
+    impl ObjectImpl for ConfigEditorDialog {}
+    impl WidgetImpl for ConfigEditorDialog {}
+    impl AdwDialogImpl for ConfigEditorDialog {}
+}
+
+glib::wrapper! {
+    pub struct ConfigEditorDialog(ObjectSubclass<imp::ConfigEditorDialog>)
+        @extends adw::Dialog, gtk4::Widget,
+        @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget;
+}
+
+impl ConfigEditorDialog {
+    pub fn new(dbus_client: &DbusClient) -> Self {
+        let dialog: Self = glib::Object::builder()
+            .property("title", gettext("Edit Configuration"))
+            .property("content-width", 640)
+            .property("content-height", 560)
+            .build();
+
+        dialog
+            .imp()
+            .dbus_client
+            .replace(Some(dbus_client.clone()));
+
+        dialog.build_ui();
+        dialog.load_config();
+        dialog
+    }
+
+    fn build_ui(&self) {
+        let imp = self.imp();
+
+        let toolbar_view = adw::ToolbarView::new();
+        let header = adw::HeaderBar::new();
+
+        let validate_button = gtk4::Button::builder()
+            .label(&gettext("Validate"))
+            .build();
+        header.pack_start(&validate_button);
+
+        let save_button = gtk4::Button::builder()
+            .label(&gettext("Save"))
+            .css_classes(["suggested-action"])
+            .sensitive(false)
+            .build();
+        header.pack_end(&save_button);
+        imp.save_button.replace(Some(save_button.clone()));
+
+        toolbar_view.add_top_bar(&header);
+
+        let text_view = gtk4::TextView::builder()
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .top_margin(12)
+            .bottom_margin(12)
+            .left_margin(12)
+            .right_margin(12)
+            .build();
+        text_view.update_property(&[gtk4::accessible::Property::Label(&gettext(
+            "Daemon configuration",
+        ))]);
+        imp.text_view.replace(Some(text_view.clone()));
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vexpand(true)
+            .child(&text_view)
+            .build();
+
+        // Inline validation result, below the editor.
+        let status_label = gtk4::Label::builder()
+            .halign(gtk4::Align::Start)
+            .wrap(true)
+            .selectable(true)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(6)
+            .margin_bottom(6)
+            .visible(false)
+            .build();
+        imp.status_label.replace(Some(status_label.clone()));
+
+        let content = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
+        content.append(&scrolled);
+        content.append(&status_label);
+
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&content));
+        imp.toast_overlay.replace(Some(toast_overlay.clone()));
+
+        toolbar_view.set_content(Some(&toast_overlay));
+        self.set_child(Some(&toolbar_view));
+
+        // Any edit invalidates the last validation result.
+        let dialog = self.clone();
+        text_view.buffer().connect_changed(move |_| {
+            dialog.clear_status();
+        });
+
+        let dialog = self.clone();
+        validate_button.connect_clicked(move |_| {
+            dialog.validate();
+        });
+
+        let dialog = self.clone();
+        save_button.connect_clicked(move |_| {
+            if dialog.validate() {
+                dialog.confirm_save();
+            }
+        });
+    }
+
+    /// Prefill the editor from the daemon, with secrets redacted.
+    fn load_config(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let dialog = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.get_config().await {
+                Ok(yaml) => {
+                    if let Some(ref view) = *dialog.imp().text_view.borrow() {
+                        view.buffer().set_text(&redact_config_lines(&yaml));
+                    }
+                    dialog.imp().original.replace(yaml);
+                    if let Some(ref button) = *dialog.imp().save_button.borrow() {
+                        button.set_sensitive(true);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Could not load config: {}", e);
+                    dialog.show_status(
+                        &gettext("Could not load the configuration"),
+                        false,
+                    );
+                }
+            }
+        });
+    }
+
+    /// Current editor contents.
+    fn text(&self) -> String {
+        match *self.imp().text_view.borrow() {
+            Some(ref view) => {
+                let buffer = view.buffer();
+                buffer
+                    .text(&buffer.start_iter(), &buffer.end_iter(), false)
+                    .to_string()
+            }
+            None => String::new(),
+        }
+    }
+
+    /// The buffer with the redacted values restored, as it would be saved.
+    fn restored_text(&self) -> Result<String, String> {
+        restore_redacted(&self.text(), &self.imp().original.borrow())
+    }
+
+    /// Parse the buffer and report the result inline. Returns whether it
+    /// is valid.
+    fn validate(&self) -> bool {
+        match self.restored_text().and_then(|yaml| validate_config(&yaml)) {
+            Ok(()) => {
+                self.show_status(&gettext("The configuration is valid"), true);
+                true
+            }
+            Err(e) => {
+                self.show_status(&e, false);
+                false
+            }
+        }
+    }
+
+    fn show_status(&self, message: &str, ok: bool) {
+        if let Some(ref label) = *self.imp().status_label.borrow() {
+            label.set_label(message);
+            if ok {
+                label.remove_css_class("error");
+                label.add_css_class("success");
+            } else {
+                label.remove_css_class("success");
+                label.add_css_class("error");
+            }
+            label.set_visible(true);
+        }
+    }
+
+    fn clear_status(&self) {
+        if let Some(ref label) = *self.imp().status_label.borrow() {
+            label.set_visible(false);
+        }
+    }
+
+    /// Warn about the consequences of a bad config before writing it.
+    fn confirm_save(&self) {
+        let confirm = util::confirmation_dialog(
+            &gettext("Save Configuration?"),
+            &gettext(
+                "The daemon applies the new configuration immediately. Settings it does not accept can stop LNXDrive from syncing.",
+            ),
+        );
+        confirm.add_response("save", &gettext("Save"));
+        confirm.set_response_appearance("save", adw::ResponseAppearance::Destructive);
+
+        let dialog = self.clone();
+        confirm.connect_response(None, move |_dialog, response| {
+            if response == "save" {
+                dialog.save();
+            }
+        });
+
+        adw::prelude::AdwDialogExt::present(&confirm, Some(self.upcast_ref::<gtk4::Widget>()));
+    }
+
+    /// Send the buffer, with the redacted values restored, to the daemon and
+    /// close on success.
+    fn save(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+        let yaml = match self.restored_text() {
+            Ok(yaml) => yaml,
+            Err(e) => {
+                self.show_status(&e, false);
+                return;
+            }
+        };
+
+        let dialog = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.set_config(&yaml).await {
+                Ok(()) => {
+                    dialog.close();
+                }
+                Err(e) => {
+                    eprintln!("Could not save config: {}", e);
+                    if let Some(ref overlay) = *dialog.imp().toast_overlay.borrow() {
+                        overlay.add_toast(adw::Toast::new(&gettext(
+                            "Could not save the configuration",
+                        )));
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::{present, run_widget_test, wait_until, MockState, TestDaemon};
+
+    #[test]
+    fn never_shows_secrets_and_keeps_them_on_save() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState {
+                config: "sync_root: /home/user/OneDrive\nauth:\n  refresh_token: M.R3_BAY.secret\n"
+                    .to_string(),
+                ..MockState::default()
+            });
+            let window = present(&gtk4::Box::new(gtk4::Orientation::Vertical, 0));
+            let editor = ConfigEditorDialog::new(daemon.client());
+            editor.present(Some(&window));
+
+            wait_until("the configuration", || editor.text().contains("sync_root"));
+            assert!(!editor.text().contains("M.R3_BAY.secret"));
+
+            let edited = editor.text().replace("/home/user/OneDrive", "/home/user/Cloud");
+            if let Some(ref view) = *editor.imp().text_view.borrow() {
+                view.buffer().set_text(&edited);
+            }
+            editor.save();
+            wait_until("the configuration to be saved", || {
+                daemon.state().config.contains("/home/user/Cloud")
+            });
+            assert!(daemon.state().config.contains("M.R3_BAY.secret"));
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn saves_an_unedited_config_as_it_was() {
+        run_widget_test(|| {
+            let config = concat!(
+                "# Managed by hand\n",
+                "sync_root: /home/user/OneDrive  # default\n",
+                "auth:\n",
+                "  refresh_token: M.R3_BAY.secret\n",
+            );
+            let daemon = TestDaemon::start(MockState {
+                config: config.to_string(),
+                ..MockState::default()
+            });
+            let window = present(&gtk4::Box::new(gtk4::Orientation::Vertical, 0));
+            let editor = ConfigEditorDialog::new(daemon.client());
+            editor.present(Some(&window));
+
+            wait_until("the configuration", || editor.text().contains("sync_root"));
+            assert!(editor.text().contains("# Managed by hand"));
+            assert!(!editor.text().contains("M.R3_BAY.secret"));
+
+            // Saving an unchanged config must still reach the daemon.
+            daemon.state().config.clear();
+            editor.save();
+            wait_until("the configuration to be saved", || {
+                !daemon.state().config.is_empty()
+            });
+            assert_eq!(daemon.state().config, config);
+
+            window.destroy();
+        });
+    }
+}
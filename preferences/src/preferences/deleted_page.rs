@@ -0,0 +1,417 @@
+// Recently Deleted Page — adw::PreferencesPage subclass
+//
+// Lists items that were deleted in the cloud and then removed from this
+// computer by sync, newest first, so a deletion made by mistake elsewhere can
+// be undone. Each row shows the file name, the path and when it was deleted,
+// with a "Restore" button that asks the daemon to bring the item back in both
+// places. Like the conflicts page, the list is replaced wholesale on every
+// load and an "empty" row stands in for it when nothing was deleted. If the
+// list can't be read, an error row with "Retry" is shown instead of the
+// empty row, so a failed call never reads as "nothing to restore".
+
+use std::cell::RefCell;
+
+use gettextrs::gettext;
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use serde::Deserialize;
+
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
+
+use crate::dbus_client::DbusClient;
+
+// ---------------------------------------------------------------------------
+// DeletedItem — deserialized from daemon JSON
+// ---------------------------------------------------------------------------
+
+/// An item from `ListDeleted`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeletedItem {
+    pub id: String,
+    pub path: String,
+    /// ISO 8601.
+    #[serde(default)]
+    pub deleted_at: String,
+    /// False once the cloud copy is gone for good.
+    #[serde(default = "restorable_default")]
+    pub restorable: bool,
+}
+
+fn restorable_default() -> bool {
+    true
+}
+
+impl DeletedItem {
+    /// Parse a JSON array string into a list of items, newest first. Fails
+    /// on a malformed reply rather than showing it as an empty list.
+    pub fn from_json_array(json_str: &str) -> Result<Vec<Self>, serde_json::Error> {
+        let mut items: Vec<Self> = serde_json::from_str(json_str)?;
+        items.sort_by_key(|item| {
+            std::cmp::Reverse(item.deleted_datetime().map(|dt| dt.to_unix()))
+        });
+        Ok(items)
+    }
+
+    pub fn filename(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    fn deleted_datetime(&self) -> Option<glib::DateTime> {
+        glib::DateTime::from_iso8601(&self.deleted_at, Some(&glib::TimeZone::utc())).ok()
+    }
+
+    /// Deletion time in the user's local time zone and locale format, or
+    /// the raw timestamp if it cannot be parsed.
+    pub fn deleted_absolute(&self) -> String {
+        self.deleted_datetime()
+            .and_then(|dt| dt.to_local().ok())
+            .and_then(|dt| dt.format("%c").ok())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| self.deleted_at.clone())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// DeletedPage — adw::PreferencesPage subclass
+// ---------------------------------------------------------------------------
+
+mod imp {
+    use super::*;
+    use gtk4::subclass::prelude::*;
+    use libadwaita::subclass::prelude::*;
+
+    pub struct DeletedPage {
+        pub dbus_client: RefCell<Option<DbusClient>>,
+        pub deleted_group: RefCell<Option<adw::PreferencesGroup>>,
+        /// "Nothing was deleted recently", shown instead of an empty list.
+        pub empty_row: RefCell<Option<adw::ActionRow>>,
+        /// Shown with a Retry button when the list could not be loaded.
+        pub error_row: RefCell<Option<adw::ActionRow>>,
+        pub item_rows: RefCell<Vec<adw::ActionRow>>,
+    }
+
+    impl Default for DeletedPage {
+        fn default() -> Self {
+            Self {
+                dbus_client: RefCell::new(None),
+                deleted_group: RefCell::new(None),
+                empty_row: RefCell::new(None),
+                error_row: RefCell::new(None),
+                item_rows: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for DeletedPage {
+        const NAME: &'static str = "LnxdriveDeletedPage";
+        type Type = super::DeletedPage;
+        type ParentType = adw::PreferencesPage;
+    }
+
+    impl ObjectImpl for DeletedPage {}
+    impl WidgetImpl for DeletedPage {}
+    impl PreferencesPageImpl for DeletedPage {}
+}
+
+glib::wrapper! {
+    pub struct DeletedPage(ObjectSubclass<imp::DeletedPage>)
+        @extends adw::PreferencesPage, gtk4::Widget,
+        @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget;
+}
+
+impl DeletedPage {
+    pub fn new(dbus_client: &DbusClient) -> Self {
+        let page: Self = glib::Object::builder()
+            .property("icon-name", "user-trash-symbolic")
+            .property("title", gettext("Recently Deleted"))
+            .build();
+
+        page.imp()
+            .dbus_client
+            .replace(Some(dbus_client.clone()));
+
+        page.build_ui();
+        page.load_deleted();
+
+        page
+    }
+
+    /// Fetch the deleted items again. The list is rebuilt from scratch.
+    pub fn reload(&self) {
+        self.load_deleted();
+    }
+
+    fn build_ui(&self) {
+        let imp = self.imp();
+
+        let deleted_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Recently Deleted"))
+            .description(&gettext(
+                "Items deleted from your OneDrive and then removed from this computer by sync. Restoring an item brings it back in both places.",
+            ))
+            .build();
+
+        let empty_row = adw::ActionRow::builder()
+            .title(&gettext("Nothing was deleted recently"))
+            .build();
+        deleted_group.add(&empty_row);
+
+        let error_row = adw::ActionRow::builder()
+            .title(&gettext("Could not load recently deleted items"))
+            .css_classes(["error"])
+            .visible(false)
+            .build();
+        let retry_button = gtk4::Button::builder()
+            .label(&gettext("Retry"))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        let page = self.clone();
+        retry_button.connect_clicked(move |_| {
+            page.load_deleted();
+        });
+        error_row.add_suffix(&retry_button);
+        deleted_group.add(&error_row);
+
+        imp.empty_row.replace(Some(empty_row));
+        imp.error_row.replace(Some(error_row));
+        imp.deleted_group.replace(Some(deleted_group.clone()));
+        self.add(&deleted_group);
+    }
+
+    /// Fetch the deleted items from the daemon and populate the group.
+    fn load_deleted(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.list_deleted().await {
+                Ok(json_str) => match DeletedItem::from_json_array(&json_str) {
+                    Ok(items) => page.populate(&items),
+                    Err(e) => {
+                        eprintln!("Could not parse deleted items: {e}");
+                        page.show_load_error();
+                    }
+                },
+                Err(e) => {
+                    eprintln!("Could not load deleted items: {e}");
+                    page.show_load_error();
+                }
+            }
+        });
+    }
+
+    /// Show the error row in place of the empty row, keeping any rows from
+    /// an earlier load.
+    fn show_load_error(&self) {
+        let imp = self.imp();
+        if let Some(ref error_row) = *imp.error_row.borrow() {
+            error_row.set_visible(true);
+        }
+        if let Some(ref empty_row) = *imp.empty_row.borrow() {
+            empty_row.set_visible(false);
+        }
+    }
+
+    fn populate(&self, items: &[DeletedItem]) {
+        let imp = self.imp();
+        let group = match imp.deleted_group.borrow().clone() {
+            Some(g) => g,
+            None => return,
+        };
+
+        if let Some(ref error_row) = *imp.error_row.borrow() {
+            error_row.set_visible(false);
+        }
+
+        for row in imp.item_rows.borrow_mut().drain(..) {
+            group.remove(&row);
+        }
+
+        let mut rows = Vec::new();
+        for item in items {
+            let row = self.create_item_row(item);
+            group.add(&row);
+            rows.push(row);
+        }
+        *imp.item_rows.borrow_mut() = rows;
+
+        if let Some(ref empty_row) = *imp.empty_row.borrow() {
+            empty_row.set_visible(items.is_empty());
+        }
+    }
+
+    fn create_item_row(&self, item: &DeletedItem) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(item.filename())
+            .subtitle(&format!("{} — {}", item.path, item.deleted_absolute()))
+            .subtitle_lines(1)
+            .tooltip_text(&item.path)
+            .build();
+
+        if item.restorable {
+            let restore_button = gtk4::Button::builder()
+                .label(&gettext("Restore"))
+                .valign(gtk4::Align::Center)
+                .css_classes(["flat"])
+                .build();
+            let page = self.clone();
+            let item = item.clone();
+            restore_button.connect_clicked(move |button| {
+                button.set_sensitive(false);
+                page.restore(&item);
+            });
+            row.add_suffix(&restore_button);
+        }
+
+        row
+    }
+
+    /// Ask the daemon to restore `item`, then reload the list.
+    fn restore(&self, item: &DeletedItem) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let item = item.clone();
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.restore_deleted(&item.id).await {
+                Ok(true) => {
+                    page.show_toast(&gettext("Restored {}").replace("{}", item.filename()));
+                }
+                Ok(false) => {
+                    page.show_toast(
+                        &gettext("{} can no longer be restored").replace("{}", item.filename()),
+                    );
+                }
+                Err(e) => {
+                    eprintln!("Could not restore {}: {e}", item.path);
+                    page.show_toast(
+                        &gettext("Could not restore {}").replace("{}", item.filename()),
+                    );
+                }
+            }
+            page.load_deleted();
+        });
+    }
+
+    /// Show a toast on the enclosing PreferencesDialog.
+    fn show_toast(&self, message: &str) {
+        if let Some(dialog) = self
+            .ancestor(adw::PreferencesDialog::static_type())
+            .and_then(|w| w.downcast::<adw::PreferencesDialog>().ok())
+        {
+            dialog.add_toast(adw::Toast::new(message));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::test_harness::{
+        find_widgets, present, run_widget_test, wait_until, MockState, TestDaemon,
+    };
+
+    /// Title of each item row, top to bottom.
+    fn item_titles(page: &DeletedPage) -> Vec<String> {
+        page.imp()
+            .item_rows
+            .borrow()
+            .iter()
+            .map(|row| row.title().to_string())
+            .collect()
+    }
+
+    /// The row's Restore button, if the item can be restored.
+    fn restore_button(row: &adw::ActionRow) -> Option<gtk4::Button> {
+        find_widgets::<gtk4::Button>(row)
+            .into_iter()
+            .find(|b| b.label().is_some_and(|l| l == gettext("Restore")))
+    }
+
+    #[test]
+    fn lists_deleted_items_and_restores_one() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let page = DeletedPage::new(daemon.client());
+            let window = present(&page);
+
+            wait_until("the deleted items", || !item_titles(&page).is_empty());
+            assert_eq!(
+                item_titles(&page),
+                ["IMG_0042.jpg", "old-report.docx", "2019.zip"]
+            );
+            let rows = page.imp().item_rows.borrow().clone();
+            assert!(restore_button(&rows[2]).is_none());
+
+            restore_button(&rows[1])
+                .expect("a Restore button")
+                .emit_clicked();
+            wait_until("the restored item to go", || {
+                item_titles(&page) == ["IMG_0042.jpg", "2019.zip"]
+            });
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn offers_retry_when_the_list_cannot_be_loaded() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState {
+                deleted_supported: false,
+                ..MockState::default()
+            });
+            let page = DeletedPage::new(daemon.client());
+            let window = present(&page);
+
+            let imp = page.imp();
+            let error_row = imp.error_row.borrow().clone().unwrap();
+            wait_until("the error row", || error_row.is_visible());
+            assert!(!imp.empty_row.borrow().as_ref().unwrap().is_visible());
+
+            daemon.state().deleted_supported = true;
+            find_widgets::<gtk4::Button>(&error_row)
+                .into_iter()
+                .find(|b| b.label().is_some_and(|l| l == gettext("Retry")))
+                .expect("a Retry button")
+                .emit_clicked();
+            wait_until("the deleted items", || item_titles(&page).len() == 3);
+            assert!(!error_row.is_visible());
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn reports_a_malformed_list_instead_of_an_empty_one() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState {
+                // No "id", which every item must have.
+                deleted: vec![json!({"path": "/Documents/old-report.docx"})],
+                ..MockState::default()
+            });
+            let page = DeletedPage::new(daemon.client());
+            let window = present(&page);
+
+            let imp = page.imp();
+            let error_row = imp.error_row.borrow().clone().unwrap();
+            wait_until("the error row", || error_row.is_visible());
+            assert!(!imp.empty_row.borrow().as_ref().unwrap().is_visible());
+            assert!(item_titles(&page).is_empty());
+
+            window.destroy();
+        });
+    }
+}
@@ -3,21 +3,60 @@
 // Displays the remote OneDrive folder hierarchy using a `gtk::ListView` backed
 // by a `gtk::TreeListModel`. Each row has a TreeExpander, a CheckButton, and a
 // Label. Toggling a folder propagates to its children. The set of selected
-// paths is sent to the daemon via `set_selected_folders()`.
+// folders is sent to the daemon via `set_selected_folders()`, each folder
+// named by its unique `id` (two nodes can share a path, e.g. a folder you own
+// and one shared with you) or by its path when the daemon sends no id.
+// Selections saved before ids existed name folders by path; such an entry is
+// taken as the id of the one folder at that path, and saved as the id. Each
+// tree shows a single sync root, identified by its `root_id`. Rows show their
+// full remote path as a tooltip, and a breadcrumb above the list follows the
+// focused row so deeply nested folders can be placed at a glance.
+//
+// Each row also carries a badge for how much of the folder is on disk: a
+// cloud for placeholders (online-only), a half-filled one for partly
+// downloaded folders and a checkmark for fully downloaded ones. The context
+// menu (right-click or long-press) pins or unpins the folder's contents;
+// unpinning first asks for confirmation, stating how much disk it frees.
+//
+// On business accounts, shared folders get a "Shared with N people" button
+// whose popover lists the collaborators. Personal accounts don't report
+// sharing, so the button never appears there.
+//
+// Once the tree and the daemon's selection have loaded, an empty selection
+// is spelled out above the list ("nothing will sync"), so it can't be mistaken
+// for a tree that is still loading. The hint goes away with the first check.
+//
+// "Show selected only" above the list narrows it to checked folders and the
+// folders leading to them. The rows pass through a FilterListModel whose
+// EveryFilter holds that check, so any further filter (such as a text search)
+// joins the same EveryFilter and both apply together.
+//
+// Toggles are saved after a 500ms debounce; the confirmation toast offers
+// "Undo", which restores the selection the daemon had before that save.
+// With the `apply-folder-changes` setting, toggles are only staged instead:
+// an "Unsaved changes" bar with Revert and Apply appears above the list, and
+// nothing reaches the daemon until Apply. Staged changes are dropped on
+// reload. Turning the setting off saves any staged changes.
 //
 // The tree is lazily loaded: each expand triggers the TreeListModel's
 // create_model closure, which parses the JSON subtree for the expanded node.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
+use gettextrs::{gettext, ngettext};
 use gtk4::gio;
 use gtk4::glib;
 use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
 use serde::Deserialize;
 
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 
-use crate::dbus_client::DbusClient;
+use crate::config;
+use crate::dbus_client::{Collaborator, DbusClient, HydrationState, SyncRoot};
+use crate::settings::{keys, AppSettings};
+use crate::util;
 
 // ---------------------------------------------------------------------------
 // JSON schema for the remote folder tree returned by the daemon
@@ -25,14 +64,67 @@ use crate::dbus_client::DbusClient;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct FolderNodeJson {
+    /// Unique across the tree; empty when the daemon sends none.
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub path: String,
     #[serde(default)]
     pub children: Vec<FolderNodeJson>,
 }
 
+impl FolderNodeJson {
+    /// How the daemon's selection names this folder: its id, or its path if
+    /// it has none.
+    pub fn selection_key(&self) -> &str {
+        if self.id.is_empty() {
+            &self.path
+        } else {
+            &self.id
+        }
+    }
+
+    /// This folder and every folder below it.
+    fn flatten(&self) -> Vec<&FolderNodeJson> {
+        std::iter::once(self)
+            .chain(self.children.iter().flat_map(FolderNodeJson::flatten))
+            .collect()
+    }
+
+    /// Whether this folder or any folder below it is in `selected`.
+    fn contains_selection(&self, selected: &[String]) -> bool {
+        selected.iter().any(|key| key == self.selection_key())
+            || self.children.iter().any(|child| child.contains_selection(selected))
+    }
+}
+
+/// `selected` with each path saved by an earlier version, which named every
+/// folder by its path, replaced by the id of the one folder at that path in
+/// `nodes`. Paths shared by several folders are kept, since they can't be
+/// told apart.
+fn migrate_path_selections(nodes: &[FolderNodeJson], selected: &[String]) -> Vec<String> {
+    let all: Vec<&FolderNodeJson> = nodes.iter().flat_map(FolderNodeJson::flatten).collect();
+    let mut migrated: Vec<String> = Vec::new();
+    for key in selected {
+        let key = key.as_str();
+        let key = if all.iter().any(|node| node.selection_key() == key) {
+            key
+        } else {
+            let mut at_path = all.iter().filter(|node| node.path == key);
+            match (at_path.next(), at_path.next()) {
+                (Some(node), None) => node.selection_key(),
+                _ => key,
+            }
+        };
+        if !migrated.iter().any(|k| k == key) {
+            migrated.push(key.to_string());
+        }
+    }
+    migrated
+}
+
 // ---------------------------------------------------------------------------
-// FolderNode — glib::Object subclass with name, path, selected properties
+// FolderNode — glib::Object subclass with id, name, path, selected properties
 // ---------------------------------------------------------------------------
 
 mod folder_node_imp {
@@ -42,9 +134,16 @@ mod folder_node_imp {
 
     #[derive(Default)]
     pub struct FolderNode {
+        pub id: RefCell<String>,
         pub name: RefCell<String>,
         pub path: RefCell<String>,
         pub selected: Cell<bool>,
+        /// How much of the folder is stored locally; `Unknown` until the
+        /// daemon has been asked.
+        pub hydration: Cell<HydrationState>,
+        /// People the folder is shared with; empty if it isn't shared or
+        /// the daemon has not been asked.
+        pub collaborators: RefCell<Vec<Collaborator>>,
         /// Serialised JSON children — kept for lazy tree model expansion.
         pub children_json: RefCell<Vec<FolderNodeJson>>,
     }
@@ -62,6 +161,9 @@ mod folder_node_imp {
             static PROPERTIES: OnceLock<Vec<glib::ParamSpec>> = OnceLock::new();
             PROPERTIES.get_or_init(|| {
                 vec![
+                    glib::ParamSpecString::builder("id")
+                        .default_value(Some(""))
+                        .build(),
                     glib::ParamSpecString::builder("name")
                         .default_value(Some(""))
                         .build(),
@@ -71,12 +173,24 @@ mod folder_node_imp {
                     glib::ParamSpecBoolean::builder("selected")
                         .default_value(false)
                         .build(),
+                    // The D-Bus name of the HydrationState, for bindings.
+                    glib::ParamSpecString::builder("hydration")
+                        .default_value(Some("unknown"))
+                        .build(),
+                    // Number of collaborators, for bindings.
+                    glib::ParamSpecUInt::builder("shared-count")
+                        .read_only()
+                        .build(),
                 ]
             })
         }
 
         fn set_property(&self, _id: usize, value: &glib::Value, pspec: &glib::ParamSpec) {
             match pspec.name() {
+                "id" => {
+                    let val: String = value.get().unwrap_or_default();
+                    *self.id.borrow_mut() = val;
+                }
                 "name" => {
                     let val: String = value.get().unwrap_or_default();
                     *self.name.borrow_mut() = val;
@@ -89,16 +203,23 @@ mod folder_node_imp {
                     let val: bool = value.get().unwrap_or(false);
                     self.selected.set(val);
                 }
-                _ => unimplemented!(),
+                "hydration" => {
+                    let val: String = value.get().unwrap_or_default();
+                    self.hydration.set(HydrationState::from_dbus(&val));
+                }
+                _ => unreachable!(),
             }
         }
 
         fn property(&self, _id: usize, pspec: &glib::ParamSpec) -> glib::Value {
             match pspec.name() {
+                "id" => self.id.borrow().to_value(),
                 "name" => self.name.borrow().to_value(),
                 "path" => self.path.borrow().to_value(),
                 "selected" => self.selected.get().to_value(),
-                _ => unimplemented!(),
+                "hydration" => self.hydration.get().as_dbus().to_value(),
+                "shared-count" => (self.collaborators.borrow().len() as u32).to_value(),
+                _ => unreachable!(),
             }
         }
     }
@@ -109,17 +230,29 @@ glib::wrapper! {
 }
 
 impl FolderNode {
-    pub fn new(name: &str, path: &str, selected: bool, children: Vec<FolderNodeJson>) -> Self {
+    pub fn new(json: &FolderNodeJson, selected: bool) -> Self {
         let obj: Self = glib::Object::builder()
-            .property("name", name)
-            .property("path", path)
+            .property("id", &json.id)
+            .property("name", &json.name)
+            .property("path", &json.path)
             .property("selected", selected)
             .build();
 
-        *obj.imp().children_json.borrow_mut() = children;
+        *obj.imp().children_json.borrow_mut() = json.children.clone();
         obj
     }
 
+    /// How the daemon's selection names this folder: its id, or its path if
+    /// it has none.
+    pub fn selection_key(&self) -> String {
+        let id = self.imp().id.borrow();
+        if id.is_empty() {
+            self.path()
+        } else {
+            id.clone()
+        }
+    }
+
     pub fn name(&self) -> String {
         self.imp().name.borrow().clone()
     }
@@ -137,6 +270,24 @@ impl FolderNode {
         self.notify("selected");
     }
 
+    pub fn hydration(&self) -> HydrationState {
+        self.imp().hydration.get()
+    }
+
+    pub fn set_hydration(&self, value: HydrationState) {
+        self.imp().hydration.set(value);
+        self.notify("hydration");
+    }
+
+    pub fn collaborators(&self) -> Vec<Collaborator> {
+        self.imp().collaborators.borrow().clone()
+    }
+
+    pub fn set_collaborators(&self, value: Vec<Collaborator>) {
+        *self.imp().collaborators.borrow_mut() = value;
+        self.notify("shared-count");
+    }
+
     pub fn children_json(&self) -> Vec<FolderNodeJson> {
         self.imp().children_json.borrow().clone()
     }
@@ -146,26 +297,176 @@ impl FolderNode {
 // FolderTree — gtk::Box subclass containing the tree view
 // ---------------------------------------------------------------------------
 
+/// Object data key under which each bound ListItem keeps the SignalHandlerId
+/// of its CheckButton's `toggled` handler.
+const TOGGLED_HANDLER_KEY: &str = "lnxdrive-toggled-handler";
+
+/// Object data key under which each bound ListItem keeps the glib::Bindings
+/// from its FolderNode's `selected`, `hydration` and `shared-count`
+/// properties to the row.
+const ROW_BINDINGS_KEY: &str = "lnxdrive-row-bindings";
+
+/// Badge icon name and tooltip for a folder's hydration state, given by its
+/// D-Bus name (the FolderNode `hydration` property).
+fn hydration_badge(state: &str) -> Option<(&'static str, String)> {
+    match HydrationState::from_dbus(state) {
+        HydrationState::Placeholder => Some((
+            "weather-overcast-symbolic",
+            gettext("Placeholder: online-only, downloaded when opened"),
+        )),
+        HydrationState::Partial => Some((
+            "weather-few-clouds-symbolic",
+            gettext("Partly downloaded"),
+        )),
+        HydrationState::Hydrated => Some((
+            "emblem-ok-symbolic",
+            gettext("Downloaded: available offline"),
+        )),
+        HydrationState::Unknown => None,
+    }
+}
+
+/// "Shared with N people" for a folder's collaborator count.
+fn shared_label(count: u32) -> String {
+    ngettext("Shared with {} person", "Shared with {} people", count)
+        .replace("{}", &count.to_string())
+}
+
+/// Popover listing the people `node` is shared with.
+fn collaborators_popover(node: &FolderNode) -> gtk4::Popover {
+    let collaborators = node.collaborators();
+    let content = gtk4::Box::builder()
+        .orientation(gtk4::Orientation::Vertical)
+        .spacing(6)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+    content.append(
+        &gtk4::Label::builder()
+            .label(&shared_label(collaborators.len() as u32))
+            .css_classes(["heading"])
+            .halign(gtk4::Align::Start)
+            .build(),
+    );
+    for person in &collaborators {
+        let label = if person.email.is_empty() {
+            person.name.clone()
+        } else {
+            format!("{} <{}>", person.name, person.email)
+        };
+        content.append(
+            &gtk4::Label::builder()
+                .label(&label)
+                .halign(gtk4::Align::Start)
+                .selectable(true)
+                .build(),
+        );
+    }
+    gtk4::Popover::builder().child(&content).build()
+}
+
+/// Return the CheckButton inside a row built by the factory's setup closure
+/// (ListItem -> TreeExpander -> Box -> CheckButton).
+fn row_check_button(list_item: &gtk4::ListItem) -> Option<gtk4::CheckButton> {
+    list_item
+        .child()
+        .and_downcast::<gtk4::TreeExpander>()?
+        .child()
+        .and_downcast::<gtk4::Box>()?
+        .first_child()
+        .and_downcast::<gtk4::CheckButton>()
+}
+
+/// Return the FolderNode currently bound to a list item, if any.
+fn list_item_node(list_item: &gtk4::ListItem) -> Option<FolderNode> {
+    list_item
+        .item()
+        .and_downcast::<gtk4::TreeListRow>()?
+        .item()
+        .and_downcast::<FolderNode>()
+}
+
+/// Deepest level "Expand All" will open. Guards against pathological trees
+/// where expanding everything would materialise an enormous row count.
+const MAX_EXPAND_DEPTH: u32 = 16;
+
 mod imp {
     use super::*;
     use gtk4::subclass::prelude::*;
 
     pub struct FolderTree {
         pub dbus_client: RefCell<Option<DbusClient>>,
+        /// Sync root whose folders this tree shows.
+        pub root_id: RefCell<String>,
+        /// Local folder of the sync root, which remote paths are joined to
+        /// for the Files interface. Read from the config's `sync_root` when
+        /// the root doesn't report one.
+        pub local_root: RefCell<String>,
         pub tree_model: RefCell<Option<gtk4::TreeListModel>>,
+        /// Filter behind "Show selected only"; passes every row while the
+        /// toggle is off.
+        pub selected_filter: RefCell<Option<gtk4::CustomFilter>>,
+        pub show_selected_only: Cell<bool>,
         pub root_store: RefCell<Option<gio::ListStore>>,
+        /// The top-level folders as last loaded, for matching selections
+        /// saved by path.
+        pub tree_nodes: RefCell<Vec<FolderNodeJson>>,
         pub list_view: RefCell<Option<gtk4::ListView>>,
+        /// "Documents › Work" path of the focused row, above the list.
+        pub breadcrumb: RefCell<Option<gtk4::Label>>,
+        /// Switches between the "loading", "list", "empty" and "error" views.
+        pub stack: RefCell<Option<gtk4::Stack>>,
+        /// Selection keys (see `FolderNode::selection_key`) of checked
+        /// folders.
         pub selected_folders: RefCell<Vec<String>>,
+        /// Selection the daemon last confirmed; the undo target of the next
+        /// save.
+        pub saved_folders: RefCell<Vec<String>>,
+        /// Whether the daemon's selection has been fetched, so an empty
+        /// `selected_folders` means "nothing selected" rather than "unknown".
+        pub selection_loaded: Cell<bool>,
+        /// "No folders selected" hint above the list.
+        pub empty_selection_hint: RefCell<Option<gtk4::Box>>,
+        /// Pending debounced save of the selection (500ms after the last toggle).
+        pub save_source: RefCell<Option<glib::SourceId>>,
+        /// Whether the account reports folder sharing (business accounts);
+        /// `None` until the account info has been fetched.
+        pub sharing_supported: Cell<Option<bool>>,
+        /// Compact row density; bound to the `compact-lists` setting.
+        pub compact: Cell<bool>,
+        /// Stage selection changes until Apply; bound to the
+        /// `apply-folder-changes` setting.
+        pub manual_apply: Cell<bool>,
+        /// "Unsaved changes" bar with Revert and Apply, shown while the
+        /// staged selection differs from the saved one.
+        pub unsaved_bar: RefCell<Option<gtk4::Box>>,
     }
 
     impl Default for FolderTree {
         fn default() -> Self {
             Self {
                 dbus_client: RefCell::new(None),
+                root_id: RefCell::new(String::new()),
+                local_root: RefCell::new(String::new()),
                 tree_model: RefCell::new(None),
+                selected_filter: RefCell::new(None),
+                show_selected_only: Cell::new(false),
                 root_store: RefCell::new(None),
+                tree_nodes: RefCell::new(Vec::new()),
                 list_view: RefCell::new(None),
+                breadcrumb: RefCell::new(None),
+                stack: RefCell::new(None),
                 selected_folders: RefCell::new(Vec::new()),
+                saved_folders: RefCell::new(Vec::new()),
+                selection_loaded: Cell::new(false),
+                empty_selection_hint: RefCell::new(None),
+                save_source: RefCell::new(None),
+                sharing_supported: Cell::new(None),
+                compact: Cell::new(false),
+                manual_apply: Cell::new(false),
+                unsaved_bar: RefCell::new(None),
             }
         }
     }
@@ -177,7 +478,52 @@ mod imp {
         type ParentType = gtk4::Box;
     }
 
-    impl ObjectImpl for FolderTree {}
+    impl ObjectImpl for FolderTree {
+        fn properties() -> &'static [glib::ParamSpec] {
+            use std::sync::OnceLock;
+            static PROPERTIES: OnceLock<Vec<glib::ParamSpec>> = OnceLock::new();
+            PROPERTIES.get_or_init(|| {
+                vec![
+                    glib::ParamSpecBoolean::builder("compact").build(),
+                    glib::ParamSpecBoolean::builder("manual-apply").build(),
+                ]
+            })
+        }
+
+        fn set_property(&self, _id: usize, value: &glib::Value, pspec: &glib::ParamSpec) {
+            match pspec.name() {
+                "compact" => {
+                    let compact: bool = value.get().unwrap_or(false);
+                    self.compact.set(compact);
+                    if let Some(ref view) = *self.list_view.borrow() {
+                        util::set_css_class(view, "compact", compact);
+                    }
+                }
+                "manual-apply" => {
+                    let manual: bool = value.get().unwrap_or(false);
+                    if manual == self.manual_apply.replace(manual) {
+                        return;
+                    }
+                    let obj = self.obj();
+                    if manual {
+                        obj.flush_pending_save();
+                    } else if obj.has_unsaved_changes() {
+                        obj.save_selected_folders(true);
+                    }
+                    obj.update_unsaved_bar();
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        fn property(&self, _id: usize, pspec: &glib::ParamSpec) -> glib::Value {
+            match pspec.name() {
+                "compact" => self.compact.get().to_value(),
+                "manual-apply" => self.manual_apply.get().to_value(),
+                _ => unreachable!(),
+            }
+        }
+    }
     impl WidgetImpl for FolderTree {}
     impl BoxImpl for FolderTree {}
 }
@@ -190,11 +536,14 @@ glib::wrapper! {
 }
 
 impl FolderTree {
-    pub fn new(dbus_client: Option<&DbusClient>) -> Self {
+    pub fn new(dbus_client: Option<&DbusClient>, root: &SyncRoot) -> Self {
         let tree: Self = glib::Object::builder()
             .property("orientation", gtk4::Orientation::Vertical)
             .build();
 
+        tree.imp().root_id.replace(root.id.clone());
+        tree.imp().local_root.replace(root.local_path.clone());
+
         if let Some(client) = dbus_client {
             tree.imp()
                 .dbus_client
@@ -202,12 +551,33 @@ impl FolderTree {
         }
 
         tree.build_ui();
+        AppSettings::new().bind(keys::COMPACT_LISTS, &tree, "compact");
+        AppSettings::new().bind(keys::APPLY_FOLDER_CHANGES, &tree, "manual-apply");
         tree.load_remote_tree();
         tree.load_selected_folders();
 
         tree
     }
 
+    /// Fetch the remote folders and the saved selection again, starting from
+    /// an empty tree. A selection change still waiting to be saved is saved
+    /// first; changes staged for Apply are dropped.
+    pub fn reload(&self) {
+        self.flush_pending_save();
+
+        let imp = self.imp();
+        if let Some(store) = imp.root_store.borrow().clone() {
+            store.remove_all();
+        }
+        imp.selected_folders.borrow_mut().clear();
+        imp.saved_folders.borrow_mut().clear();
+        imp.selection_loaded.set(false);
+        imp.sharing_supported.set(None);
+
+        self.load_remote_tree();
+        self.load_selected_folders();
+    }
+
     fn build_ui(&self) {
         let imp = self.imp();
 
@@ -217,6 +587,7 @@ impl FolderTree {
 
         // Tree list model: the create_model closure returns a child ListStore
         // when a row is expanded, populated from the FolderNode's children_json.
+        let tree_weak = self.downgrade();
         let tree_model = gtk4::TreeListModel::new(
             root_store.clone(),
             false,  // passthrough = false (we want TreeListRow wrappers)
@@ -234,13 +605,12 @@ impl FolderTree {
                 let child_store = gio::ListStore::new::<FolderNode>();
                 let parent_selected = node.selected();
                 for child in &children {
-                    let child_node = FolderNode::new(
-                        &child.name,
-                        &child.path,
-                        parent_selected,
-                        child.children.clone(),
-                    );
-                    child_store.append(&child_node);
+                    child_store.append(&FolderNode::new(child, parent_selected));
+                }
+
+                if let Some(tree) = tree_weak.upgrade() {
+                    tree.load_hydration_states(&child_store);
+                    tree.load_shared_info(&child_store);
                 }
 
                 Some(child_store.upcast())
@@ -248,13 +618,32 @@ impl FolderTree {
         );
         imp.tree_model.replace(Some(tree_model.clone()));
 
+        let tree_weak = self.downgrade();
+        let selected_filter = gtk4::CustomFilter::new(move |item| {
+            let Some(tree) = tree_weak.upgrade() else {
+                return true;
+            };
+            if !tree.imp().show_selected_only.get() {
+                return true;
+            }
+            item.downcast_ref::<gtk4::TreeListRow>()
+                .and_then(|row| row.item())
+                .and_downcast::<FolderNode>()
+                .is_some_and(|node| tree.is_selected_or_ancestor(&node))
+        });
+        let filters = gtk4::EveryFilter::new();
+        filters.append(selected_filter.clone());
+        imp.selected_filter.replace(Some(selected_filter));
+        let filter_model = gtk4::FilterListModel::new(Some(tree_model), Some(filters));
+
         // Selection model — NoSelection because toggling is via CheckButton.
-        let selection_model = gtk4::NoSelection::new(Some(tree_model));
+        let selection_model = gtk4::NoSelection::new(Some(filter_model));
 
         // Factory for list items.
         let factory = gtk4::SignalListItemFactory::new();
 
-        factory.connect_setup(|_factory, list_item| {
+        let tree_widget = self.downgrade();
+        factory.connect_setup(move |_factory, list_item| {
             let list_item = list_item
                 .downcast_ref::<gtk4::ListItem>()
                 .expect("ListItem expected");
@@ -270,12 +659,83 @@ impl FolderTree {
                 .halign(gtk4::Align::Start)
                 .hexpand(true)
                 .build();
+            let badge = gtk4::Image::builder()
+                .css_classes(["dim-label"])
+                .visible(false)
+                .build();
+
+            // Screen readers announce the folder name when the checkbox
+            // gets focus instead of an unnamed "check box".
+            check.update_relation(&[gtk4::accessible::Relation::LabelledBy(&[
+                label.upcast_ref(),
+            ])]);
+
+            // The popover is built when opened, from the node bound at
+            // that moment, since rows are recycled.
+            let share_button = gtk4::MenuButton::builder()
+                .icon_name("system-users-symbolic")
+                .css_classes(["flat"])
+                .valign(gtk4::Align::Center)
+                .visible(false)
+                .build();
+            let item_weak = list_item.downgrade();
+            share_button.set_create_popup_func(move |button| {
+                if let Some(node) = item_weak.upgrade().as_ref().and_then(list_item_node) {
+                    button.set_popover(Some(&collaborators_popover(&node)));
+                }
+            });
 
             hbox.append(&check);
             hbox.append(&label);
+            hbox.append(&badge);
+            hbox.append(&share_button);
 
             expander.set_child(Some(&hbox));
             list_item.set_child(Some(&expander));
+
+            // Context menu on right-click and long-press. As with the
+            // breadcrumb, the node is looked up when the menu opens.
+            let secondary = gtk4::GestureClick::builder()
+                .button(gtk4::gdk::BUTTON_SECONDARY)
+                .build();
+            let tree_ref = tree_widget.clone();
+            let item_weak = list_item.downgrade();
+            secondary.connect_pressed(move |gesture, _, x, y| {
+                let node = item_weak.upgrade().as_ref().and_then(list_item_node);
+                if let (Some(tree), Some(node), Some(widget)) =
+                    (tree_ref.upgrade(), node, gesture.widget())
+                {
+                    gesture.set_state(gtk4::EventSequenceState::Claimed);
+                    tree.show_folder_menu(&widget, &node, x, y);
+                }
+            });
+            expander.add_controller(secondary);
+
+            let long_press = gtk4::GestureLongPress::new();
+            let tree_ref = tree_widget.clone();
+            let item_weak = list_item.downgrade();
+            long_press.connect_pressed(move |gesture, x, y| {
+                let node = item_weak.upgrade().as_ref().and_then(list_item_node);
+                if let (Some(tree), Some(node), Some(widget)) =
+                    (tree_ref.upgrade(), node, gesture.widget())
+                {
+                    tree.show_folder_menu(&widget, &node, x, y);
+                }
+            });
+            expander.add_controller(long_press);
+
+            // Update the breadcrumb when focus moves into this row. The node
+            // is looked up on focus, since rows are recycled.
+            let focus = gtk4::EventControllerFocus::new();
+            let tree_ref = tree_widget.clone();
+            let item_weak = list_item.downgrade();
+            focus.connect_enter(move |_| {
+                let node = item_weak.upgrade().as_ref().and_then(list_item_node);
+                if let (Some(tree), Some(node)) = (tree_ref.upgrade(), node) {
+                    tree.set_breadcrumb(&node.path());
+                }
+            });
+            expander.add_controller(focus);
         });
 
         let tree_widget = self.clone();
@@ -317,23 +777,99 @@ impl FolderTree {
                 .and_downcast::<gtk4::Label>()
                 .expect("Second child must be Label");
 
+            let badge = label
+                .next_sibling()
+                .and_downcast::<gtk4::Image>()
+                .expect("Third child must be Image");
+
+            let share_button = badge
+                .next_sibling()
+                .and_downcast::<gtk4::MenuButton>()
+                .expect("Fourth child must be MenuButton");
+
             label.set_label(&node.name());
-            check.set_active(node.selected());
+            expander.set_tooltip_text(Some(&node.path()));
+
+            // Keep the checkbox in sync with the node so programmatic changes
+            // (select all, applying the daemon's selection) show up live.
+            let bindings = vec![
+                node.bind_property("selected", &check, "active")
+                    .sync_create()
+                    .build(),
+                node.bind_property("hydration", &badge, "icon-name")
+                    .transform_to(|_, state: String| {
+                        Some(hydration_badge(&state).map(|(icon, _)| icon.to_string()))
+                    })
+                    .sync_create()
+                    .build(),
+                node.bind_property("hydration", &badge, "tooltip-text")
+                    .transform_to(|_, state: String| {
+                        Some(hydration_badge(&state).map(|(_, tip)| tip))
+                    })
+                    .sync_create()
+                    .build(),
+                node.bind_property("hydration", &badge, "visible")
+                    .transform_to(|_, state: String| Some(hydration_badge(&state).is_some()))
+                    .sync_create()
+                    .build(),
+                node.bind_property("shared-count", &share_button, "visible")
+                    .transform_to(|_, count: u32| Some(count > 0))
+                    .sync_create()
+                    .build(),
+                node.bind_property("shared-count", &share_button, "tooltip-text")
+                    .transform_to(|_, count: u32| Some(shared_label(count)))
+                    .sync_create()
+                    .build(),
+            ];
+            // SAFETY: the key is private to this module and always holds a
+            // Vec<glib::Binding>.
+            unsafe {
+                list_item.set_data(ROW_BINDINGS_KEY, bindings);
+            }
 
-            // Connect checkbox toggle.
+            // Connect checkbox toggle. The handler ID is stored on the list
+            // item so unbind can disconnect it before the row is recycled.
+            // The node is looked up from the list item when the toggle fires
+            // rather than captured here, so a recycled row can never mutate
+            // the folder it displayed before.
             let tree_ref = tree_widget.clone();
-            let node_ref = node.clone();
-            check.connect_toggled(move |btn| {
+            let item_weak = list_item.downgrade();
+            let handler_id = check.connect_toggled(move |btn| {
+                let Some(node) = item_weak.upgrade().as_ref().and_then(list_item_node) else {
+                    return;
+                };
                 let new_val = btn.is_active();
-                node_ref.set_selected(new_val);
+                if node.selected() == new_val {
+                    return;
+                }
+                node.set_selected(new_val);
                 tree_ref.on_selection_changed();
             });
+            // SAFETY: the key is private to this module and always holds a
+            // SignalHandlerId.
+            unsafe {
+                list_item.set_data(TOGGLED_HANDLER_KEY, handler_id);
+            }
         });
 
         factory.connect_unbind(|_factory, list_item| {
-            // Clean up: we don't store signal handler IDs because the
-            // CheckButton is recreated on each bind cycle.
-            let _ = list_item;
+            let list_item = list_item
+                .downcast_ref::<gtk4::ListItem>()
+                .expect("ListItem expected");
+
+            // The factory reuses row widgets, so drop this bind's handler to
+            // keep it from firing against the next node shown in the row.
+            // SAFETY: see connect_bind.
+            let handler_id =
+                unsafe { list_item.steal_data::<glib::SignalHandlerId>(TOGGLED_HANDLER_KEY) };
+            if let (Some(handler_id), Some(check)) = (handler_id, row_check_button(list_item)) {
+                check.disconnect(handler_id);
+            }
+            let bindings =
+                unsafe { list_item.steal_data::<Vec<glib::Binding>>(ROW_BINDINGS_KEY) };
+            for binding in bindings.into_iter().flatten() {
+                binding.unbind();
+            }
         });
 
         // List view.
@@ -342,9 +878,36 @@ impl FolderTree {
             .factory(&factory)
             .build();
         list_view.add_css_class("boxed-list");
+        list_view.update_property(&[gtk4::accessible::Property::Label(&gettext(
+            "Remote folders",
+        ))]);
 
         imp.list_view.replace(Some(list_view.clone()));
 
+        // "folder.pin" / "folder.unpin", targeted at a folder's selection key
+        // (see `FolderNode::selection_key`); used by the row context menu.
+        let actions = gio::SimpleActionGroup::new();
+        for (name, pin) in [("pin", true), ("unpin", false)] {
+            let action = gio::SimpleAction::new(name, Some(glib::VariantTy::STRING));
+            let tree = self.downgrade();
+            action.connect_activate(move |_, param| {
+                let Some(tree) = tree.upgrade() else {
+                    return;
+                };
+                let Some(node) = param.and_then(|p| p.str()).and_then(|key| tree.find_node(key))
+                else {
+                    return;
+                };
+                if pin {
+                    tree.set_folder_pinned(&node, true);
+                } else {
+                    tree.confirm_unpin(&node);
+                }
+            });
+            actions.add_action(&action);
+        }
+        self.insert_action_group("folder", Some(&actions));
+
         // Scrolled window.
         let scrolled = gtk4::ScrolledWindow::builder()
             .hscrollbar_policy(gtk4::PolicyType::Never)
@@ -354,7 +917,594 @@ impl FolderTree {
             .build();
         scrolled.set_child(Some(&list_view));
 
-        self.append(&scrolled);
+        // Placeholder shown when the daemon returns a valid but empty tree.
+        let empty_page = adw::StatusPage::builder()
+            .icon_name("folder-symbolic")
+            .title(&gettext("No Folders"))
+            .description(&gettext("Your OneDrive has no folders to choose from."))
+            .css_classes(["compact"])
+            .build();
+
+        // Placeholder shown when the tree could not be loaded or parsed.
+        let retry_button = gtk4::Button::builder()
+            .label(&gettext("Retry"))
+            .halign(gtk4::Align::Center)
+            .css_classes(["pill"])
+            .build();
+        let error_page = adw::StatusPage::builder()
+            .icon_name("dialog-warning-symbolic")
+            .title(&gettext("Couldn't Load Folder List"))
+            .css_classes(["compact"])
+            .child(&retry_button)
+            .build();
+
+        let tree = self.clone();
+        retry_button.connect_clicked(move |_| {
+            tree.load_remote_tree();
+        });
+
+        // Spinner shown while the tree request is in flight.
+        let spinner = gtk4::Spinner::builder()
+            .spinning(true)
+            .width_request(32)
+            .height_request(32)
+            .halign(gtk4::Align::Center)
+            .valign(gtk4::Align::Center)
+            .build();
+        let loading_label = gtk4::Label::builder()
+            .label(&gettext("Loading folders..."))
+            .css_classes(["dim-label"])
+            .build();
+        let loading_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .valign(gtk4::Align::Center)
+            .margin_top(24)
+            .margin_bottom(24)
+            .build();
+        loading_box.append(&spinner);
+        loading_box.append(&loading_label);
+
+        let breadcrumb = gtk4::Label::builder()
+            .halign(gtk4::Align::Start)
+            .ellipsize(gtk4::pango::EllipsizeMode::Start)
+            .css_classes(["dim-label", "caption"])
+            .margin_bottom(6)
+            .visible(false)
+            .build();
+        imp.breadcrumb.replace(Some(breadcrumb.clone()));
+
+        let hint_label = gtk4::Label::builder()
+            .label(&gettext("No folders selected — nothing will sync. Select folders below."))
+            .halign(gtk4::Align::Start)
+            .wrap(true)
+            .xalign(0.0)
+            .build();
+        let empty_selection_hint = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .margin_bottom(6)
+            .css_classes(["warning"])
+            .visible(false)
+            .build();
+        empty_selection_hint.append(&gtk4::Image::from_icon_name("dialog-warning-symbolic"));
+        empty_selection_hint.append(&hint_label);
+        imp.empty_selection_hint.replace(Some(empty_selection_hint.clone()));
+
+        let unsaved_label = gtk4::Label::builder()
+            .label(&gettext("Unsaved changes"))
+            .halign(gtk4::Align::Start)
+            .hexpand(true)
+            .xalign(0.0)
+            .css_classes(["dim-label"])
+            .build();
+        let revert_button = gtk4::Button::builder()
+            .label(&gettext("Revert"))
+            .build();
+        let apply_button = gtk4::Button::builder()
+            .label(&gettext("Apply"))
+            .css_classes(["suggested-action"])
+            .build();
+        let unsaved_bar = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .margin_bottom(6)
+            .visible(false)
+            .build();
+        unsaved_bar.append(&unsaved_label);
+        unsaved_bar.append(&revert_button);
+        unsaved_bar.append(&apply_button);
+        imp.unsaved_bar.replace(Some(unsaved_bar.clone()));
+
+        let tree = self.downgrade();
+        revert_button.connect_clicked(move |_| {
+            if let Some(tree) = tree.upgrade() {
+                tree.revert_staged_selection();
+            }
+        });
+        let tree = self.downgrade();
+        apply_button.connect_clicked(move |_| {
+            if let Some(tree) = tree.upgrade() {
+                tree.save_selected_folders(true);
+            }
+        });
+
+        let selected_only_check = gtk4::CheckButton::builder()
+            .label(&gettext("Show selected only"))
+            .halign(gtk4::Align::Start)
+            .margin_bottom(6)
+            .build();
+        let tree = self.downgrade();
+        selected_only_check.connect_toggled(move |check| {
+            if let Some(tree) = tree.upgrade() {
+                tree.set_show_selected_only(check.is_active());
+            }
+        });
+
+        let stack = gtk4::Stack::builder()
+            .transition_type(gtk4::StackTransitionType::Crossfade)
+            .build();
+        stack.add_named(&loading_box, Some("loading"));
+        stack.add_named(&scrolled, Some("list"));
+        stack.add_named(&empty_page, Some("empty"));
+        stack.add_named(&error_page, Some("error"));
+        imp.stack.replace(Some(stack.clone()));
+
+        self.append(&empty_selection_hint);
+        self.append(&unsaved_bar);
+        self.append(&selected_only_check);
+        self.append(&breadcrumb);
+        self.append(&stack);
+    }
+
+    /// Show the "No folders selected" hint while the loaded tree has nothing
+    /// checked.
+    fn update_empty_selection_hint(&self) {
+        let imp = self.imp();
+        let showing_list = imp
+            .stack
+            .borrow()
+            .as_ref()
+            .and_then(|stack| stack.visible_child_name())
+            .is_some_and(|name| name == "list");
+        let visible = showing_list
+            && imp.selection_loaded.get()
+            && imp.selected_folders.borrow().is_empty();
+        if let Some(ref hint) = *imp.empty_selection_hint.borrow() {
+            hint.set_visible(visible);
+        }
+    }
+
+    /// Whether the selection in the tree differs from the one the daemon
+    /// last confirmed. Order doesn't matter: the tree lists paths in its own
+    /// order, the daemon in whatever order it stored them.
+    fn has_unsaved_changes(&self) -> bool {
+        let imp = self.imp();
+        if !imp.selection_loaded.get() {
+            return false;
+        }
+        let mut selected = imp.selected_folders.borrow().clone();
+        let mut saved = imp.saved_folders.borrow().clone();
+        selected.sort();
+        saved.sort();
+        selected != saved
+    }
+
+    /// Show the Revert/Apply bar while changes are staged.
+    fn update_unsaved_bar(&self) {
+        let visible = self.imp().manual_apply.get() && self.has_unsaved_changes();
+        if let Some(ref bar) = *self.imp().unsaved_bar.borrow() {
+            bar.set_visible(visible);
+        }
+    }
+
+    /// Drop the staged changes and show the saved selection again.
+    fn revert_staged_selection(&self) {
+        let imp = self.imp();
+        let saved = imp.saved_folders.borrow().clone();
+        *imp.selected_folders.borrow_mut() = saved;
+        self.apply_selections();
+    }
+
+    /// Narrow the list to selected folders and their ancestors, or show
+    /// every folder again.
+    fn set_show_selected_only(&self, enabled: bool) {
+        let imp = self.imp();
+        imp.show_selected_only.set(enabled);
+        if let Some(ref filter) = *imp.selected_filter.borrow() {
+            filter.changed(if enabled {
+                gtk4::FilterChange::MoreStrict
+            } else {
+                gtk4::FilterChange::LessStrict
+            });
+        }
+    }
+
+    /// Whether `node` is checked or contains a checked folder. Descendants
+    /// are found through the node's subtree, not its path, since paths are
+    /// not unique.
+    fn is_selected_or_ancestor(&self, node: &FolderNode) -> bool {
+        if node.selected() {
+            return true;
+        }
+        let selected = self.imp().selected_folders.borrow();
+        node.imp()
+            .children_json
+            .borrow()
+            .iter()
+            .any(|child| child.contains_selection(&selected))
+    }
+
+    /// Re-run "Show selected only" after the selection changed.
+    fn refilter_selected(&self) {
+        let imp = self.imp();
+        if !imp.show_selected_only.get() {
+            return;
+        }
+        if let Some(ref filter) = *imp.selected_filter.borrow() {
+            filter.changed(gtk4::FilterChange::Different);
+        }
+    }
+
+    /// Show `path` ("/Documents/Work") as "Documents › Work" above the list;
+    /// an empty path hides the breadcrumb.
+    fn set_breadcrumb(&self, path: &str) {
+        if let Some(ref label) = *self.imp().breadcrumb.borrow() {
+            let crumbs: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+            label.set_label(&crumbs.join(" › "));
+            label.set_visible(!crumbs.is_empty());
+        }
+    }
+
+    /// Pop up the pin/unpin menu for `node` at (`x`, `y`) in `widget`. The
+    /// entry matching the folder's current state is left out.
+    fn show_folder_menu(&self, widget: &gtk4::Widget, node: &FolderNode, x: f64, y: f64) {
+        let key = node.selection_key();
+        let hydration = node.hydration();
+
+        let menu = gio::Menu::new();
+        if hydration != HydrationState::Hydrated {
+            let item = gio::MenuItem::new(Some(&gettext("Keep Available Offline")), None);
+            item.set_action_and_target_value(Some("folder.pin"), Some(&key.to_variant()));
+            menu.append_item(&item);
+        }
+        if hydration != HydrationState::Placeholder {
+            let item = gio::MenuItem::new(Some(&gettext("Free Up Space")), None);
+            item.set_action_and_target_value(Some("folder.unpin"), Some(&key.to_variant()));
+            menu.append_item(&item);
+        }
+
+        let popover = gtk4::PopoverMenu::from_model(Some(&menu));
+        popover.set_parent(widget);
+        popover.set_has_arrow(false);
+        popover.set_halign(gtk4::Align::Start);
+        popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+        // Rows are recycled, so the popover must not outlive this opening.
+        // Unparent once the chosen action has been dispatched.
+        popover.connect_closed(|popover| {
+            let popover = popover.clone();
+            glib::idle_add_local_once(move || popover.unparent());
+        });
+        popover.popup();
+    }
+
+    /// Absolute local path of the remote folder `path`, or `None` while the
+    /// sync root's local folder is unknown.
+    fn local_path(&self, path: &str) -> Option<String> {
+        let root = self.imp().local_root.borrow();
+        if root.is_empty() {
+            return None;
+        }
+        let local = std::path::Path::new(root.as_str()).join(path.trim_start_matches('/'));
+        Some(local.display().to_string())
+    }
+
+    /// Fill in `local_root` from the config's `sync_root` if the sync root
+    /// didn't report a local folder.
+    async fn resolve_local_root(&self, client: &DbusClient) {
+        if !self.imp().local_root.borrow().is_empty() {
+            return;
+        }
+        match client.get_config().await {
+            Ok(yaml) => {
+                if let Some(root) = config::top_level_value(&yaml, "sync_root") {
+                    self.imp().local_root.replace(root);
+                }
+            }
+            Err(e) => eprintln!("Could not load config: {}", e),
+        }
+    }
+
+    /// Ask the daemon how much of each folder in `store` is downloaded and
+    /// update their badges.
+    fn load_hydration_states(&self, store: &gio::ListStore) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let nodes: Vec<FolderNode> = (0..store.n_items())
+            .filter_map(|i| store.item(i).and_downcast::<FolderNode>())
+            .collect();
+        if nodes.is_empty() {
+            return;
+        }
+
+        let tree = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            tree.resolve_local_root(&client).await;
+
+            let local_paths: Vec<(FolderNode, String)> = nodes
+                .into_iter()
+                .filter_map(|node| tree.local_path(&node.path()).map(|local| (node, local)))
+                .collect();
+            if local_paths.is_empty() {
+                return;
+            }
+
+            let paths: Vec<String> = local_paths.iter().map(|(_, p)| p.clone()).collect();
+            match client.get_hydration_states(&paths).await {
+                Ok(states) => {
+                    for (node, local) in &local_paths {
+                        if let Some(state) = states.get(local) {
+                            node.set_hydration(*state);
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Could not load folder hydration states: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Whether the account reports folder sharing. Only business accounts
+    /// do; the answer is cached for the tree's lifetime.
+    async fn sharing_supported(&self, client: &DbusClient) -> bool {
+        if let Some(supported) = self.imp().sharing_supported.get() {
+            return supported;
+        }
+        let supported = client
+            .get_account_info()
+            .await
+            .map(|info| info.is_business())
+            .unwrap_or(false);
+        self.imp().sharing_supported.set(Some(supported));
+        supported
+    }
+
+    /// Ask the daemon which folders in `store` are shared and with whom, and
+    /// show the sharing button on those rows.
+    fn load_shared_info(&self, store: &gio::ListStore) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let nodes: Vec<FolderNode> = (0..store.n_items())
+            .filter_map(|i| store.item(i).and_downcast::<FolderNode>())
+            .collect();
+        if nodes.is_empty() {
+            return;
+        }
+
+        let tree = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            if !tree.sharing_supported(&client).await {
+                return;
+            }
+            tree.resolve_local_root(&client).await;
+
+            for node in nodes {
+                let Some(local) = tree.local_path(&node.path()) else {
+                    continue;
+                };
+                match client.get_shared_info(&local).await {
+                    Ok(info) if info.shared => node.set_collaborators(info.collaborators),
+                    Ok(_) => {}
+                    Err(e) => {
+                        // Older daemons lack the method; stop asking.
+                        eprintln!("Could not load folder sharing info: {}", e);
+                        tree.imp().sharing_supported.set(Some(false));
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    /// The materialised FolderNode whose selection key is `key`, if its row
+    /// exists.
+    fn find_node(&self, key: &str) -> Option<FolderNode> {
+        let model = self.imp().tree_model.borrow().clone()?;
+        (0..model.n_items())
+            .filter_map(|i| model.row(i).and_then(|row| row.item()).and_downcast::<FolderNode>())
+            .find(|node| node.selection_key() == key)
+    }
+
+    /// Ask before making the folder `node` online-only, with the amount of
+    /// disk space that frees. Without a size from the daemon the dialog just
+    /// omits it.
+    fn confirm_unpin(&self, node: &FolderNode) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let node = node.clone();
+        let tree = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            tree.resolve_local_root(&client).await;
+            let size = match tree.local_path(&node.path()) {
+                Some(local) => match client.get_folder_sizes(std::slice::from_ref(&local)).await {
+                    Ok(sizes) => sizes.get(&local).copied(),
+                    Err(e) => {
+                        eprintln!("Could not get size of {}: {}", local, e);
+                        None
+                    }
+                },
+                None => None,
+            };
+
+            let name = node.name();
+            let heading = match size {
+                Some(bytes) if bytes > 0 => {
+                    gettext("Free Up {}?").replace("{}", &util::format_size(bytes))
+                }
+                _ => gettext("Free Up Space?"),
+            };
+
+            let confirm = util::confirmation_dialog(
+                &heading,
+                &gettext("The files in {} will be removed from this computer and stay available online. They are downloaded again when opened.")
+                    .replace("{}", &name),
+            );
+            confirm.add_response("free", &gettext("Free Up Space"));
+            confirm.set_response_appearance("free", adw::ResponseAppearance::Destructive);
+
+            let tree_confirm = tree.clone();
+            confirm.connect_response(None, move |_dialog, response| {
+                if response == "free" {
+                    tree_confirm.set_folder_pinned(&node, false);
+                }
+            });
+
+            adw::prelude::AdwDialogExt::present(&confirm, Some(tree.upcast_ref::<gtk4::Widget>()));
+        });
+    }
+
+    /// Pin (download and keep local) or unpin (make online-only) the
+    /// contents of the folder `node`.
+    fn set_folder_pinned(&self, node: &FolderNode, pin: bool) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let node = node.clone();
+        let tree = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            tree.resolve_local_root(&client).await;
+            let Some(local) = tree.local_path(&node.path()) else {
+                tree.show_toast(&adw::Toast::new(&gettext(
+                    "The sync folder for these files is not known",
+                )));
+                return;
+            };
+
+            let result = if pin {
+                client.pin_path(&local).await
+            } else {
+                client.unpin_path(&local).await
+            };
+
+            let name = node.name();
+            match result {
+                Ok(()) => {
+                    node.set_hydration(if pin {
+                        HydrationState::Hydrated
+                    } else {
+                        HydrationState::Placeholder
+                    });
+                    let message = if pin {
+                        gettext("{} is available offline")
+                    } else {
+                        gettext("{} is now online-only")
+                    };
+                    tree.show_toast(&adw::Toast::new(&message.replace("{}", &name)));
+                }
+                Err(e) => {
+                    eprintln!("Could not change availability of {}: {}", local, e);
+                    let message = if pin {
+                        gettext("Could not make {} available offline")
+                    } else {
+                        gettext("Could not free up space for {}")
+                    };
+                    tree.show_toast(&adw::Toast::new(&message.replace("{}", &name)));
+                }
+            }
+        });
+    }
+
+    /// Check or uncheck every folder row currently in the tree, then save
+    /// the selection once. Only the top-level folders end up in the saved
+    /// selection (see `collect_selected`); their descendants follow them, and
+    /// children expanded later inherit the state from their parent.
+    pub fn set_all_selected(&self, selected: bool) {
+        let model = match self.imp().tree_model.borrow().clone() {
+            Some(m) => m,
+            None => return,
+        };
+
+        for i in 0..model.n_items() {
+            if let Some(node) = model
+                .row(i)
+                .and_then(|row| row.item())
+                .and_downcast::<FolderNode>()
+            {
+                node.set_selected(selected);
+            }
+        }
+
+        self.on_selection_changed();
+    }
+
+    /// Expand every row. Expansion is lazy, so newly revealed children are
+    /// expanded on subsequent passes until no expandable rows remain or
+    /// `MAX_EXPAND_DEPTH` is reached.
+    pub fn expand_all(&self) {
+        let model = match self.imp().tree_model.borrow().clone() {
+            Some(m) => m,
+            None => return,
+        };
+
+        for _ in 0..=MAX_EXPAND_DEPTH {
+            let mut changed = false;
+            // n_items() grows as rows expand, so re-read it every iteration.
+            let mut i = 0;
+            while i < model.n_items() {
+                if let Some(row) = model.row(i) {
+                    if row.is_expandable() && !row.is_expanded() && row.depth() < MAX_EXPAND_DEPTH {
+                        row.set_expanded(true);
+                        changed = true;
+                    }
+                }
+                i += 1;
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Collapse every expanded row.
+    pub fn collapse_all(&self) {
+        let model = match self.imp().tree_model.borrow().clone() {
+            Some(m) => m,
+            None => return,
+        };
+
+        // Collapsing a row removes its descendants, so n_items() shrinks.
+        let mut i = 0;
+        while i < model.n_items() {
+            if let Some(row) = model.row(i) {
+                if row.is_expanded() {
+                    row.set_expanded(false);
+                }
+            }
+            i += 1;
+        }
+    }
+
+    /// Switch the visible view ("loading", "list", "empty" or "error").
+    fn set_view(&self, name: &str) {
+        if let Some(ref stack) = *self.imp().stack.borrow() {
+            stack.set_visible_child_name(name);
+        }
+        if name != "list" {
+            self.set_breadcrumb("");
+        }
+        self.update_empty_selection_hint();
     }
 
     /// Fetch the remote folder tree JSON from the daemon and populate the root store.
@@ -364,14 +1514,29 @@ impl FolderTree {
             None => return,
         };
 
+        self.set_view("loading");
+
+        let root_id = self.imp().root_id.borrow().clone();
         let tree = self.clone();
         glib::MainContext::default().spawn_local(async move {
-            match client.get_remote_folder_tree().await {
-                Ok(json) => {
-                    tree.populate_from_json(&json);
-                }
+            match client.get_remote_folder_tree(&root_id).await {
+                Ok(json) => match tree.populate_from_json(&json) {
+                    Ok(0) => tree.set_view("empty"),
+                    Ok(_) => {
+                        tree.set_view("list");
+                        if let Some(store) = tree.imp().root_store.borrow().clone() {
+                            tree.load_hydration_states(&store);
+                            tree.load_shared_info(&store);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Could not parse remote folder tree: {}", e);
+                        tree.set_view("error");
+                    }
+                },
                 Err(e) => {
                     eprintln!("Could not load remote folder tree: {}", e);
+                    tree.set_view("error");
                 }
             }
         });
@@ -385,10 +1550,13 @@ impl FolderTree {
             None => return,
         };
 
+        let root_id = self.imp().root_id.borrow().clone();
         let tree = self.clone();
         glib::MainContext::default().spawn_local(async move {
-            match client.get_selected_folders().await {
+            match client.get_selected_folders(&root_id).await {
                 Ok(folders) => {
+                    tree.imp().selection_loaded.set(true);
+                    *tree.imp().saved_folders.borrow_mut() = folders.clone();
                     *tree.imp().selected_folders.borrow_mut() = folders;
                     // Re-apply selections after the tree has been populated.
                     tree.apply_selections();
@@ -401,33 +1569,35 @@ impl FolderTree {
     }
 
     /// Parse the JSON folder tree and populate the root ListStore.
-    fn populate_from_json(&self, json: &str) {
+    /// Returns the number of top-level folders, or the parse error if the
+    /// JSON is malformed (in which case the store is left untouched).
+    fn populate_from_json(&self, json: &str) -> Result<u32, serde_json::Error> {
         let imp = self.imp();
 
+        // The JSON may be a single root object or an array of roots.
+        let nodes: Vec<FolderNodeJson> = if json.trim_start().starts_with('[') {
+            serde_json::from_str(json)?
+        } else {
+            serde_json::from_str::<FolderNodeJson>(json)?.children
+        };
+
         let root_store = match imp.root_store.borrow().clone() {
             Some(s) => s,
-            None => return,
+            None => return Ok(0),
         };
 
         root_store.remove_all();
 
-        // The JSON may be a single root object or an array of roots.
-        let nodes: Vec<FolderNodeJson> = if json.trim_start().starts_with('[') {
-            serde_json::from_str(json).unwrap_or_default()
-        } else {
-            match serde_json::from_str::<FolderNodeJson>(json) {
-                Ok(root) => root.children,
-                Err(_) => Vec::new(),
-            }
-        };
-
+        imp.tree_nodes.replace(nodes.clone());
+        self.migrate_saved_paths();
         let selected = imp.selected_folders.borrow().clone();
         for node in &nodes {
-            let is_selected = selected.iter().any(|p| p == &node.path);
-            let folder_node =
-                FolderNode::new(&node.name, &node.path, is_selected, node.children.clone());
+            let folder_node = FolderNode::new(node, false);
+            folder_node.set_selected(selected.contains(&folder_node.selection_key()));
             root_store.append(&folder_node);
         }
+
+        Ok(root_store.n_items())
     }
 
     /// Walk the root store and mark nodes whose path is in the selected list.
@@ -437,54 +1607,169 @@ impl FolderTree {
             Some(s) => s,
             None => return,
         };
+        self.migrate_saved_paths();
         let selected = imp.selected_folders.borrow().clone();
 
         for i in 0..store.n_items() {
             if let Some(item) = store.item(i) {
                 if let Some(node) = item.downcast_ref::<FolderNode>() {
-                    let is_selected = selected.iter().any(|p| p == &node.path());
-                    node.set_selected(is_selected);
+                    node.set_selected(selected.contains(&node.selection_key()));
                 }
             }
         }
+        self.refilter_selected();
+        self.update_empty_selection_hint();
+        self.update_unsaved_bar();
+    }
+
+    /// Match path entries saved by earlier versions to folder ids, in both
+    /// the current and the saved selection, so they show as checked and the
+    /// next save stores the ids. Needs the tree to have loaded.
+    fn migrate_saved_paths(&self) {
+        let imp = self.imp();
+        let nodes = imp.tree_nodes.borrow();
+        if nodes.is_empty() {
+            return;
+        }
+        for folders in [&imp.selected_folders, &imp.saved_folders] {
+            let migrated = migrate_path_selections(&nodes, &folders.borrow());
+            folders.replace(migrated);
+        }
     }
 
     /// Called whenever a checkbox is toggled. Propagates the selection to
-    /// children and then sends the full list of selected paths to the daemon.
+    /// children and then schedules sending the full list of selected paths
+    /// to the daemon, or only stages it while changes are applied manually.
     fn on_selection_changed(&self) {
         let imp = self.imp();
 
-        // Collect all selected paths from the root store.
+        // Collect all selected folders from the root store.
         let store = match imp.root_store.borrow().clone() {
             Some(s) => s,
             None => return,
         };
 
-        let mut selected_paths = Vec::new();
-        self.collect_selected(&store, &mut selected_paths);
+        let mut selected_keys = Vec::new();
+        self.collect_selected(&store, &mut selected_keys);
+
+        *imp.selected_folders.borrow_mut() = selected_keys;
 
-        *imp.selected_folders.borrow_mut() = selected_paths.clone();
+        self.refilter_selected();
+        self.update_empty_selection_hint();
+        if imp.manual_apply.get() {
+            self.update_unsaved_bar();
+        } else {
+            self.schedule_save();
+        }
+    }
+
+    /// Debounce selection saves so a burst of toggles results in a single
+    /// D-Bus call (and a single toast).
+    fn schedule_save(&self) {
+        let imp = self.imp();
+
+        if let Some(source_id) = imp.save_source.borrow_mut().take() {
+            source_id.remove();
+        }
+
+        let tree = self.clone();
+        let source_id = glib::timeout_add_local_once(
+            std::time::Duration::from_millis(500),
+            move || {
+                tree.imp().save_source.replace(None);
+                tree.save_selected_folders(true);
+            },
+        );
+
+        imp.save_source.replace(Some(source_id));
+    }
+
+    /// Send the current selection to the daemon and report the outcome.
+    /// With `undoable`, the success toast offers to restore the selection
+    /// that was saved before.
+    fn save_selected_folders(&self, undoable: bool) {
+        let imp = self.imp();
+        let selected_keys = imp.selected_folders.borrow().clone();
 
-        // Send to daemon.
         let client = match imp.dbus_client.borrow().clone() {
             Some(c) => c,
             None => return,
         };
 
+        let root_id = imp.root_id.borrow().clone();
+        let tree = self.clone();
         glib::MainContext::default().spawn_local(async move {
-            if let Err(e) = client.set_selected_folders(&selected_paths).await {
-                eprintln!("Could not save selected folders: {}", e);
+            match client.set_selected_folders(&root_id, &selected_keys).await {
+                Ok(()) => {
+                    let previous = tree.imp().saved_folders.replace(selected_keys.clone());
+                    tree.update_unsaved_bar();
+                    let toast = adw::Toast::new(&gettext("Sync folders updated"));
+                    if undoable && previous != selected_keys {
+                        toast.set_button_label(Some(&gettext("Undo")));
+                        let tree_undo = tree.clone();
+                        toast.connect_button_clicked(move |_| {
+                            tree_undo.restore_selection(previous.clone());
+                        });
+                    }
+                    tree.show_toast(&toast);
+                }
+                Err(e) => {
+                    eprintln!("Could not save selected folders: {}", e);
+                    let toast = adw::Toast::builder()
+                        .title(&gettext("Could not update sync folders"))
+                        .button_label(&gettext("Retry"))
+                        .build();
+                    let tree_retry = tree.clone();
+                    toast.connect_button_clicked(move |_| {
+                        tree_retry.save_selected_folders(undoable);
+                    });
+                    tree.show_toast(&toast);
+                }
             }
         });
     }
 
-    /// Recursively collect the paths of selected FolderNodes from a ListStore.
+    /// Save a debounced selection change now instead of when its timer
+    /// fires.
+    pub fn flush_pending_save(&self) {
+        if let Some(source_id) = self.imp().save_source.borrow_mut().take() {
+            source_id.remove();
+            self.save_selected_folders(true);
+        }
+    }
+
+    /// Put back an earlier selection and save it right away. The restore is
+    /// itself not undoable: only the most recent change can be undone.
+    fn restore_selection(&self, folders: Vec<String>) {
+        let imp = self.imp();
+        if let Some(source_id) = imp.save_source.borrow_mut().take() {
+            source_id.remove();
+        }
+
+        *imp.selected_folders.borrow_mut() = folders;
+        self.apply_selections();
+        self.save_selected_folders(false);
+    }
+
+    /// Show a toast on the enclosing PreferencesDialog, which is itself a
+    /// toast overlay.
+    fn show_toast(&self, toast: &adw::Toast) {
+        if let Some(dialog) = self
+            .ancestor(adw::PreferencesDialog::static_type())
+            .and_then(|w| w.downcast::<adw::PreferencesDialog>().ok())
+        {
+            dialog.add_toast(toast.clone());
+        }
+    }
+
+    /// Recursively collect the selection keys of selected FolderNodes from a
+    /// ListStore.
     fn collect_selected(&self, store: &gio::ListStore, out: &mut Vec<String>) {
         for i in 0..store.n_items() {
             if let Some(item) = store.item(i) {
                 if let Some(node) = item.downcast_ref::<FolderNode>() {
                     if node.selected() {
-                        out.push(node.path());
+                        out.push(node.selection_key());
                     }
                     // Note: children are only materialised when expanded;
                     // we rely on the user expanding and toggling them.
@@ -493,3 +1778,315 @@ impl FolderTree {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::{
+        find_widgets, present, run_widget_test, wait_until, MockState, TestDaemon, SYNC_ROOT,
+    };
+
+    fn folder(id: &str, path: &str, children: Vec<FolderNodeJson>) -> FolderNodeJson {
+        FolderNodeJson {
+            id: id.to_string(),
+            name: path.rsplit('/').next().unwrap_or_default().to_string(),
+            path: path.to_string(),
+            children,
+        }
+    }
+
+    #[test]
+    fn selection_key_prefers_id_over_path() {
+        assert_eq!(folder("a1", "/Projects", vec![]).selection_key(), "a1");
+        assert_eq!(folder("", "/Projects", vec![]).selection_key(), "/Projects");
+    }
+
+    #[test]
+    fn migrates_unique_paths_to_ids() {
+        let nodes = vec![
+            folder("docs", "/Documents", vec![folder("work", "/Documents/Work", vec![])]),
+            folder("", "/Photos", vec![]),
+            folder("owned", "/Projects", vec![]),
+            folder("shared", "/Projects", vec![]),
+        ];
+        let selected = [
+            "/Documents/Work",
+            "/Photos",
+            "/Projects",
+            "docs",
+            "/Documents",
+            "/Gone",
+        ]
+        .map(String::from);
+        assert_eq!(
+            migrate_path_selections(&nodes, &selected),
+            ["work", "/Photos", "/Projects", "docs", "/Gone"]
+        );
+    }
+
+    #[test]
+    fn contains_selection_follows_the_subtree_not_the_path() {
+        let owned = folder(
+            "owned",
+            "/Projects",
+            vec![folder("owned-src", "/Projects/src", vec![])],
+        );
+        let shared = folder(
+            "shared",
+            "/Projects",
+            vec![folder("shared-src", "/Projects/src", vec![])],
+        );
+        let selected = vec!["shared-src".to_string()];
+
+        assert!(shared.contains_selection(&selected));
+        assert!(!owned.contains_selection(&selected));
+    }
+
+    fn default_root() -> SyncRoot {
+        SyncRoot {
+            id: "default".to_string(),
+            name: "OneDrive".to_string(),
+            local_path: SYNC_ROOT.to_string(),
+        }
+    }
+
+    /// The rows on screen, top to bottom: TreeExpander -> Box -> CheckButton,
+    /// Label, badge Image, sharing MenuButton.
+    fn folder_rows(tree: &FolderTree) -> Vec<gtk4::Box> {
+        find_widgets::<gtk4::TreeExpander>(tree)
+            .iter()
+            .filter_map(|expander| expander.child().and_downcast::<gtk4::Box>())
+            .collect()
+    }
+
+    fn row_check(row: &gtk4::Box) -> gtk4::CheckButton {
+        row.first_child()
+            .and_downcast::<gtk4::CheckButton>()
+            .expect("A folder row starts with its CheckButton")
+    }
+
+    fn row_name(row: &gtk4::Box) -> String {
+        row_check(row)
+            .next_sibling()
+            .and_downcast::<gtk4::Label>()
+            .expect("The CheckButton is followed by the name")
+            .label()
+            .to_string()
+    }
+
+    /// The folder row showing `name`.
+    fn folder_row(tree: &FolderTree, name: &str) -> Option<gtk4::Box> {
+        folder_rows(tree)
+            .into_iter()
+            .find(|row| row_name(row) == name)
+    }
+
+    /// Icon of the row's hydration badge, if the badge is shown.
+    fn row_badge(row: &gtk4::Box) -> Option<String> {
+        let badge = row_check(row)
+            .next_sibling()
+            .and_then(|label| label.next_sibling())
+            .and_downcast::<gtk4::Image>()
+            .expect("The name is followed by the badge");
+        badge
+            .is_visible()
+            .then(|| badge.icon_name())
+            .flatten()
+            .map(|icon| icon.to_string())
+    }
+
+    /// The row's sharing button, after the badge.
+    fn row_share_button(row: &gtk4::Box) -> gtk4::MenuButton {
+        row.last_child()
+            .and_downcast::<gtk4::MenuButton>()
+            .expect("A folder row ends with its sharing button")
+    }
+
+    /// Each row's folder name and whether it is checked.
+    fn row_states(tree: &FolderTree) -> Vec<(String, bool)> {
+        folder_rows(tree)
+            .iter()
+            .map(|row| (row_name(row), row_check(row).is_active()))
+            .collect()
+    }
+
+    #[test]
+    fn shows_folders_checked_per_the_saved_selection() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let tree = FolderTree::new(Some(daemon.client()), &default_root());
+            let window = present(&tree);
+
+            wait_until("the saved selection", || {
+                tree.imp().selection_loaded.get() && row_states(&tree).len() == 5
+            });
+            let top_level: Vec<(String, bool)> = row_states(&tree)
+                .into_iter()
+                .filter(|(name, _)| name != "Vacation")
+                .collect();
+            assert_eq!(
+                top_level,
+                [
+                    ("Documents".to_string(), true),
+                    ("Photos".to_string(), true),
+                    ("Projects".to_string(), true),
+                    ("Projects (shared)".to_string(), false),
+                ]
+            );
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn badges_folders_by_hydration_state() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let tree = FolderTree::new(Some(daemon.client()), &default_root());
+            let window = present(&tree);
+            let badge = |name: &str| folder_row(&tree, name).and_then(|row| row_badge(&row));
+
+            wait_until("the hydration badges", || badge("Projects").is_some());
+            assert_eq!(badge("Documents").as_deref(), Some("emblem-ok-symbolic"));
+            assert_eq!(
+                badge("Photos").as_deref(),
+                Some("weather-few-clouds-symbolic")
+            );
+            assert_eq!(
+                badge("Projects").as_deref(),
+                Some("weather-overcast-symbolic")
+            );
+
+            WidgetExt::activate_action(&tree, "folder.pin", Some(&"/Photos".to_variant()))
+                .expect("The folder actions are installed");
+            wait_until("Photos to be downloaded", || {
+                badge("Photos").as_deref() == Some("emblem-ok-symbolic")
+            });
+            assert_eq!(
+                daemon.state().hydration[&format!("{SYNC_ROOT}/Photos")],
+                "hydrated"
+            );
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn confirms_unpinning_with_the_space_it_frees() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let tree = FolderTree::new(Some(daemon.client()), &default_root());
+            let window = present(&tree);
+            let badge = |name: &str| folder_row(&tree, name).and_then(|row| row_badge(&row));
+            wait_until("the hydration badges", || badge("Documents").is_some());
+
+            WidgetExt::activate_action(&tree, "folder.unpin", Some(&"/Documents".to_variant()))
+                .expect("The folder actions are installed");
+            let dialog = || find_widgets::<adw::AlertDialog>(&window).pop();
+            wait_until("the confirmation", || dialog().is_some());
+            let dialog = dialog().unwrap();
+            assert_eq!(
+                dialog.heading().unwrap_or_default(),
+                gettext("Free Up {}?").replace("{}", &util::format_size(1_572_864))
+            );
+
+            dialog.emit_by_name::<()>("response", &[&"free"]);
+            wait_until("Documents to be online-only", || {
+                badge("Documents").as_deref() == Some("weather-overcast-symbolic")
+            });
+            assert_eq!(
+                daemon.state().hydration[&format!("{SYNC_ROOT}/Documents")],
+                "cloud-only"
+            );
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn shows_who_business_folders_are_shared_with() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState {
+                account_type: "Business".to_string(),
+                ..MockState::default()
+            });
+            let tree = FolderTree::new(Some(daemon.client()), &default_root());
+            let window = present(&tree);
+            let share_button = |name: &str| {
+                folder_row(&tree, name)
+                    .map(|row| row_share_button(&row))
+                    .filter(|button| button.is_visible())
+            };
+
+            wait_until("the sharing button", || share_button("Photos").is_some());
+            assert_eq!(
+                share_button("Photos")
+                    .unwrap()
+                    .tooltip_text()
+                    .unwrap_or_default(),
+                shared_label(2)
+            );
+            assert!(share_button("Documents").is_none());
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn saves_the_toggled_folder_by_id() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let tree = FolderTree::new(Some(daemon.client()), &default_root());
+            let window = present(&tree);
+            wait_until("the saved selection", || {
+                tree.imp().selection_loaded.get() && row_states(&tree).len() == 5
+            });
+
+            // One of the two folders at /Projects.
+            let shared = folder_row(&tree, "Projects (shared)").unwrap();
+            row_check(&shared).set_active(true);
+            wait_until("the selection to be saved", || {
+                daemon
+                    .state()
+                    .selected_folders
+                    .contains(&"projects-shared".to_string())
+            });
+            let mut saved = daemon.state().selected_folders.clone();
+            saved.sort();
+            assert_eq!(
+                saved,
+                ["/Documents", "/Photos", "projects-owned", "projects-shared"]
+            );
+            assert!(row_check(&folder_row(&tree, "Projects").unwrap()).is_active());
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn keeps_a_selection_saved_by_path() {
+        run_widget_test(|| {
+            let mut state = MockState::default();
+            state.folder_tree["children"][0]["id"] = serde_json::json!("documents-id");
+            // Saved by an earlier version, before the tree had ids.
+            state.selected_folders = vec!["/Documents".to_string()];
+            let daemon = TestDaemon::start(state);
+            let tree = FolderTree::new(Some(daemon.client()), &default_root());
+            let window = present(&tree);
+            wait_until("the saved selection", || {
+                tree.imp().selection_loaded.get() && row_states(&tree).len() == 5
+            });
+            assert!(row_check(&folder_row(&tree, "Documents").unwrap()).is_active());
+
+            row_check(&folder_row(&tree, "Photos").unwrap()).set_active(true);
+            wait_until("the selection to be saved", || {
+                daemon.state().selected_folders.contains(&"/Photos".to_string())
+            });
+            let mut saved = daemon.state().selected_folders.clone();
+            saved.sort();
+            assert_eq!(saved, ["/Photos", "documents-id"]);
+
+            window.destroy();
+        });
+    }
+}
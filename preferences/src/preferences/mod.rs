@@ -1,11 +1,18 @@
 // Preferences Dialog — adw::PreferencesDialog subclass
 //
-// A three-page preferences panel: Account, Sync, and Advanced.
-// Each page is an adw::PreferencesPage subclass that reads from and writes to
-// the LNXDrive daemon via the shared DbusClient.
+// A six-page preferences panel: Account, Sync, Activity, Conflicts, Recently
+// Deleted and Advanced. Each page is an adw::PreferencesPage subclass that reads from and writes to
+// the LNXDrive daemon via the shared DbusClient. The dialog keeps the pages
+// so they can be shown by name (`navigate_to`, also behind `--page`) and
+// Ctrl+1 to Ctrl+6 jump to them in that order. `reload_all` re-reads every
+// page from the daemon, for the window's Refresh action. The dialog reopens
+// on the page it was last left on, unless a page was asked for.
 
 pub mod account_page;
+pub mod activity_page;
 pub mod advanced_page;
+pub mod config_editor;
+pub mod deleted_page;
 pub mod folder_tree;
 pub mod sync_page;
 
@@ -20,13 +27,19 @@ use libadwaita::prelude::*;
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 
 use crate::dbus_client::DbusClient;
+use crate::settings::AppSettings;
 
 use account_page::AccountPage;
+use activity_page::ActivityPage;
 use advanced_page::AdvancedPage;
+use deleted_page::DeletedPage;
 use sync_page::SyncPage;
 
 use crate::conflicts::ConflictListPage;
 
+/// Page names accepted by `navigate_to`, in display order.
+const PAGE_NAMES: [&str; 6] = ["account", "sync", "activity", "conflicts", "deleted", "advanced"];
+
 // ---------------------------------------------------------------------------
 // PreferencesDialog — adw::PreferencesDialog subclass
 // ---------------------------------------------------------------------------
@@ -38,12 +51,24 @@ mod imp {
 
     pub struct PreferencesDialog {
         pub dbus_client: RefCell<Option<DbusClient>>,
+        pub account_page: RefCell<Option<AccountPage>>,
+        pub sync_page: RefCell<Option<SyncPage>>,
+        pub activity_page: RefCell<Option<ActivityPage>>,
+        pub conflicts_page: RefCell<Option<ConflictListPage>>,
+        pub deleted_page: RefCell<Option<DeletedPage>>,
+        pub advanced_page: RefCell<Option<AdvancedPage>>,
     }
 
     impl Default for PreferencesDialog {
         fn default() -> Self {
             Self {
                 dbus_client: RefCell::new(None),
+                account_page: RefCell::new(None),
+                sync_page: RefCell::new(None),
+                activity_page: RefCell::new(None),
+                conflicts_page: RefCell::new(None),
+                deleted_page: RefCell::new(None),
+                advanced_page: RefCell::new(None),
             }
         }
     }
@@ -68,8 +93,9 @@ glib::wrapper! {
 }
 
 impl PreferencesDialog {
-    /// Create the preferences dialog and populate it with the four pages.
-    /// If `initial_page` matches a page name, navigate to it.
+    /// Create the preferences dialog and populate it with the six pages.
+    /// If `initial_page` matches a page name, navigate to it; without one,
+    /// show the page last visited.
     pub fn new(dbus_client: &DbusClient, initial_page: Option<&str>) -> Self {
         let dialog: Self = glib::Object::builder()
             .property("title", gettext("LNXDrive Preferences"))
@@ -81,31 +107,126 @@ impl PreferencesDialog {
             .dbus_client
             .replace(Some(dbus_client.clone()));
 
-        // Build the four pages.
+        // Build the six pages.
         let account_page = AccountPage::new(dbus_client);
         let sync_page = SyncPage::new(dbus_client);
+        let activity_page = ActivityPage::new(dbus_client);
         let conflicts_page = ConflictListPage::new(dbus_client);
+        let deleted_page = DeletedPage::new(dbus_client);
         let advanced_page = AdvancedPage::new(dbus_client);
 
         dialog.add(&account_page);
         dialog.add(&sync_page);
+        dialog.add(&activity_page);
         dialog.add(&conflicts_page);
+        dialog.add(&deleted_page);
         dialog.add(&advanced_page);
 
-        // Navigate to initial page if specified
-        if let Some(page_name) = initial_page {
-            match page_name {
-                "account" => dialog.set_visible_page(&account_page),
-                "sync" => dialog.set_visible_page(&sync_page),
-                "conflicts" => dialog.set_visible_page(&conflicts_page),
-                "advanced" => dialog.set_visible_page(&advanced_page),
-                _ => {}
+        let imp = dialog.imp();
+        imp.account_page.replace(Some(account_page));
+        imp.sync_page.replace(Some(sync_page));
+        imp.activity_page.replace(Some(activity_page));
+        imp.conflicts_page.replace(Some(conflicts_page));
+        imp.deleted_page.replace(Some(deleted_page));
+        imp.advanced_page.replace(Some(advanced_page));
+
+        dialog.add_page_shortcuts();
+
+        // Navigate to initial page if specified. A bad name may have been
+        // carried through onboarding, so say why the dialog opened elsewhere.
+        let settings = AppSettings::new();
+        match initial_page {
+            Some(page_name) => {
+                if !dialog.navigate_to(page_name) {
+                    eprintln!("Unknown preferences page {page_name:?}; expected one of {PAGE_NAMES:?}");
+                }
+            }
+            None => {
+                dialog.navigate_to(&settings.last_page());
             }
         }
 
+        // Connected only now, so restoring the page doesn't write it back.
+        dialog.connect_visible_page_notify(move |dialog| {
+            let name = dialog.visible_page().and_then(|page| dialog.page_name(&page));
+            if let Some(name) = name {
+                let _ = settings.set_last_page(name);
+            }
+        });
+
         dialog
     }
 
+    /// Show the page called `name` ("account", "sync", "activity",
+    /// "conflicts", "deleted" or "advanced"). Returns false for an unknown name.
+    pub fn navigate_to(&self, name: &str) -> bool {
+        match self.page(name) {
+            Some(page) => {
+                self.set_visible_page(&page);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The page called `name`, if there is one.
+    fn page(&self, name: &str) -> Option<adw::PreferencesPage> {
+        let imp = self.imp();
+        match name {
+            "account" => imp.account_page.borrow().clone().map(|p| p.upcast()),
+            "sync" => imp.sync_page.borrow().clone().map(|p| p.upcast()),
+            "activity" => imp.activity_page.borrow().clone().map(|p| p.upcast()),
+            "conflicts" => imp.conflicts_page.borrow().clone().map(|p| p.upcast()),
+            "deleted" => imp.deleted_page.borrow().clone().map(|p| p.upcast()),
+            "advanced" => imp.advanced_page.borrow().clone().map(|p| p.upcast()),
+            _ => None,
+        }
+    }
+
+    /// The name `navigate_to` takes for `page`.
+    fn page_name(&self, page: &adw::PreferencesPage) -> Option<&'static str> {
+        PAGE_NAMES
+            .into_iter()
+            .find(|name| self.page(name).as_ref() == Some(page))
+    }
+
+    /// Fetch every page's data from the daemon again. The Activity page is
+    /// left out: it refreshes itself every few seconds.
+    pub fn reload_all(&self) {
+        let imp = self.imp();
+        if let Some(ref page) = *imp.account_page.borrow() {
+            page.reload();
+        }
+        if let Some(ref page) = *imp.sync_page.borrow() {
+            page.reload();
+        }
+        if let Some(ref page) = *imp.conflicts_page.borrow() {
+            page.reload();
+        }
+        if let Some(ref page) = *imp.deleted_page.borrow() {
+            page.reload();
+        }
+        if let Some(ref page) = *imp.advanced_page.borrow() {
+            page.reload();
+        }
+    }
+
+    /// Ctrl+1 to Ctrl+6 show the page at that position.
+    fn add_page_shortcuts(&self) {
+        let controller = gtk4::ShortcutController::new();
+        for (index, &name) in PAGE_NAMES.iter().enumerate() {
+            let trigger = gtk4::ShortcutTrigger::parse_string(&format!("<Control>{}", index + 1));
+            let action = gtk4::CallbackAction::new(move |widget, _| {
+                match widget.downcast_ref::<PreferencesDialog>() {
+                    Some(dialog) if dialog.navigate_to(name) => glib::Propagation::Stop,
+                    _ => glib::Propagation::Proceed,
+                }
+            });
+            controller.add_shortcut(gtk4::Shortcut::new(trigger, Some(action)));
+        }
+        self.add_controller(controller);
+    }
+
     /// Present the dialog over the given parent widget.
     pub fn present(&self, parent: &impl IsA<gtk4::Widget>) {
         adw::prelude::AdwDialogExt::present(self, Some(parent));
@@ -1,12 +1,23 @@
 // Sync Page — adw::PreferencesPage subclass
 //
-// Contains sync options (auto sync, conflict resolution, interval) and the
-// selective sync folder tree (FolderTree widget). Loads initial values from
-// the daemon and debounces changes before sending them back.
-
-use std::cell::RefCell;
-
-use gettextrs::gettext;
+// Contains the local sync folder (with a confirmed "Change..." action), sync
+// options (auto sync, conflict resolution, interval) and one selective sync
+// folder tree (FolderTree widget) per sync root. Loads initial
+// values from the daemon and debounces changes before sending them back.
+// The sync interval is a list of presets from five minutes to daily, plus
+// "Custom" for any other number of minutes; a stored value that is not a
+// preset shows up as Custom.
+// Switching conflict resolution to an automatic strategy asks for
+// confirmation first and can apply the strategy to existing conflicts. The
+// "Suggest a Version" switch is app-side only (GSettings) and controls the
+// conflict dialog's "Recommended" badge. "Apply Folder Changes Manually",
+// also in GSettings, makes the folder trees stage selection changes behind
+// Apply/Revert instead of saving them as they are made.
+
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+
+use gettextrs::{gettext, ngettext};
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita as adw;
@@ -14,7 +25,12 @@ use libadwaita::prelude::*;
 
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 
-use crate::dbus_client::DbusClient;
+use crate::config;
+use crate::conflicts::conflict_dialog::ConflictInfo;
+use crate::dbus_client::{DbusClient, SyncRoot};
+use crate::onboarding::folder_page::{choose_sync_folder, validate_sync_folder};
+use crate::settings::{keys, AppSettings};
+use crate::util;
 
 use super::folder_tree::FolderTree;
 
@@ -29,10 +45,20 @@ mod imp {
 
     pub struct SyncPage {
         pub dbus_client: RefCell<Option<DbusClient>>,
+        /// Shows the configured `sync_root`.
+        pub folder_row: RefCell<Option<adw::ActionRow>>,
+        pub sync_root: RefCell<String>,
         pub auto_sync_row: RefCell<Option<adw::SwitchRow>>,
         pub conflict_row: RefCell<Option<adw::ComboRow>>,
-        pub interval_row: RefCell<Option<adw::SpinRow>>,
-        pub folder_tree: RefCell<Option<FolderTree>>,
+        /// Confirmed conflict strategy index; what `save_settings` sends while
+        /// a change is awaiting confirmation.
+        pub committed_conflict: Cell<u32>,
+        /// Interval presets; the last entry is "Custom".
+        pub interval_row: RefCell<Option<adw::ComboRow>>,
+        /// Minutes for the "Custom" interval, shown only when it is selected.
+        pub custom_interval_row: RefCell<Option<adw::SpinRow>>,
+        /// One Selective Sync group and folder tree per sync root.
+        pub root_groups: RefCell<Vec<(adw::PreferencesGroup, FolderTree)>>,
         /// Source ID for the debounce timer. When a setting changes, we start a
         /// 500ms timeout; if another change arrives before it fires we reset it.
         pub debounce_source: RefCell<Option<glib::SourceId>>,
@@ -42,10 +68,14 @@ mod imp {
         fn default() -> Self {
             Self {
                 dbus_client: RefCell::new(None),
+                folder_row: RefCell::new(None),
+                sync_root: RefCell::new(String::new()),
                 auto_sync_row: RefCell::new(None),
                 conflict_row: RefCell::new(None),
+                committed_conflict: Cell::new(0),
                 interval_row: RefCell::new(None),
-                folder_tree: RefCell::new(None),
+                custom_interval_row: RefCell::new(None),
+                root_groups: RefCell::new(Vec::new()),
                 debounce_source: RefCell::new(None),
             }
         }
@@ -78,6 +108,28 @@ const CONFLICT_LABELS: &[&str] = &[
     "Keep Both",
 ];
 
+/// Config values for CONFLICT_LABELS, in the same order.
+const CONFLICT_VALUES: &[&str] = &["always_ask", "keep_local", "keep_remote", "keep_both"];
+
+/// Sync interval presets; the combo row adds "Custom" after them.
+const INTERVAL_LABELS: &[&str] = &[
+    "5 minutes",
+    "15 minutes",
+    "30 minutes",
+    "1 hour",
+    "4 hours",
+    "Daily",
+];
+
+/// `sync_interval_minutes` for INTERVAL_LABELS, in the same order.
+const INTERVAL_MINUTES: &[u32] = &[5, 15, 30, 60, 240, 1440];
+
+/// Default interval, also used when the config has none.
+const DEFAULT_INTERVAL_MINUTES: u32 = 5;
+
+/// Longest custom interval: 30 days.
+const MAX_INTERVAL_MINUTES: u32 = 30 * 1440;
+
 impl SyncPage {
     pub fn new(dbus_client: &DbusClient) -> Self {
         let page: Self = glib::Object::builder()
@@ -91,13 +143,57 @@ impl SyncPage {
 
         page.build_ui();
         page.load_initial_values();
+        page.load_sync_roots();
 
         page
     }
 
+    /// Fetch the settings and sync roots again, rebuilding the Selective
+    /// Sync groups so added or removed roots show up. Changes still waiting
+    /// to be saved are saved first.
+    pub fn reload(&self) {
+        let imp = self.imp();
+        if let Some(source_id) = imp.debounce_source.borrow_mut().take() {
+            source_id.remove();
+            self.save_settings();
+        }
+
+        let groups: Vec<_> = imp.root_groups.borrow_mut().drain(..).collect();
+        for (group, tree) in groups {
+            tree.flush_pending_save();
+            self.remove(&group);
+        }
+
+        self.load_initial_values();
+        self.load_sync_roots();
+    }
+
     fn build_ui(&self) {
         let imp = self.imp();
 
+        // -- Location group --------------------------------------------------
+
+        let location_group = adw::PreferencesGroup::builder()
+            .title(&gettext("Location"))
+            .build();
+
+        let change_button = gtk4::Button::builder()
+            .label(&gettext("Change..."))
+            .valign(gtk4::Align::Center)
+            .build();
+        let folder_row = adw::ActionRow::builder()
+            .title(&gettext("Sync Folder"))
+            .subtitle(&gettext("Loading..."))
+            .build();
+        folder_row.add_suffix(&change_button);
+        imp.folder_row.replace(Some(folder_row.clone()));
+        location_group.add(&folder_row);
+
+        let page = self.clone();
+        change_button.connect_clicked(move |_| {
+            page.on_change_sync_folder();
+        });
+
         // -- Sync Options group ----------------------------------------------
 
         let options_group = adw::PreferencesGroup::builder()
@@ -129,42 +225,59 @@ impl SyncPage {
             .build();
         imp.conflict_row.replace(Some(conflict_row.clone()));
 
-        // Sync Interval spin row
-        let interval_row = adw::SpinRow::with_range(1.0, 60.0, 1.0);
-        interval_row.set_title(&gettext("Sync Interval (minutes)"));
-        interval_row.set_subtitle(&gettext("How often to check for remote changes"));
-        interval_row.set_value(5.0);
-        interval_row.set_snap_to_ticks(true);
+        // Sync Interval presets, plus "Custom"
+        let mut interval_labels: Vec<String> =
+            INTERVAL_LABELS.iter().map(|s| gettext(*s)).collect();
+        interval_labels.push(gettext("Custom"));
+        let interval_model = gtk4::StringList::new(
+            &interval_labels.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
+        );
+        let interval_row = adw::ComboRow::builder()
+            .title(&gettext("Sync Interval"))
+            .subtitle(&gettext("How often to check for remote changes"))
+            .model(&interval_model)
+            .build();
         imp.interval_row.replace(Some(interval_row.clone()));
 
-        options_group.add(&auto_sync_row);
-        options_group.add(&conflict_row);
-        options_group.add(&interval_row);
-
-        // -- Selective Sync group (FR-014) ------------------------------------
-
-        let selective_group = adw::PreferencesGroup::builder()
-            .title(&gettext("Selective Sync"))
-            .description(&gettext(
-                "Choose which remote folders to sync to this computer.",
+        let custom_interval_row =
+            adw::SpinRow::with_range(1.0, MAX_INTERVAL_MINUTES as f64, 1.0);
+        custom_interval_row.set_title(&gettext("Custom Interval (minutes)"));
+        custom_interval_row.set_value(DEFAULT_INTERVAL_MINUTES as f64);
+        custom_interval_row.set_snap_to_ticks(true);
+        custom_interval_row.set_visible(false);
+        imp.custom_interval_row
+            .replace(Some(custom_interval_row.clone()));
+
+        // Recommendation hint in the conflict dialog; stored in GSettings,
+        // not the daemon config.
+        let recommend_row = adw::SwitchRow::builder()
+            .title(&gettext("Suggest a Version"))
+            .subtitle(&gettext(
+                "Mark the newer version as recommended when resolving a conflict by hand",
             ))
             .build();
+        AppSettings::new().bind(keys::RECOMMEND_CONFLICT_VERSION, &recommend_row, "active");
 
-        let client = imp.dbus_client.borrow().clone();
-        let folder_tree = FolderTree::new(client.as_ref());
-        imp.folder_tree.replace(Some(folder_tree.clone()));
-
-        // Wrap in a ListBoxRow so it fits inside a PreferencesGroup.
-        let tree_row = gtk4::ListBoxRow::builder()
-            .activatable(false)
-            .selectable(false)
-            .child(&folder_tree)
+        let manual_apply_row = adw::SwitchRow::builder()
+            .title(&gettext("Apply Folder Changes Manually"))
+            .subtitle(&gettext(
+                "Collect selective sync changes and save them together with Apply",
+            ))
             .build();
-        selective_group.add(&tree_row);
+        AppSettings::new().bind(keys::APPLY_FOLDER_CHANGES, &manual_apply_row, "active");
+
+        options_group.add(&auto_sync_row);
+        options_group.add(&conflict_row);
+        options_group.add(&recommend_row);
+        options_group.add(&interval_row);
+        options_group.add(&custom_interval_row);
+        options_group.add(&manual_apply_row);
 
         // Add groups to page.
+        self.add(&location_group);
         self.add(&options_group);
-        self.add(&selective_group);
+        // Selective Sync groups are added by `load_sync_roots` once the
+        // daemon has reported the configured roots.
 
         // Connect change signals with debounce.
         let page = self.clone();
@@ -173,16 +286,60 @@ impl SyncPage {
         });
 
         let page = self.clone();
-        conflict_row.connect_selected_notify(move |_| {
+        conflict_row.connect_selected_notify(move |row| {
+            page.on_conflict_strategy_changed(row.selected());
+        });
+
+        let page = self.clone();
+        interval_row.connect_selected_notify(move |row| {
+            let custom = row.selected() as usize >= INTERVAL_MINUTES.len();
+            if let Some(ref spin) = *page.imp().custom_interval_row.borrow() {
+                spin.set_visible(custom);
+            }
             page.schedule_save();
         });
 
         let page = self.clone();
-        interval_row.connect_value_notify(move |_| {
+        custom_interval_row.connect_value_notify(move |_| {
             page.schedule_save();
         });
     }
 
+    /// Show `minutes` as its preset, or as "Custom" with that many minutes.
+    fn set_interval(&self, minutes: u32) {
+        let imp = self.imp();
+        let minutes = minutes.clamp(1, MAX_INTERVAL_MINUTES);
+        let preset = INTERVAL_MINUTES.iter().position(|m| *m == minutes);
+        if preset.is_none() {
+            if let Some(ref spin) = *imp.custom_interval_row.borrow() {
+                spin.set_value(minutes as f64);
+            }
+        }
+        if let Some(ref row) = *imp.interval_row.borrow() {
+            row.set_selected(preset.unwrap_or(INTERVAL_MINUTES.len()) as u32);
+        }
+    }
+
+    /// The interval the rows currently describe, in minutes.
+    fn interval(&self) -> u32 {
+        let imp = self.imp();
+        let selected = imp
+            .interval_row
+            .borrow()
+            .as_ref()
+            .map(|r| r.selected() as usize)
+            .unwrap_or(0);
+        match INTERVAL_MINUTES.get(selected) {
+            Some(minutes) => *minutes,
+            None => imp
+                .custom_interval_row
+                .borrow()
+                .as_ref()
+                .map(|r| r.value() as u32)
+                .unwrap_or(DEFAULT_INTERVAL_MINUTES),
+        }
+    }
+
     /// Load initial setting values from the daemon.
     fn load_initial_values(&self) {
         let client = match self.imp().dbus_client.borrow().clone() {
@@ -203,6 +360,135 @@ impl SyncPage {
         });
     }
 
+    /// Fetch the configured sync roots and add a Selective Sync group for
+    /// each of them.
+    fn load_sync_roots(&self) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            // Business accounts sync SharePoint document libraries as well as
+            // OneDrive folders; adjust the wording to match.
+            let business = client
+                .get_account_info()
+                .await
+                .map(|info| info.is_business())
+                .unwrap_or(false);
+
+            // Without the list, still show the single root every daemon has.
+            let roots = match client.list_sync_roots().await {
+                Ok(roots) => roots,
+                Err(e) => {
+                    eprintln!("Could not load sync roots: {}", e);
+                    vec![SyncRoot::legacy()]
+                }
+            };
+
+            // With a single root the group keeps its generic title; with
+            // several, each group is titled after its drive or library.
+            let titled = roots.len() > 1;
+            for root in &roots {
+                page.add_root_group(root, titled, business);
+            }
+        });
+    }
+
+    /// Build the Selective Sync group (header buttons, "All Folders" row and
+    /// folder tree) for one sync root and append it to the page.
+    fn add_root_group(&self, root: &SyncRoot, titled: bool, business: bool) {
+        let imp = self.imp();
+
+        let title = if titled && !root.name.is_empty() {
+            root.name.clone()
+        } else {
+            gettext("Selective Sync")
+        };
+        let description = if business {
+            gettext("Choose which SharePoint sites and folders to sync to this computer.")
+        } else {
+            gettext("Choose which remote folders to sync to this computer.")
+        };
+
+        let selective_group = adw::PreferencesGroup::builder()
+            .title(&title)
+            .description(&description)
+            .build();
+
+        let client = imp.dbus_client.borrow().clone();
+        let folder_tree = FolderTree::new(client.as_ref(), root);
+
+        // Expand All / Collapse All in the group header.
+        let expand_button = gtk4::Button::builder()
+            .label(&gettext("Expand All"))
+            .css_classes(["flat"])
+            .build();
+        let collapse_button = gtk4::Button::builder()
+            .label(&gettext("Collapse All"))
+            .css_classes(["flat"])
+            .build();
+
+        let tree = folder_tree.clone();
+        expand_button.connect_clicked(move |_| {
+            tree.expand_all();
+        });
+        let tree = folder_tree.clone();
+        collapse_button.connect_clicked(move |_| {
+            tree.collapse_all();
+        });
+
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        header_box.append(&expand_button);
+        header_box.append(&collapse_button);
+        selective_group.set_header_suffix(Some(&header_box));
+
+        // Select All / Deselect All for the whole tree.
+        let select_all_button = gtk4::Button::builder()
+            .label(&gettext("Select All"))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        let deselect_all_button = gtk4::Button::builder()
+            .label(&gettext("Deselect All"))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+
+        let tree = folder_tree.clone();
+        select_all_button.connect_clicked(move |_| {
+            tree.set_all_selected(true);
+        });
+        let tree = folder_tree.clone();
+        deselect_all_button.connect_clicked(move |_| {
+            tree.set_all_selected(false);
+        });
+
+        let all_folders_row = adw::ActionRow::builder()
+            .title(&gettext("All Folders"))
+            .build();
+        all_folders_row.add_suffix(&select_all_button);
+        all_folders_row.add_suffix(&deselect_all_button);
+        selective_group.add(&all_folders_row);
+
+        // Wrap in a ListBoxRow so it fits inside a PreferencesGroup.
+        let tree_row = gtk4::ListBoxRow::builder()
+            .activatable(false)
+            .selectable(false)
+            .child(&folder_tree)
+            .build();
+        selective_group.add(&tree_row);
+
+        self.add(&selective_group);
+        imp.root_groups
+            .borrow_mut()
+            .push((selective_group, folder_tree));
+    }
+
     /// Parse the daemon's YAML config and apply values to the UI widgets.
     /// We do simple line-based parsing to avoid pulling in a full YAML crate
     /// beyond serde (the config is flat key-value).
@@ -216,6 +502,12 @@ impl SyncPage {
                 let value = value.trim().trim_matches('"');
 
                 match key {
+                    "sync_root" => {
+                        imp.sync_root.replace(value.to_string());
+                        if let Some(ref row) = *imp.folder_row.borrow() {
+                            row.set_subtitle(value);
+                        }
+                    }
                     "sync_mode" | "auto_sync" => {
                         let active = value == "true" || value == "auto" || value == "automatic";
                         if let Some(ref row) = *imp.auto_sync_row.borrow() {
@@ -230,15 +522,16 @@ impl SyncPage {
                             "keep_both" | "both" => 3,
                             _ => 0,
                         };
+                        // Commit first so the notify handler doesn't ask for
+                        // confirmation of the daemon's own value.
+                        imp.committed_conflict.set(idx);
                         if let Some(ref row) = *imp.conflict_row.borrow() {
                             row.set_selected(idx);
                         }
                     }
                     "sync_interval" | "sync_interval_minutes" => {
                         if let Ok(mins) = value.parse::<f64>() {
-                            if let Some(ref row) = *imp.interval_row.borrow() {
-                                row.set_value(mins.clamp(1.0, 60.0));
-                            }
+                            self.set_interval(mins.round().max(1.0) as u32);
                         }
                     }
                     _ => {}
@@ -261,6 +554,7 @@ impl SyncPage {
         let source_id = glib::timeout_add_local_once(
             std::time::Duration::from_millis(500),
             move || {
+                page.imp().debounce_source.replace(None);
                 page.save_settings();
             },
         );
@@ -279,27 +573,12 @@ impl SyncPage {
             .map(|r| r.is_active())
             .unwrap_or(false);
 
-        let conflict_idx = imp
-            .conflict_row
-            .borrow()
-            .as_ref()
-            .map(|r| r.selected())
-            .unwrap_or(0);
+        let conflict_value = CONFLICT_VALUES
+            .get(imp.committed_conflict.get() as usize)
+            .copied()
+            .unwrap_or("always_ask");
 
-        let conflict_value = match conflict_idx {
-            0 => "always_ask",
-            1 => "keep_local",
-            2 => "keep_remote",
-            3 => "keep_both",
-            _ => "always_ask",
-        };
-
-        let interval = imp
-            .interval_row
-            .borrow()
-            .as_ref()
-            .map(|r| r.value() as u32)
-            .unwrap_or(5);
+        let interval = self.interval();
 
         let sync_mode = if auto_sync { "automatic" } else { "manual" };
 
@@ -319,4 +598,208 @@ impl SyncPage {
             }
         });
     }
+
+    // -- Sync folder ---------------------------------------------------------
+
+    /// Let the user pick a new local sync folder.
+    fn on_change_sync_folder(&self) {
+        let current = PathBuf::from(self.imp().sync_root.borrow().as_str());
+        let parent = self.root().and_downcast::<gtk4::Window>();
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let Some(path) = choose_sync_folder(parent.as_ref(), &current).await else {
+                // User cancelled.
+                return;
+            };
+            if path == current {
+                return;
+            }
+            match validate_sync_folder(&path) {
+                Ok(()) => page.confirm_sync_folder(path),
+                Err(reason) => page.show_toast(&reason),
+            }
+        });
+    }
+
+    /// Explain what moving the sync root does before applying it.
+    fn confirm_sync_folder(&self, path: PathBuf) {
+        let display = path.display().to_string();
+        let confirm = util::confirmation_dialog(
+            &gettext("Change Sync Folder?"),
+            &gettext(
+                "LNXDrive will sync into {} from now on. Files that are not already there will be downloaded again; the current folder is left as it is.",
+            )
+            .replace("{}", &display),
+        );
+        confirm.add_response("change", &gettext("Change Folder"));
+        confirm.set_response_appearance("change", adw::ResponseAppearance::Destructive);
+
+        let page = self.clone();
+        confirm.connect_response(None, move |_dialog, response| {
+            if response == "change" {
+                page.save_sync_folder(&display);
+            }
+        });
+
+        adw::prelude::AdwDialogExt::present(&confirm, Some(self.upcast_ref::<gtk4::Widget>()));
+    }
+
+    /// Write the new `sync_root`; only the row is updated on success.
+    fn save_sync_folder(&self, path: &str) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let yaml = config::string_setting("sync_root", path);
+        let path = path.to_string();
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.set_config(&yaml).await {
+                Ok(()) => {
+                    let imp = page.imp();
+                    if let Some(ref row) = *imp.folder_row.borrow() {
+                        row.set_subtitle(&path);
+                    }
+                    imp.sync_root.replace(path);
+                    page.show_toast(&gettext("Sync folder changed"));
+                }
+                Err(e) => {
+                    eprintln!("Could not change sync folder: {}", e);
+                    page.show_toast(&gettext("Could not change the sync folder"));
+                }
+            }
+        });
+    }
+
+    // -- Conflict resolution -------------------------------------------------
+
+    /// "Always Ask" is saved straight away; an automatic strategy may
+    /// overwrite edits, so it is only saved once the user confirms.
+    fn on_conflict_strategy_changed(&self, idx: u32) {
+        let imp = self.imp();
+        if idx == imp.committed_conflict.get() {
+            return;
+        }
+
+        if idx == 0 {
+            imp.committed_conflict.set(idx);
+            self.schedule_save();
+            return;
+        }
+
+        let client = match imp.dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        // Count the open conflicts first so the dialog can offer to resolve
+        // them; if the count fails, just don't offer it.
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let open = client
+                .list_conflicts()
+                .await
+                .ok()
+                .map(|json| ConflictInfo::from_json_array(&json).len())
+                .unwrap_or(0);
+            page.confirm_conflict_strategy(idx, open);
+        });
+    }
+
+    /// Explain what the automatic strategy does and save it on confirm. Any
+    /// other response puts the combo back to the committed strategy.
+    fn confirm_conflict_strategy(&self, idx: u32, open_conflicts: usize) {
+        let strategy = CONFLICT_VALUES.get(idx as usize).copied().unwrap_or("always_ask");
+
+        let consequence = match strategy {
+            "keep_local" => gettext(
+                "When a file changes in both places, the version on this computer is kept and the changes in OneDrive are discarded.",
+            ),
+            "keep_remote" => gettext(
+                "When a file changes in both places, the version in OneDrive is kept and your local edits are overwritten.",
+            ),
+            _ => gettext(
+                "When a file changes in both places, both versions are kept and your local version is saved under a new name.",
+            ),
+        };
+
+        let body = if open_conflicts > 0 {
+            let pending = ngettext(
+                "There is {} unresolved conflict. You can resolve it with this strategy now.",
+                "There are {} unresolved conflicts. You can resolve them with this strategy now.",
+                open_conflicts as u32,
+            )
+            .replace("{}", &open_conflicts.to_string());
+            format!("{}\n\n{}", consequence, pending)
+        } else {
+            consequence
+        };
+
+        let confirm = util::confirmation_dialog(
+            &gettext("Resolve Conflicts Automatically?"),
+            &body,
+        );
+        confirm.add_response("change", &gettext("Change Setting"));
+        if open_conflicts > 0 {
+            confirm.add_response("resolve", &gettext("Change and Resolve"));
+            confirm.set_response_appearance("resolve", adw::ResponseAppearance::Destructive);
+        } else {
+            confirm.set_response_appearance("change", adw::ResponseAppearance::Destructive);
+        }
+
+        let page = self.clone();
+        confirm.connect_response(None, move |_dialog, response| {
+            let imp = page.imp();
+            match response {
+                "change" | "resolve" => {
+                    imp.committed_conflict.set(idx);
+                    page.schedule_save();
+                    if response == "resolve" {
+                        page.resolve_open_conflicts(strategy);
+                    }
+                }
+                _ => {
+                    if let Some(ref row) = *imp.conflict_row.borrow() {
+                        row.set_selected(imp.committed_conflict.get());
+                    }
+                }
+            }
+        });
+
+        adw::prelude::AdwDialogExt::present(&confirm, Some(self.upcast_ref::<gtk4::Widget>()));
+    }
+
+    /// Apply `strategy` to every unresolved conflict.
+    fn resolve_open_conflicts(&self, strategy: &'static str) {
+        let client = match self.imp().dbus_client.borrow().clone() {
+            Some(c) => c,
+            None => return,
+        };
+
+        let page = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            match client.resolve_all_conflicts(strategy).await {
+                Ok(count) => {
+                    let msg = ngettext("Resolved {} conflict", "Resolved {} conflicts", count)
+                        .replace("{}", &count.to_string());
+                    page.show_toast(&msg);
+                }
+                Err(e) => {
+                    eprintln!("Could not resolve conflicts: {}", e);
+                    page.show_toast(&gettext("Could not resolve conflicts"));
+                }
+            }
+        });
+    }
+
+    /// Show a toast on the enclosing PreferencesDialog.
+    fn show_toast(&self, message: &str) {
+        if let Some(dialog) = self
+            .ancestor(adw::PreferencesDialog::static_type())
+            .and_then(|w| w.downcast::<adw::PreferencesDialog>().ok())
+        {
+            dialog.add_toast(adw::Toast::new(message));
+        }
+    }
 }
@@ -0,0 +1,121 @@
+// systemd user service control for the LNXDrive daemon
+//
+// Many installations run the daemon as the `lnxdrive.service` systemd user
+// unit. These helpers drive `systemctl --user` through gio::Subprocess, so
+// they are async on the glib main loop like the D-Bus calls. On systems
+// without systemd, or without the unit, `active_state()` returns `None` and
+// callers hide their controls.
+
+use gettextrs::gettext;
+use gtk4::gio;
+use gtk4::glib;
+
+/// Unit name of the daemon's systemd user service.
+pub const UNIT: &str = "lnxdrive.service";
+
+/// The unit's ActiveState, as reported by systemd.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActiveState {
+    Active,
+    Activating,
+    Deactivating,
+    Inactive,
+    Failed,
+}
+
+impl ActiveState {
+    fn from_systemd(value: &str) -> Self {
+        match value {
+            "active" | "reloading" => ActiveState::Active,
+            "activating" => ActiveState::Activating,
+            "deactivating" => ActiveState::Deactivating,
+            "failed" => ActiveState::Failed,
+            _ => ActiveState::Inactive,
+        }
+    }
+
+    /// User-facing label, e.g. "Running".
+    pub fn label(&self) -> String {
+        match self {
+            ActiveState::Active => gettext("Running"),
+            ActiveState::Activating => gettext("Starting..."),
+            ActiveState::Deactivating => gettext("Stopping..."),
+            ActiveState::Inactive => gettext("Stopped"),
+            ActiveState::Failed => gettext("Failed"),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self, ActiveState::Active | ActiveState::Activating)
+    }
+}
+
+/// Spawn `systemctl --user <args> lnxdrive.service`.
+fn systemctl(args: &[&str], flags: gio::SubprocessFlags) -> Result<gio::Subprocess, glib::Error> {
+    let mut argv: Vec<&std::ffi::OsStr> =
+        vec![std::ffi::OsStr::new("systemctl"), std::ffi::OsStr::new("--user")];
+    argv.extend(args.iter().map(std::ffi::OsStr::new));
+    argv.push(std::ffi::OsStr::new(UNIT));
+    gio::Subprocess::newv(&argv, flags)
+}
+
+/// Current state of the unit, or `None` if systemctl is missing or the unit
+/// is not installed.
+pub async fn active_state() -> Option<ActiveState> {
+    let process = systemctl(
+        &["show", "--property=LoadState", "--property=ActiveState"],
+        gio::SubprocessFlags::STDOUT_PIPE | gio::SubprocessFlags::STDERR_SILENCE,
+    )
+    .ok()?;
+    let (stdout, _) = process.communicate_utf8_future(None).await.ok()?;
+    let stdout = stdout?;
+
+    let mut loaded = false;
+    let mut state = ActiveState::Inactive;
+    for line in stdout.lines() {
+        match line.split_once('=') {
+            Some(("LoadState", value)) => loaded = value == "loaded",
+            Some(("ActiveState", value)) => state = ActiveState::from_systemd(value),
+            _ => {}
+        }
+    }
+    loaded.then_some(state)
+}
+
+/// Run `systemctl --user <verb>` and wait for it to finish.
+async fn run(verb: &str) -> Result<(), glib::Error> {
+    systemctl(&[verb], gio::SubprocessFlags::STDERR_SILENCE)?
+        .wait_check_future()
+        .await
+}
+
+/// Whether the unit is enabled to start with the user session.
+pub async fn is_enabled() -> bool {
+    match systemctl(
+        &["is-enabled", "--quiet"],
+        gio::SubprocessFlags::STDOUT_SILENCE | gio::SubprocessFlags::STDERR_SILENCE,
+    ) {
+        Ok(process) => process.wait_check_future().await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+pub async fn enable() -> Result<(), glib::Error> {
+    run("enable").await
+}
+
+pub async fn disable() -> Result<(), glib::Error> {
+    run("disable").await
+}
+
+pub async fn start() -> Result<(), glib::Error> {
+    run("start").await
+}
+
+pub async fn stop() -> Result<(), glib::Error> {
+    run("stop").await
+}
+
+pub async fn restart() -> Result<(), glib::Error> {
+    run("restart").await
+}
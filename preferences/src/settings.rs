@@ -0,0 +1,219 @@
+// LNXDrive Settings — typed wrapper around the app's GSettings schema
+//
+// Centralises the schema ID and key names so callers never pass raw strings
+// to gio::Settings. Scalar keys get typed getters/setters; widget properties
+// can be two-way bound to a key with `bind`.
+//
+// gio::Settings aborts the process when its schema is not installed, which
+// is the normal state of a build run from the source tree. The schema is
+// therefore looked up first; without it every key reads as its schema
+// default, writes are dropped and a warning is logged once.
+
+use std::sync::Once;
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+
+/// GSettings schema ID, installed from data/com.enigmora.LNXDrive.Preferences.gschema.xml.
+pub const SCHEMA_ID: &str = "com.enigmora.LNXDrive.Preferences";
+
+/// Key names defined in the schema.
+pub mod keys {
+    pub const WINDOW_WIDTH: &str = "window-width";
+    pub const WINDOW_HEIGHT: &str = "window-height";
+    pub const LAST_PAGE: &str = "last-page";
+    pub const RECOMMEND_CONFLICT_VERSION: &str = "recommend-conflict-version";
+    pub const COMPACT_LISTS: &str = "compact-lists";
+    pub const APPLY_FOLDER_CHANGES: &str = "apply-folder-changes";
+    pub const DEVELOPER_MODE: &str = "developer-mode";
+    pub const NOTIFICATIONS: &str = "notifications";
+    pub const RUN_IN_BACKGROUND: &str = "run-in-background";
+}
+
+/// Which sync events become desktop notifications; the `notifications` key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    None,
+    /// Conflicts and errors.
+    Important,
+    /// Also sync started and finished.
+    All,
+}
+
+impl NotificationLevel {
+    /// The key's value for this level.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Important => "important",
+            Self::All => "all",
+        }
+    }
+
+    fn from_key(value: &str) -> Self {
+        match value {
+            "none" => Self::None,
+            "all" => Self::All,
+            _ => Self::Important,
+        }
+    }
+}
+
+/// Default window `(width, height)`, as in the schema.
+pub const DEFAULT_WINDOW_SIZE: (i32, i32) = (800, 600);
+
+/// Schema defaults, used when the schema is not installed. Keep in sync with
+/// the gschema file.
+fn default_value(key: &str) -> glib::Value {
+    match key {
+        keys::WINDOW_WIDTH => DEFAULT_WINDOW_SIZE.0.to_value(),
+        keys::WINDOW_HEIGHT => DEFAULT_WINDOW_SIZE.1.to_value(),
+        keys::LAST_PAGE => "account".to_value(),
+        keys::NOTIFICATIONS => NotificationLevel::Important.as_str().to_value(),
+        keys::RECOMMEND_CONFLICT_VERSION => true.to_value(),
+        keys::COMPACT_LISTS
+        | keys::APPLY_FOLDER_CHANGES
+        | keys::DEVELOPER_MODE
+        | keys::RUN_IN_BACKGROUND => false.to_value(),
+        _ => unreachable!("unknown settings key {key}"),
+    }
+}
+
+/// Whether the schema is installed. Warns once if it is not.
+fn schema_installed() -> bool {
+    static WARN_MISSING: Once = Once::new();
+
+    let installed = gio::SettingsSchemaSource::default()
+        .and_then(|source| source.lookup(SCHEMA_ID, true))
+        .is_some();
+    if !installed {
+        WARN_MISSING.call_once(|| {
+            eprintln!(
+                "GSettings schema {SCHEMA_ID} is not installed; using default settings, changes will not be saved. \
+                 Install the schema or set GSETTINGS_SCHEMA_DIR to a directory with the compiled schema."
+            );
+        });
+    }
+    installed
+}
+
+/// Typed access to the application's GSettings.
+#[derive(Clone)]
+pub struct AppSettings {
+    /// `None` when the schema is not installed.
+    settings: Option<gio::Settings>,
+}
+
+impl AppSettings {
+    pub fn new() -> Self {
+        Self {
+            settings: schema_installed().then(|| gio::Settings::new(SCHEMA_ID)),
+        }
+    }
+
+    /// Settings backed by the installed schema, or `None` if it is missing,
+    /// for callers that skip persistence altogether instead of reading
+    /// defaults.
+    pub fn installed() -> Option<Self> {
+        let settings = Self::new();
+        settings.settings.is_some().then_some(settings)
+    }
+
+    /// Saved window `(width, height)` in pixels.
+    pub fn window_size(&self) -> (i32, i32) {
+        (self.int(keys::WINDOW_WIDTH), self.int(keys::WINDOW_HEIGHT))
+    }
+
+    pub fn set_window_size(&self, width: i32, height: i32) -> Result<(), glib::BoolError> {
+        let Some(ref settings) = self.settings else {
+            return Ok(());
+        };
+        settings.set_int(keys::WINDOW_WIDTH, width)?;
+        settings.set_int(keys::WINDOW_HEIGHT, height)
+    }
+
+    /// Identifier of the last visited preferences page (e.g. "account").
+    pub fn last_page(&self) -> String {
+        match self.settings {
+            Some(ref settings) => settings.string(keys::LAST_PAGE).into(),
+            None => default_value(keys::LAST_PAGE).get().unwrap_or_default(),
+        }
+    }
+
+    pub fn set_last_page(&self, page: &str) -> Result<(), glib::BoolError> {
+        match self.settings {
+            Some(ref settings) => settings.set_string(keys::LAST_PAGE, page),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the conflict dialog marks a version as recommended.
+    pub fn recommend_conflict_version(&self) -> bool {
+        self.boolean(keys::RECOMMEND_CONFLICT_VERSION)
+    }
+
+    /// Whether the Developer section of the Advanced page is shown. Hidden
+    /// setting: `gsettings set com.enigmora.LNXDrive.Preferences developer-mode true`.
+    pub fn developer_mode(&self) -> bool {
+        self.boolean(keys::DEVELOPER_MODE)
+    }
+
+    /// Which sync events are shown as desktop notifications.
+    pub fn notification_level(&self) -> NotificationLevel {
+        match self.settings {
+            Some(ref settings) => NotificationLevel::from_key(&settings.string(keys::NOTIFICATIONS)),
+            None => NotificationLevel::Important,
+        }
+    }
+
+    pub fn set_notification_level(&self, level: NotificationLevel) -> Result<(), glib::BoolError> {
+        match self.settings {
+            Some(ref settings) => settings.set_string(keys::NOTIFICATIONS, level.as_str()),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the app keeps running after its window is closed.
+    pub fn run_in_background(&self) -> bool {
+        self.boolean(keys::RUN_IN_BACKGROUND)
+    }
+
+    /// Call `f` whenever `key` changes, for as long as this AppSettings is
+    /// alive. Without the schema nothing ever changes, so `f` is never called.
+    pub fn connect_changed(&self, key: &str, f: impl Fn() + 'static) {
+        if let Some(ref settings) = self.settings {
+            settings.connect_changed(Some(key), move |_, _| f());
+        }
+    }
+
+    /// Two-way bind `key` to `property` on `object`, e.g.
+    /// `settings.bind(keys::SOME_SWITCH, &switch_row, "active")`. Without
+    /// the schema the property is only set to the key's default.
+    pub fn bind(&self, key: &str, object: &impl IsA<glib::Object>, property: &str) {
+        match self.settings {
+            Some(ref settings) => settings.bind(key, object, property).build(),
+            None => object.set_property_from_value(property, &default_value(key)),
+        }
+    }
+
+    fn int(&self, key: &str) -> i32 {
+        match self.settings {
+            Some(ref settings) => settings.int(key),
+            None => default_value(key).get().unwrap_or_default(),
+        }
+    }
+
+    fn boolean(&self, key: &str) -> bool {
+        match self.settings {
+            Some(ref settings) => settings.boolean(key),
+            None => default_value(key).get().unwrap_or_default(),
+        }
+    }
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
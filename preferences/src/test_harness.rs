@@ -0,0 +1,687 @@
+// Widget test harness: a mock daemon on a private bus
+//
+// Tests build the real pages against a zbus service that implements the
+// daemon's interfaces with canned responses (`MockState`), served on a
+// private bus started with gio::TestDBus, and talk to it through
+// `DbusClient::from_connection`. `run_widget_test` runs each test on the one
+// thread GTK is initialised on; without a display the tests are skipped.
+// `present` puts a widget in a window so list views build their rows, and
+// `wait_until` iterates the main loop until the rows have arrived.
+//
+// Requires dbus-daemon (used by gio::TestDBus).
+
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, LazyLock, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use serde_json::{json, Value};
+use zbus::zvariant;
+
+use crate::dbus_client::DbusClient;
+
+const BUS_NAME: &str = "com.enigmora.LNXDrive";
+const OBJECT_PATH: &str = "/com/enigmora/LNXDrive";
+
+/// Local folder of the mock's sync root.
+pub const SYNC_ROOT: &str = "/tmp/lnxdrive-test-sync-root";
+
+/// The default root's folder tree (`folder_tree`) and selection
+/// (`selected_folders`), shared with tests/mock-dbus-daemon.py. Two folders
+/// share /Projects and are told apart by their ids.
+static FOLDER_TREE_FIXTURE: LazyLock<Value> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../tests/fixtures/folder-tree.json"))
+        .expect("The folder tree fixture is valid JSON")
+});
+
+/// How long `wait_until` waits before failing the test.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Run `test` on the thread GTK is initialised on. Cargo runs every test on
+/// a thread of its own, but GTK may only be used from one, so all widget
+/// tests share a single worker. Without a display GTK cannot be initialised
+/// and `test` is skipped.
+pub fn run_widget_test<F>(test: F)
+where
+    F: FnOnce() + Send + std::panic::UnwindSafe + 'static,
+{
+    static WORKER: OnceLock<Option<glib::ThreadPool>> = OnceLock::new();
+    let worker = WORKER.get_or_init(|| {
+        let pool = glib::ThreadPool::exclusive(1).ok()?;
+        let (tx, rx) = mpsc::sync_channel(1);
+        pool.push(move || {
+            let _ = tx.send(adw::init().is_ok());
+        })
+        .ok()?;
+        rx.recv().ok()?.then_some(pool)
+    });
+    let Some(pool) = worker else {
+        eprintln!("Skipping widget test: GTK could not be initialised (no display?)");
+        return;
+    };
+
+    let (tx, rx) = mpsc::sync_channel(1);
+    pool.push(move || {
+        let _ = tx.send(std::panic::catch_unwind(test));
+    })
+    .expect("Could not schedule the widget test");
+    if let Err(panic) = rx.recv().expect("The widget test worker stopped") {
+        std::panic::resume_unwind(panic);
+    }
+}
+
+/// Iterate the main loop until `done` returns true, failing the test after
+/// `TIMEOUT`.
+pub fn wait_until(what: &str, mut done: impl FnMut() -> bool) {
+    let context = glib::MainContext::default();
+    let deadline = Instant::now() + TIMEOUT;
+    while !done() {
+        assert!(Instant::now() < deadline, "Timed out waiting for {what}");
+        while context.pending() {
+            context.iteration(false);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Show `widget` in a window of its own, so it is mapped and list views
+/// build their rows. Dialogs it presents open inside the window. Destroy the
+/// window at the end of the test.
+pub fn present(widget: &impl IsA<gtk4::Widget>) -> adw::Window {
+    let window = adw::Window::builder()
+        .default_width(640)
+        .default_height(800)
+        .content(widget)
+        .build();
+    window.present();
+    window
+}
+
+/// Descendants of `root` of type `W` that are on screen, in tree order.
+pub fn find_widgets<W: IsA<gtk4::Widget>>(root: &impl IsA<gtk4::Widget>) -> Vec<W> {
+    let mut found = Vec::new();
+    let mut child = root.as_ref().first_child();
+    while let Some(widget) = child {
+        child = widget.next_sibling();
+        if !widget.is_mapped() {
+            continue;
+        }
+        found.extend(find_widgets::<W>(&widget));
+        if let Ok(widget) = widget.downcast::<W>() {
+            found.push(widget);
+        }
+    }
+    found
+}
+
+/// Text of every label on screen under `root`.
+pub fn labels(root: &impl IsA<gtk4::Widget>) -> Vec<String> {
+    find_widgets::<gtk4::Label>(root)
+        .iter()
+        .map(|label| label.label().to_string())
+        .collect()
+}
+
+/// Title and subtitle of every action row on screen under `root`.
+pub fn action_rows(root: &impl IsA<gtk4::Widget>) -> Vec<(String, String)> {
+    find_widgets::<adw::ActionRow>(root)
+        .iter()
+        .map(|row| {
+            let subtitle = row.subtitle().map(|s| s.to_string()).unwrap_or_default();
+            (row.title().to_string(), subtitle)
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Mock daemon
+// ---------------------------------------------------------------------------
+
+/// What the mock daemon serves. Tests adjust it before starting the daemon
+/// and read back what the widgets sent it.
+#[derive(Clone, Debug)]
+pub struct MockState {
+    pub account_type: String,
+    /// The `Sync.SyncStatus` property, e.g. "idle" or "syncing".
+    pub sync_status: String,
+    pub quota: (u64, u64),
+    /// Unresolved conflicts, in the `Conflicts.List` format.
+    pub conflicts: Vec<Value>,
+    /// Conflicts resolved since the daemon started.
+    pub resolved: Vec<Value>,
+    /// Resolution history, newest first, in the
+    /// `Conflicts.GetResolutionHistory` format.
+    pub history: Vec<Value>,
+    /// Whether `Conflicts.ResolveMany` exists, as on current daemons.
+    pub resolve_many_supported: bool,
+    /// The ids each `Resolve` (one id) or `ResolveMany` call was given.
+    pub resolve_calls: Vec<(&'static str, Vec<String>)>,
+    /// The default root's remote folder tree.
+    pub folder_tree: Value,
+    /// The default root's selected folders.
+    pub selected_folders: Vec<String>,
+    /// Hydration state of local folders, by absolute path, in the
+    /// `Files.GetHydrationStates` format.
+    pub hydration: HashMap<String, String>,
+    /// Bytes each local folder holds once downloaded, by absolute path.
+    pub folder_sizes: HashMap<String, u64>,
+    /// Who local folders are shared with, by absolute path, in the
+    /// `Files.GetSharedInfo` collaborator format.
+    pub collaborators: HashMap<String, Vec<Value>>,
+    /// The daemon log, oldest line first.
+    pub log_lines: Vec<String>,
+    /// In-flight transfers, in the `Status.GetActiveTransfers` format.
+    pub active_transfers: Vec<Value>,
+    /// Whether `Status.GetActiveTransfers` exists, as on current daemons.
+    pub transfers_supported: bool,
+    /// Items that failed to sync, in the `Sync.GetSyncErrors` format.
+    pub sync_errors: Vec<Value>,
+    /// `Status.PingRemote`: the round trip in milliseconds, or why OneDrive
+    /// could not be reached.
+    pub ping: Result<u32, String>,
+    /// Items deleted in the cloud, in the `Files.ListDeleted` format.
+    pub deleted: Vec<Value>,
+    /// Whether `Files.ListDeleted` exists, as on current daemons.
+    pub deleted_supported: bool,
+    /// `Status.GetLastError`: message and ISO 8601 time, both empty once
+    /// acknowledged.
+    pub last_error: (String, String),
+    /// The daemon configuration, as `Settings.GetConfig` returns it.
+    pub config: String,
+}
+
+impl Default for MockState {
+    fn default() -> Self {
+        Self {
+            account_type: "Personal".to_string(),
+            sync_status: "idle".to_string(),
+            quota: (5_368_709_120, 16_106_127_360),
+            conflicts: vec![
+                json!({
+                    "id": "conflict-001",
+                    "item_id": "item-budget",
+                    "item_path": format!("{SYNC_ROOT}/budget.xlsx"),
+                    "detected_at": "2026-02-07T10:30:00Z",
+                    "local_version": {
+                        "hash": "abc123def456",
+                        "size_bytes": 45_056,
+                        "modified_at": "2026-02-07T10:25:00Z",
+                    },
+                    "remote_version": {
+                        "hash": "789ghi012jkl",
+                        "size_bytes": 47_104,
+                        "modified_at": "2026-02-07T10:28:00Z",
+                    },
+                }),
+                json!({
+                    "id": "conflict-002",
+                    "item_id": "item-team-notes",
+                    "item_path": format!("{SYNC_ROOT}/shared/team-notes.docx"),
+                    "detected_at": "2026-02-07T11:00:00Z",
+                    "local_version": {
+                        "hash": "mno345pqr678",
+                        "size_bytes": 128_000,
+                        "modified_at": "2026-02-07T10:55:00Z",
+                    },
+                    "remote_version": {
+                        "hash": "stu901vwx234",
+                        "size_bytes": 130_048,
+                        "modified_at": "2026-02-07T10:58:00Z",
+                        "modified_by": "Alex Kim",
+                    },
+                }),
+            ],
+            resolved: Vec::new(),
+            history: Vec::new(),
+            resolve_many_supported: true,
+            resolve_calls: Vec::new(),
+            folder_tree: FOLDER_TREE_FIXTURE["folder_tree"].clone(),
+            selected_folders: FOLDER_TREE_FIXTURE["selected_folders"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|key| key.as_str().map(str::to_string))
+                .collect(),
+            hydration: HashMap::from([
+                (format!("{SYNC_ROOT}/Documents"), "hydrated".to_string()),
+                (format!("{SYNC_ROOT}/Photos"), "partial".to_string()),
+                (format!("{SYNC_ROOT}/Projects"), "cloud-only".to_string()),
+            ]),
+            folder_sizes: HashMap::from([
+                (format!("{SYNC_ROOT}/Documents"), 1_572_864),
+                (format!("{SYNC_ROOT}/Photos"), 734_003_200),
+                (format!("{SYNC_ROOT}/Projects"), 52_428_800),
+            ]),
+            collaborators: HashMap::from([(
+                format!("{SYNC_ROOT}/Photos"),
+                vec![
+                    json!({"name": "Alex Kim", "email": "alex@example.com"}),
+                    json!({"name": "Sam Lee", "email": "sam@example.com"}),
+                ],
+            )]),
+            log_lines: [
+                "2026-02-05T09:00:00Z INFO lnxdrive: daemon started",
+                "2026-02-05T09:00:01Z INFO auth: refresh_token: M.R3_BAY.mock-refresh-token",
+                "2026-02-05T09:00:02Z INFO sync: cycle started",
+                "2026-02-05T09:00:06Z INFO sync: cycle completed (12 files, 0 errors)",
+            ]
+            .map(String::from)
+            .to_vec(),
+            active_transfers: Vec::new(),
+            transfers_supported: true,
+            sync_errors: vec![
+                json!({
+                    "path": "/Videos/holiday.mp4",
+                    "message": "File is larger than the upload limit",
+                    "timestamp": "2026-02-07T09:30:00Z",
+                    "kind": "permanent",
+                    "failed_permanently": true,
+                }),
+                json!({
+                    "path": "/Documents/draft.docx",
+                    "message": "Network timeout",
+                    "timestamp": "2026-02-07T09:45:00Z",
+                    "kind": "transient",
+                    "failed_permanently": false,
+                }),
+            ],
+            ping: Ok(42),
+            deleted: vec![
+                json!({
+                    "id": "del-001",
+                    "path": "/Documents/old-report.docx",
+                    "deleted_at": "2026-02-06T10:00:00Z",
+                    "restorable": true,
+                }),
+                json!({
+                    "id": "del-002",
+                    "path": "/Photos/IMG_0042.jpg",
+                    "deleted_at": "2026-02-07T08:00:00Z",
+                    "restorable": true,
+                }),
+                // Gone from the cloud's recycle bin for good.
+                json!({
+                    "id": "del-003",
+                    "path": "/Archive/2019.zip",
+                    "deleted_at": "2026-01-01T12:00:00Z",
+                    "restorable": false,
+                }),
+            ],
+            deleted_supported: true,
+            last_error: (
+                "Could not reach OneDrive: connection reset".to_string(),
+                "2026-02-07T09:50:00Z".to_string(),
+            ),
+            config: format!("sync_root: \"{SYNC_ROOT}\"\n"),
+        }
+    }
+}
+
+impl MockState {
+    /// Resolve a conflict the way the daemon does: move it out of the list
+    /// and record the resolution. Returns false for an unknown id.
+    pub fn resolve(&mut self, id: &str, strategy: &str) -> bool {
+        let Some(index) = self.conflicts.iter().position(|c| c["id"] == id) else {
+            return false;
+        };
+        let mut conflict = self.conflicts.remove(index);
+        if strategy == "keep_both" {
+            // "budget.xlsx" is kept as "budget (conflicted copy).xlsx".
+            let path = conflict["item_path"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let renamed = match path.rsplit_once('.') {
+                Some((stem, ext)) => format!("{stem} (conflicted copy).{ext}"),
+                None => format!("{path} (conflicted copy)"),
+            };
+            conflict["renamed_copy"] = json!(renamed);
+        }
+        for record in self.history.iter_mut().filter(|r| r["id"] == id) {
+            record["reopenable"] = json!(false);
+        }
+        self.history.insert(
+            0,
+            json!({
+                "id": id,
+                "item_path": conflict["item_path"],
+                "strategy": strategy,
+                "resolved_at": "2026-02-07T12:00:00Z",
+                "reopenable": true,
+            }),
+        );
+        self.resolved.push(conflict);
+        true
+    }
+}
+
+type SharedState = Arc<Mutex<MockState>>;
+
+fn lock(state: &SharedState) -> MutexGuard<'_, MockState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+struct Status(SharedState);
+
+#[zbus::interface(name = "com.enigmora.LNXDrive.Status")]
+impl Status {
+    fn get_account_info(&self) -> HashMap<String, zvariant::Value<'static>> {
+        let account_type = lock(&self.0).account_type.clone();
+        HashMap::from([
+            (
+                "email".to_string(),
+                zvariant::Value::from("user@example.com"),
+            ),
+            (
+                "display_name".to_string(),
+                zvariant::Value::from("Test User"),
+            ),
+            (
+                "account_type".to_string(),
+                zvariant::Value::from(account_type),
+            ),
+        ])
+    }
+
+    fn get_quota(&self) -> (u64, u64) {
+        lock(&self.0).quota
+    }
+
+    fn get_last_error(&self) -> (String, String) {
+        lock(&self.0).last_error.clone()
+    }
+
+    fn clear_last_error(&self) {
+        lock(&self.0).last_error = Default::default();
+    }
+
+    fn ping_remote(&self) -> (bool, u32, String) {
+        match lock(&self.0).ping.clone() {
+            Ok(latency_ms) => (true, latency_ms, String::new()),
+            Err(error) => (false, 0, error),
+        }
+    }
+
+    fn get_active_transfers(&self) -> zbus::fdo::Result<String> {
+        let state = lock(&self.0);
+        if !state.transfers_supported {
+            return Err(zbus::fdo::Error::UnknownMethod(
+                "GetActiveTransfers".to_string(),
+            ));
+        }
+        Ok(Value::from(state.active_transfers.clone()).to_string())
+    }
+
+    fn get_transfer_stats(&self) -> HashMap<String, u64> {
+        HashMap::from([
+            ("session_uploaded".to_string(), 12_582_912),
+            ("session_downloaded".to_string(), 98_566_144),
+            ("total_uploaded".to_string(), 2_147_483_648),
+            ("total_downloaded".to_string(), 9_663_676_416),
+        ])
+    }
+}
+
+struct Sync(SharedState);
+
+#[zbus::interface(name = "com.enigmora.LNXDrive.Sync")]
+impl Sync {
+    #[zbus(property)]
+    fn sync_status(&self) -> String {
+        lock(&self.0).sync_status.clone()
+    }
+
+    fn get_sync_errors(&self) -> String {
+        Value::from(lock(&self.0).sync_errors.clone()).to_string()
+    }
+
+    /// Repairs the items the daemon had given up on.
+    fn verify_integrity(&self) -> u32 {
+        let mut state = lock(&self.0);
+        let before = state.sync_errors.len();
+        state
+            .sync_errors
+            .retain(|e| e["failed_permanently"] != true);
+        (before - state.sync_errors.len()) as u32
+    }
+
+    /// The retry succeeds, so the item is no longer listed.
+    fn retry_item(&self, path: &str) {
+        lock(&self.0).sync_errors.retain(|e| e["path"] != path);
+    }
+}
+
+struct Manager(SharedState);
+
+#[zbus::interface(name = "com.enigmora.LNXDrive.Manager")]
+impl Manager {
+    fn get_recent_logs(&self, lines: u32) -> Vec<String> {
+        let state = lock(&self.0);
+        let skip = state.log_lines.len().saturating_sub(lines as usize);
+        state.log_lines[skip..].to_vec()
+    }
+}
+
+struct Conflicts(SharedState);
+
+#[zbus::interface(name = "com.enigmora.LNXDrive.Conflicts")]
+impl Conflicts {
+    fn list(&self) -> String {
+        Value::from(lock(&self.0).conflicts.clone()).to_string()
+    }
+
+    fn get_details(&self, id: &str) -> zbus::fdo::Result<String> {
+        let state = lock(&self.0);
+        state
+            .conflicts
+            .iter()
+            .chain(&state.resolved)
+            .find(|c| c["id"] == id)
+            .map(|c| c.to_string())
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("No conflict {id}")))
+    }
+
+    fn resolve(&self, id: &str, strategy: &str) -> bool {
+        let mut state = lock(&self.0);
+        state.resolve_calls.push(("Resolve", vec![id.to_string()]));
+        state.resolve(id, strategy)
+    }
+
+    fn resolve_many(&self, ids: Vec<String>, strategy: &str) -> zbus::fdo::Result<u32> {
+        let mut state = lock(&self.0);
+        if !state.resolve_many_supported {
+            return Err(zbus::fdo::Error::UnknownMethod("ResolveMany".to_string()));
+        }
+        let resolved = ids.iter().filter(|id| state.resolve(id, strategy)).count();
+        state.resolve_calls.push(("ResolveMany", ids));
+        Ok(resolved as u32)
+    }
+
+    fn get_resolution_history(&self, limit: u32) -> String {
+        let state = lock(&self.0);
+        Value::from_iter(state.history.iter().take(limit as usize).cloned()).to_string()
+    }
+
+    fn reopen(&self, id: &str) -> bool {
+        let mut state = lock(&self.0);
+        let Some(index) = state.resolved.iter().position(|c| c["id"] == id) else {
+            return false;
+        };
+        let mut conflict = state.resolved.remove(index);
+        if let Some(fields) = conflict.as_object_mut() {
+            fields.remove("renamed_copy");
+        }
+        state.conflicts.push(conflict);
+        for record in state.history.iter_mut().filter(|r| r["id"] == id) {
+            record["reopenable"] = json!(false);
+        }
+        true
+    }
+}
+
+struct Settings(SharedState);
+
+#[zbus::interface(name = "com.enigmora.LNXDrive.Settings")]
+impl Settings {
+    fn get_config(&self) -> String {
+        lock(&self.0).config.clone()
+    }
+
+    fn set_config(&self, yaml: String) {
+        lock(&self.0).config = yaml;
+    }
+
+    fn get_sync_roots(&self) -> String {
+        json!([{"id": "default", "name": "OneDrive", "local_path": SYNC_ROOT}]).to_string()
+    }
+
+    fn get_root_remote_folder_tree(&self, _root_id: &str) -> String {
+        lock(&self.0).folder_tree.to_string()
+    }
+
+    fn get_root_selected_folders(&self, _root_id: &str) -> Vec<String> {
+        lock(&self.0).selected_folders.clone()
+    }
+
+    fn set_root_selected_folders(&self, _root_id: &str, folders: Vec<String>) {
+        lock(&self.0).selected_folders = folders;
+    }
+}
+
+struct Files(SharedState);
+
+#[zbus::interface(name = "com.enigmora.LNXDrive.Files")]
+impl Files {
+    fn get_hydration_states(&self, paths: Vec<String>) -> HashMap<String, String> {
+        let state = lock(&self.0);
+        paths
+            .into_iter()
+            .filter_map(|path| state.hydration.get(&path).map(|s| (path, s.clone())))
+            .collect()
+    }
+
+    /// Placeholders take no space, so only downloaded folders count.
+    fn get_folder_sizes(&self, paths: Vec<String>) -> HashMap<String, u64> {
+        let state = lock(&self.0);
+        paths
+            .into_iter()
+            .map(|path| {
+                let downloaded = state
+                    .hydration
+                    .get(&path)
+                    .is_some_and(|s| s != "cloud-only");
+                let size = state.folder_sizes.get(&path).copied().unwrap_or(0);
+                (path, if downloaded { size } else { 0 })
+            })
+            .collect()
+    }
+
+    fn list_deleted(&self) -> zbus::fdo::Result<String> {
+        let state = lock(&self.0);
+        if !state.deleted_supported {
+            return Err(zbus::fdo::Error::UnknownMethod("ListDeleted".to_string()));
+        }
+        Ok(Value::from(state.deleted.clone()).to_string())
+    }
+
+    fn restore_deleted(&self, id: &str) -> bool {
+        let mut state = lock(&self.0);
+        let restorable = |item: &Value| item["id"] == id && item["restorable"] == true;
+        let Some(index) = state.deleted.iter().position(restorable) else {
+            return false;
+        };
+        state.deleted.remove(index);
+        true
+    }
+
+    fn get_shared_info(&self, path: &str) -> String {
+        let collaborators = lock(&self.0)
+            .collaborators
+            .get(path)
+            .cloned()
+            .unwrap_or_default();
+        json!({"shared": !collaborators.is_empty(), "collaborators": collaborators}).to_string()
+    }
+
+    fn pin_file(&self, path: String) {
+        lock(&self.0).hydration.insert(path, "hydrated".to_string());
+    }
+
+    fn unpin_file(&self, path: String) {
+        lock(&self.0)
+            .hydration
+            .insert(path, "cloud-only".to_string());
+    }
+}
+
+/// Address of the private bus, started on first use. It is shared by the
+/// tests and stopped when the test process exits.
+fn test_bus_address() -> String {
+    thread_local! {
+        static TEST_BUS: gio::TestDBus = {
+            let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+            bus.up();
+            bus
+        };
+    }
+    TEST_BUS.with(|bus| bus.bus_address().expect("The test bus is up").to_string())
+}
+
+/// The mock daemon, owning the daemon's bus name on the test bus until it is
+/// dropped, and a client connected to it.
+pub struct TestDaemon {
+    state: SharedState,
+    service: zbus::Connection,
+    client: DbusClient,
+}
+
+impl TestDaemon {
+    pub fn start(state: MockState) -> Self {
+        let address = test_bus_address();
+        let state = Arc::new(Mutex::new(state));
+        let (service, client) = glib::MainContext::default()
+            .block_on(async {
+                let service = zbus::connection::Builder::address(address.as_str())?
+                    .name(BUS_NAME)?
+                    .serve_at(OBJECT_PATH, Status(state.clone()))?
+                    .serve_at(OBJECT_PATH, Manager(state.clone()))?
+                    .serve_at(OBJECT_PATH, Sync(state.clone()))?
+                    .serve_at(OBJECT_PATH, Conflicts(state.clone()))?
+                    .serve_at(OBJECT_PATH, Settings(state.clone()))?
+                    .serve_at(OBJECT_PATH, Files(state.clone()))?
+                    .build()
+                    .await?;
+                let connection = zbus::connection::Builder::address(address.as_str())?
+                    .build()
+                    .await?;
+                Ok::<_, zbus::Error>((service, DbusClient::from_connection(connection)))
+            })
+            .expect("Could not start the mock daemon");
+
+        Self {
+            state,
+            service,
+            client,
+        }
+    }
+
+    pub fn client(&self) -> &DbusClient {
+        &self.client
+    }
+
+    /// The daemon's state, e.g. the selection a widget saved.
+    pub fn state(&self) -> MutexGuard<'_, MockState> {
+        lock(&self.state)
+    }
+}
+
+impl Drop for TestDaemon {
+    fn drop(&mut self) {
+        // Free the name for the next test's daemon.
+        let _ = glib::MainContext::default().block_on(self.service.release_name(BUS_NAME));
+    }
+}
@@ -0,0 +1,113 @@
+// Utilities shared by the pages
+//
+// Coalescer turns a burst of triggers (e.g. D-Bus signals during a large
+// sync) into a single deferred action, so pages refresh once per burst
+// instead of once per signal. `confirmation_dialog` builds the AlertDialogs
+// that guard destructive actions, with the same keyboard behaviour
+// everywhere. `set_css_class` toggles a style class from a boolean setting.
+// `format_size` is the one way sizes are shown.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gettextrs::gettext;
+use gtk4::glib;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+/// An AlertDialog with a "cancel" response, to which the caller adds the
+/// action responses. Both Escape (close response) and Enter (default
+/// response) map to "cancel", so nothing destructive runs unless its button
+/// is chosen explicitly.
+pub fn confirmation_dialog(heading: &str, body: &str) -> adw::AlertDialog {
+    let dialog = adw::AlertDialog::builder()
+        .heading(heading)
+        .body(body)
+        .build();
+
+    dialog.add_response("cancel", &gettext("Cancel"));
+    dialog.set_default_response(Some("cancel"));
+    dialog.set_close_response("cancel");
+    dialog
+}
+
+/// A byte count such as "1.5 GiB", in binary (IEC) units to match file
+/// managers and OneDrive's own quota, with the user's locale for the
+/// number.
+pub fn format_size(bytes: u64) -> String {
+    glib::format_size_full(bytes, glib::FormatSizeFlags::IEC_UNITS).to_string()
+}
+
+/// Add or remove the style class `class` on `widget`.
+pub fn set_css_class(widget: &impl IsA<gtk4::Widget>, class: &str, enabled: bool) {
+    if enabled {
+        widget.add_css_class(class);
+    } else {
+        widget.remove_css_class(class);
+    }
+}
+
+/// Quiet period used for refreshes triggered by daemon signals.
+pub const SIGNAL_COALESCE_DELAY: Duration = Duration::from_millis(300);
+
+/// Runs an action once, `delay` after the first trigger; triggers that
+/// arrive while it is pending are dropped. Dropping the Coalescer cancels a
+/// pending action.
+pub struct Coalescer {
+    delay: Duration,
+    pending: Rc<RefCell<Option<glib::SourceId>>>,
+}
+
+impl Coalescer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Schedule `action` unless one is already pending.
+    pub fn trigger(&self, action: impl FnOnce() + 'static) {
+        if self.pending.borrow().is_some() {
+            return;
+        }
+
+        let pending = self.pending.clone();
+        let source = glib::timeout_add_local_once(self.delay, move || {
+            // The source is gone once it fires; forget it before running the
+            // action so the action may trigger again.
+            pending.borrow_mut().take();
+            action();
+        });
+        self.pending.replace(Some(source));
+    }
+}
+
+impl Drop for Coalescer {
+    fn drop(&mut self) {
+        if let Some(source) = self.pending.borrow_mut().take() {
+            source.remove();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// GLib separates number and unit with a no-break space in newer
+    /// versions and a plain one in older ones.
+    fn normalized(bytes: u64) -> String {
+        format_size(bytes).replace('\u{a0}', " ")
+    }
+
+    #[test]
+    fn format_size_uses_iec_units() {
+        assert_eq!(normalized(0), "0 bytes");
+        assert_eq!(normalized(1023), "1023 bytes");
+        assert_eq!(normalized(1024), "1.0 KiB");
+        assert_eq!(normalized(3 * 1024 * 1024 * 1024), "3.0 GiB");
+        assert_eq!(normalized(1536 * 1024 * 1024), "1.5 GiB");
+    }
+}
@@ -1,29 +1,93 @@
 // LNXDrive Main Window — adw::ApplicationWindow subclass
 //
 // Hosts either the onboarding wizard (NavigationView) or the preferences panel.
-// Persists window geometry via GSettings.
+// Persists window geometry via GSettings. Behind the preferences dialog the
+// window is a backdrop: a status page following the sync state, and banners
+// for an expired session, the network, a missing sync root and the daemon's
+// last error.
 
-use gettextrs::gettext;
+use futures_util::future::{AbortHandle, Abortable};
+use futures_util::StreamExt;
+use gettextrs::{gettext, ngettext};
 use gtk4::gio;
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita as adw;
 use libadwaita::prelude::*;
 
-use crate::dbus_client::DbusClient;
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
+
+use crate::auth_flow;
+use crate::config;
+use crate::diagnostics;
+use crate::conflicts::conflict_dialog::ConflictInfo;
+use crate::dbus_client::{DbusClient, LastError, LnxdriveConflictsProxy, LnxdriveSyncProxy};
+use crate::network;
+use crate::onboarding::folder_page::{choose_sync_folder, validate_sync_folder};
 use crate::onboarding::OnboardingView;
 use crate::preferences::PreferencesDialog;
+use crate::settings::{AppSettings, DEFAULT_WINDOW_SIZE};
+use crate::util::{self, Coalescer, SIGNAL_COALESCE_DELAY};
 
 mod imp {
     use super::*;
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
 
     use gtk4::subclass::prelude::*;
     use libadwaita::subclass::prelude::*;
 
     #[derive(Default)]
     pub struct LnxdriveWindow {
-        pub settings: RefCell<Option<gio::Settings>>,
+        /// Geometry persistence; `None` when the schema is not installed.
+        pub settings: RefCell<Option<AppSettings>>,
+        /// The PreferencesDialog currently presented over the window, if any.
+        pub preferences_dialog: glib::WeakRef<PreferencesDialog>,
+        /// "Your session expired" banner on the preferences backdrop.
+        pub session_banner: RefCell<Option<adw::Banner>>,
+        /// "You're offline" banner on the preferences backdrop.
+        pub offline_banner: RefCell<Option<adw::Banner>>,
+        /// "Sync is paused on this connection" banner on the preferences
+        /// backdrop.
+        pub connection_banner: RefCell<Option<adw::Banner>>,
+        /// "Sync folder not found" banner on the preferences backdrop.
+        pub missing_root_banner: RefCell<Option<adw::Banner>>,
+        /// Banner with the daemon's last unacknowledged error.
+        pub last_error_banner: RefCell<Option<adw::Banner>>,
+        /// NetworkMonitor "network-changed" and "notify::network-metered"
+        /// handlers driving the offline and connection banners.
+        pub network_handlers: RefCell<Vec<glib::SignalHandlerId>>,
+        /// Whether the config restricts syncing to Wi-Fi and ethernet.
+        pub wifi_ethernet_only: Cell<bool>,
+        /// The connection's type as of the last check, for the connection
+        /// banner.
+        pub connection_type: Cell<network::ConnectionType>,
+        /// True while sync is paused because of the connection type, so it
+        /// is resumed only if the window paused it.
+        pub paused_for_connection: Cell<bool>,
+        /// Set once the user confirmed closing during a sync, so the
+        /// following close goes through.
+        pub close_confirmed: Cell<bool>,
+        /// True while the inline re-authentication flow is running.
+        pub reauth_running: Cell<bool>,
+        /// Header title of the preferences backdrop; its subtitle carries
+        /// the sync/conflict summary.
+        pub window_title: RefCell<Option<adw::WindowTitle>>,
+        /// Subscription that keeps the title in sync with the daemon.
+        pub status_watch: RefCell<Option<AbortHandle>>,
+        /// Subscription to SyncProgress for the backdrop's percentage.
+        pub progress_watch: RefCell<Option<AbortHandle>>,
+        /// Status page on the preferences backdrop, reflecting SyncStatus.
+        pub status_page: RefCell<Option<adw::StatusPage>>,
+        /// Progress of the running sync, under the status page title.
+        pub status_progress: RefCell<Option<gtk4::ProgressBar>>,
+        /// SyncStatus last shown on the status page.
+        pub sync_status: RefCell<Option<String>>,
+        /// Percentage of the running sync, from the last SyncProgress.
+        pub sync_percent: Cell<Option<u32>>,
+        /// Toasts on the backdrop while the preferences dialog is closed.
+        pub toast_overlay: RefCell<Option<adw::ToastOverlay>>,
+        /// Set by onboarding until its first sync has been seen to finish.
+        pub initial_sync_pending: Cell<bool>,
     }
 
     #[glib::object_subclass]
@@ -40,28 +104,61 @@ mod imp {
             let obj = self.obj();
 
             // Load GSettings for window geometry persistence.
-            let settings = gio::Settings::new("com.enigmora.LNXDrive.Preferences");
+            let settings = AppSettings::installed();
 
-            let width = settings.int("window-width");
-            let height = settings.int("window-height");
+            let (width, height) = settings
+                .as_ref()
+                .map_or(DEFAULT_WINDOW_SIZE, AppSettings::window_size);
             obj.set_default_size(width, height);
 
-            *self.settings.borrow_mut() = Some(settings);
+            *self.settings.borrow_mut() = settings;
 
             obj.set_title(Some(&gettext("LNXDrive")));
         }
+
+        fn dispose(&self) {
+            if let Some(handle) = self.status_watch.borrow_mut().take() {
+                handle.abort();
+            }
+            if let Some(handle) = self.progress_watch.borrow_mut().take() {
+                handle.abort();
+            }
+            for handler in self.network_handlers.take() {
+                gio::NetworkMonitor::default().disconnect(handler);
+            }
+        }
     }
 
     impl WidgetImpl for LnxdriveWindow {}
 
     impl WindowImpl for LnxdriveWindow {
         fn close_request(&self) -> glib::Propagation {
+            let obj = self.obj();
+
             // Persist the current window size to GSettings.
             if let Some(ref settings) = *self.settings.borrow() {
-                let obj = self.obj();
                 let (width, height) = obj.default_size();
-                let _ = settings.set_int("window-width", width);
-                let _ = settings.set_int("window-height", height);
+                let _ = settings.set_window_size(width, height);
+            }
+
+            // While the app runs in the background (see `app`), closing
+            // only hides the window, so nothing needs confirming.
+            let in_background = obj
+                .application()
+                .and_downcast::<crate::app::LnxdriveApp>()
+                .is_some_and(|app| app.runs_in_background());
+            if in_background {
+                obj.set_visible(false);
+                return glib::Propagation::Stop;
+            }
+
+            if obj.is_syncing() && !self.close_confirmed.get() {
+                let win = obj.clone();
+                obj.confirm_quit_during_sync(move || {
+                    win.imp().close_confirmed.set(true);
+                    win.close();
+                });
+                return glib::Propagation::Stop;
             }
 
             self.parent_close_request()
@@ -86,22 +183,34 @@ impl LnxdriveWindow {
             .build()
     }
 
-    /// Replace the window content with the onboarding wizard.
-    pub fn show_onboarding(&self, dbus_client: DbusClient) {
-        let onboarding = OnboardingView::new(dbus_client, self.clone());
+    /// Replace the window content with the onboarding wizard. Once it
+    /// completes, preferences open on `initial_page` if set.
+    pub fn show_onboarding(&self, dbus_client: DbusClient, initial_page: Option<&str>) {
+        self.imp().session_banner.replace(None);
+        self.imp().missing_root_banner.replace(None);
+        self.imp().last_error_banner.replace(None);
+        self.stop_status_watch();
+        self.stop_network_watch();
+        let onboarding = OnboardingView::new(dbus_client, self.clone(), initial_page);
         self.set_content(Some(&onboarding));
     }
 
-    /// Set the window content to a "connected" status page and present the
-    /// preferences dialog on top. The underlying window content acts as the
-    /// backdrop while the PreferencesDialog is open.
+    /// Set the window content to a status page following the sync state and
+    /// present the preferences dialog on top. The underlying window content
+    /// acts as the backdrop while the PreferencesDialog is open.
     /// If `initial_page` is set, navigate directly to that page.
     pub fn show_preferences(&self, dbus_client: &DbusClient, initial_page: Option<&str>) {
         // Set up window content behind the dialog.
+        // Filled in by the status watch once SyncStatus has been read.
         let status = adw::StatusPage::builder()
-            .icon_name("emblem-ok-symbolic")
+            .icon_name("emblem-synchronizing-symbolic")
             .title(&gettext("LNXDrive"))
-            .description(&gettext("Your OneDrive files are syncing."))
+            .build();
+
+        let status_progress = gtk4::ProgressBar::builder()
+            .halign(gtk4::Align::Center)
+            .width_request(240)
+            .visible(false)
             .build();
 
         // Add a button to re-open preferences if the dialog is closed.
@@ -110,29 +219,818 @@ impl LnxdriveWindow {
             .halign(gtk4::Align::Center)
             .css_classes(["pill"])
             .build();
-        status.set_child(Some(&open_prefs_button));
+
+        let status_box = gtk4::Box::new(gtk4::Orientation::Vertical, 24);
+        status_box.append(&status_progress);
+        status_box.append(&open_prefs_button);
+        status.set_child(Some(&status_box));
+
+        let imp = self.imp();
+        imp.status_page.replace(Some(status.clone()));
+        imp.status_progress.replace(Some(status_progress));
+        imp.sync_status.replace(None);
+        imp.sync_percent.set(None);
+
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&status));
+        imp.toast_overlay.replace(Some(toast_overlay.clone()));
+
+        // Revealed when the token expires; offers inline re-authentication.
+        let session_banner = adw::Banner::builder()
+            .title(&gettext("Your session expired."))
+            .button_label(&gettext("Sign In Again"))
+            .revealed(false)
+            .build();
+        self.imp().session_banner.replace(Some(session_banner.clone()));
+
+        let offline_banner = adw::Banner::builder()
+            .title(&gettext("You're offline — changes will sync when reconnected"))
+            .revealed(false)
+            .build();
+        self.imp().offline_banner.replace(Some(offline_banner.clone()));
+
+        let connection_banner = adw::Banner::builder()
+            .title(&gettext(
+                "Sync is paused on this connection — LNXDrive only syncs over Wi-Fi or Ethernet",
+            ))
+            .button_label(&gettext("Change..."))
+            .revealed(false)
+            .build();
+        self.imp()
+            .connection_banner
+            .replace(Some(connection_banner.clone()));
+
+        let missing_root_banner = adw::Banner::builder()
+            .button_label(&gettext("Choose Folder..."))
+            .revealed(false)
+            .build();
+        self.imp()
+            .missing_root_banner
+            .replace(Some(missing_root_banner.clone()));
+
+        let last_error_banner = adw::Banner::builder()
+            .button_label(&gettext("Dismiss"))
+            .revealed(false)
+            .build();
+        self.imp()
+            .last_error_banner
+            .replace(Some(last_error_banner.clone()));
+
+        let window_title = adw::WindowTitle::new(&gettext("LNXDrive"), "");
+        self.imp().window_title.replace(Some(window_title.clone()));
+        let header_bar = adw::HeaderBar::builder()
+            .title_widget(&window_title)
+            .build();
+
+        // "Refresh" (F5) re-reads every preferences page from the daemon;
+        // "Report a Problem" shows a redacted diagnostics report.
+        let menu = gio::Menu::new();
+        menu.append(Some(&gettext("Refresh")), Some("win.refresh"));
+        menu.append(Some(&gettext("Report a Problem...")), Some("win.report-problem"));
+        let menu_button = gtk4::MenuButton::builder()
+            .icon_name("open-menu-symbolic")
+            .tooltip_text(&gettext("Main Menu"))
+            .menu_model(&menu)
+            .primary(true)
+            .build();
+        header_bar.pack_end(&menu_button);
+
+        // Replaces the action of a previous preferences session, so it always
+        // reports on the current client.
+        let report_action = gio::SimpleAction::new("report-problem", None);
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        report_action.connect_activate(move |_, _| {
+            if let Some(win) = win.upgrade() {
+                win.report_problem(&client);
+            }
+        });
+        self.add_action(&report_action);
+
+        let refresh_action = gio::SimpleAction::new("refresh", None);
+        let win = self.downgrade();
+        refresh_action.connect_activate(move |_, _| {
+            let dialog = win
+                .upgrade()
+                .and_then(|win| win.imp().preferences_dialog.upgrade());
+            if let Some(dialog) = dialog {
+                dialog.reload_all();
+            }
+        });
+        self.add_action(&refresh_action);
 
         let toolbar_view = adw::ToolbarView::new();
-        toolbar_view.add_top_bar(&adw::HeaderBar::new());
-        toolbar_view.set_content(Some(&status));
+        toolbar_view.add_top_bar(&header_bar);
+        toolbar_view.add_top_bar(&session_banner);
+        toolbar_view.add_top_bar(&offline_banner);
+        toolbar_view.add_top_bar(&connection_banner);
+        toolbar_view.add_top_bar(&missing_root_banner);
+        toolbar_view.add_top_bar(&last_error_banner);
+        toolbar_view.set_content(Some(&toast_overlay));
 
         self.set_content(Some(&toolbar_view));
 
+        let client = dbus_client.clone();
+        let win = self.clone();
+        session_banner.connect_button_clicked(move |_| {
+            win.reauthenticate(&client);
+        });
+
+        let client = dbus_client.clone();
+        let win = self.clone();
+        connection_banner.connect_button_clicked(move |_| {
+            win.present_preferences_dialog(&client, Some("advanced"));
+        });
+
+        let client = dbus_client.clone();
+        let win = self.clone();
+        missing_root_banner.connect_button_clicked(move |_| {
+            win.replace_sync_root(&client);
+        });
+
+        // Dismissing the last error acknowledges it on the daemon.
+        let client = dbus_client.clone();
+        last_error_banner.connect_button_clicked(move |banner| {
+            banner.set_revealed(false);
+            let client = client.clone();
+            glib::MainContext::default().spawn_local(async move {
+                if let Err(e) = client.clear_last_error().await {
+                    eprintln!("Could not acknowledge the daemon error: {e}");
+                }
+            });
+        });
+
         // Connect the button to re-open preferences.
         let client = dbus_client.clone();
         let win = self.clone();
         open_prefs_button.connect_clicked(move |_| {
-            let dialog = PreferencesDialog::new(&client, None);
-            dialog.present(&win);
+            win.present_preferences_dialog(&client, None);
         });
 
         // Present the dialog immediately.
+        self.present_preferences_dialog(dbus_client, initial_page);
+
+        self.watch_status(dbus_client);
+        self.watch_network(dbus_client);
+        self.check_sync_root(dbus_client);
+    }
+
+    // -- Missing sync root ---------------------------------------------------
+
+    /// Read `sync_root` from the config and stat it. `None` if the config
+    /// can't be read or sets no sync root; otherwise the path and whether it
+    /// exists.
+    async fn sync_root_exists(client: &DbusClient) -> Option<(String, bool)> {
+        let yaml = client.get_config().await.ok()?;
+        let path = config::top_level_value(&yaml, "sync_root")?;
+        let exists = std::path::Path::new(&path).is_dir();
+        Some((path, exists))
+    }
+
+    /// If the sync root has gone missing, close preferences and offer to
+    /// choose a new folder instead of letting sync fail silently.
+    fn check_sync_root(&self, dbus_client: &DbusClient) {
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let Some((path, false)) = Self::sync_root_exists(&client).await else {
+                return;
+            };
+            let Some(win) = win.upgrade() else { return };
+            let Some(banner) = win.imp().missing_root_banner.borrow().clone() else {
+                return;
+            };
+
+            if let Some(dialog) = win.imp().preferences_dialog.upgrade() {
+                dialog.force_close();
+            }
+            banner.set_title(
+                &gettext("The sync folder {} can't be found. It may have been deleted or be on a drive that isn't connected.")
+                    .replace("{}", &path),
+            );
+            banner.set_revealed(true);
+        });
+    }
+
+    /// Pick a replacement sync folder, save it and reopen preferences.
+    fn replace_sync_root(&self, dbus_client: &DbusClient) {
+        let client = dbus_client.clone();
+        let win = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let home = glib::home_dir();
+            let Some(path) = choose_sync_folder(Some(win.upcast_ref()), &home).await else {
+                return;
+            };
+            let Some(banner) = win.imp().missing_root_banner.borrow().clone() else {
+                return;
+            };
+            if let Err(reason) = validate_sync_folder(&path) {
+                banner.set_title(&reason);
+                return;
+            }
+
+            let yaml = config::string_setting("sync_root", &path.display().to_string());
+            match client.set_config(&yaml).await {
+                Ok(()) => {
+                    banner.set_revealed(false);
+                    win.present_preferences_dialog(&client, Some("sync"));
+                }
+                Err(e) => {
+                    banner.set_title(&format!(
+                        "{}: {}",
+                        gettext("Could not change the sync folder"),
+                        e
+                    ));
+                }
+            }
+        });
+    }
+
+    /// Follow the system's network state for the offline banner and the
+    /// connection-type restriction, pausing sync on a connection it rules
+    /// out.
+    fn watch_network(&self, dbus_client: &DbusClient) {
+        self.stop_network_handlers();
+        let monitor = gio::NetworkMonitor::default();
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        let changed = monitor.connect_network_changed(move |_, _| {
+            if let Some(win) = win.upgrade() {
+                win.check_connection_type(&client);
+            }
+        });
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        let metered = monitor.connect_network_metered_notify(move |_| {
+            if let Some(win) = win.upgrade() {
+                win.check_connection_type(&client);
+            }
+        });
+        self.imp().network_handlers.replace(vec![changed, metered]);
+        self.update_network_banners();
+
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let wifi_ethernet_only = match client.get_config().await {
+                Ok(yaml) => network::wifi_ethernet_only(&yaml),
+                Err(e) => {
+                    eprintln!("Could not read the allowed connection types: {e}");
+                    return;
+                }
+            };
+            if let Some(win) = win.upgrade() {
+                win.set_wifi_ethernet_only(&client, wifi_ethernet_only);
+            }
+        });
+    }
+
+    fn stop_network_watch(&self) {
+        self.stop_network_handlers();
+        let imp = self.imp();
+        imp.offline_banner.replace(None);
+        imp.connection_banner.replace(None);
+    }
+
+    fn stop_network_handlers(&self) {
+        let monitor = gio::NetworkMonitor::default();
+        for handler in self.imp().network_handlers.take() {
+            monitor.disconnect(handler);
+        }
+    }
+
+    /// The "Only Sync on Wi-Fi or Ethernet" setting changed (or was read
+    /// from the config): pause or resume for the current connection.
+    pub fn set_wifi_ethernet_only(&self, dbus_client: &DbusClient, enabled: bool) {
+        self.imp().wifi_ethernet_only.set(enabled);
+        self.check_connection_type(dbus_client);
+    }
+
+    /// Find out whether the restriction rules out the current connection,
+    /// show or hide the connection banner accordingly and pause or resume.
+    fn check_connection_type(&self, dbus_client: &DbusClient) {
+        self.update_network_banners();
+        if !self.imp().wifi_ethernet_only.get() {
+            self.imp()
+                .connection_type
+                .set(network::ConnectionType::Allowed);
+            self.update_network_banners();
+            self.apply_connection_pause(dbus_client, false);
+            return;
+        }
+
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let connection_type = if gio::NetworkMonitor::default().is_network_available() {
+                network::connection_type().await
+            } else {
+                network::ConnectionType::Allowed
+            };
+            if let Some(win) = win.upgrade() {
+                win.imp().connection_type.set(connection_type);
+                win.update_network_banners();
+                // A metered connection of unknown type only gets the banner.
+                win.apply_connection_pause(
+                    &client,
+                    connection_type == network::ConnectionType::Mobile,
+                );
+            }
+        });
+    }
+
+    /// Pause sync on a connection the restriction rules out and resume it
+    /// once the connection is allowed again. A sync the user paused is left
+    /// alone in both directions.
+    fn apply_connection_pause(&self, dbus_client: &DbusClient, disallowed: bool) {
+        let imp = self.imp();
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        if disallowed && !imp.paused_for_connection.get() {
+            glib::MainContext::default().spawn_local(async move {
+                if client.get_sync_status().await.ok().as_deref() == Some("paused") {
+                    return;
+                }
+                match client.pause().await {
+                    Ok(()) => {
+                        if let Some(win) = win.upgrade() {
+                            win.imp().paused_for_connection.set(true);
+                        }
+                    }
+                    Err(e) => eprintln!("Could not pause sync on this connection: {e}"),
+                }
+            });
+        } else if !disallowed && imp.paused_for_connection.replace(false) {
+            glib::MainContext::default().spawn_local(async move {
+                if let Err(e) = client.resume().await {
+                    eprintln!("Could not resume sync on this connection: {e}");
+                }
+            });
+        }
+    }
+
+    /// Reveal the offline banner when there is no network and the
+    /// connection banner when the connection type is not allowed, unless the
+    /// session banner is already showing.
+    fn update_network_banners(&self) {
+        let imp = self.imp();
+        let session_shown = imp
+            .session_banner
+            .borrow()
+            .as_ref()
+            .is_some_and(|b| b.is_revealed());
+
+        if let Some(ref banner) = *imp.offline_banner.borrow() {
+            let offline = !gio::NetworkMonitor::default().is_network_available();
+            banner.set_revealed(offline && !session_shown);
+        }
+        if let Some(ref banner) = *imp.connection_banner.borrow() {
+            let connection_type = imp.connection_type.get();
+            if connection_type == network::ConnectionType::Unknown {
+                banner.set_title(&gettext(
+                    "Sync may be paused — LNXDrive only syncs over Wi-Fi or Ethernet and can't tell whether this metered connection is mobile data",
+                ));
+            } else {
+                banner.set_title(&gettext(
+                    "Sync is paused on this connection — LNXDrive only syncs over Wi-Fi or Ethernet",
+                ));
+            }
+            let restricted = connection_type != network::ConnectionType::Allowed;
+            banner.set_revealed(imp.wifi_ethernet_only.get() && restricted && !session_shown);
+        }
+    }
+
+    /// Keep the title up to date: re-read the sync status and the conflict
+    /// count whenever SyncStatus changes or a conflict is detected/resolved,
+    /// once per burst of signals.
+    fn watch_status(&self, dbus_client: &DbusClient) {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        if let Some(previous) = self.imp().status_watch.replace(Some(abort_handle)) {
+            previous.abort();
+        }
+
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let _ = Abortable::new(async move {
+                let connection = client.connection().clone();
+                let (sync_proxy, conflicts_proxy) = match (
+                    LnxdriveSyncProxy::new(&connection).await,
+                    LnxdriveConflictsProxy::new(&connection).await,
+                ) {
+                    (Ok(s), Ok(c)) => (s, c),
+                    (Err(e), _) | (_, Err(e)) => {
+                        eprintln!("Could not create proxies for the window title: {e}");
+                        return;
+                    }
+                };
+
+                let status_changes = sync_proxy.receive_sync_status_changed().await;
+                let detected = match conflicts_proxy.receive_conflict_detected().await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("Could not subscribe to ConflictDetected: {e}");
+                        return;
+                    }
+                };
+                let resolved = match conflicts_proxy.receive_conflict_resolved().await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("Could not subscribe to ConflictResolved: {e}");
+                        return;
+                    }
+                };
+
+                let mut updates = futures_util::stream::select(
+                    status_changes.map(|_| ()),
+                    futures_util::stream::select(
+                        detected.map(|_| ()),
+                        resolved.map(|_| ()),
+                    ),
+                );
+
+                match win.upgrade() {
+                    Some(win) => win.refresh_status(&client),
+                    None => return,
+                }
+
+                // A large sync can emit many signals per second; refresh
+                // once per burst.
+                let coalescer = Coalescer::new(SIGNAL_COALESCE_DELAY);
+                while updates.next().await.is_some() {
+                    let Some(win) = win.upgrade() else { break };
+                    let client = client.clone();
+                    coalescer.trigger(move || win.refresh_status(&client));
+                }
+            }, abort_registration).await;
+        });
+
+        self.watch_progress(dbus_client);
+    }
+
+    /// Re-read the sync status, the conflict count and the last error, and
+    /// update the title, the error banner and the status page.
+    fn refresh_status(&self, client: &DbusClient) {
+        let client = client.clone();
+        let win = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let status = client.get_sync_status().await.ok();
+            let conflicts = client
+                .list_conflicts()
+                .await
+                .ok()
+                .map(|json| ConflictInfo::from_json_array(&json).len());
+            // Older daemons lack the method; the banner then stays hidden.
+            let last_error = client.get_last_error().await.ok().flatten();
+
+            let Some(win) = win.upgrade() else { return };
+            win.update_title(status.as_deref(), conflicts.unwrap_or(0));
+            win.show_last_error(status.as_deref(), last_error.as_ref());
+            win.update_status_page(status.as_deref());
+            win.check_initial_sync(status.as_deref());
+        });
+    }
+
+    /// Follow SyncProgress for the percentage on the status page.
+    fn watch_progress(&self, dbus_client: &DbusClient) {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        if let Some(previous) = self.imp().progress_watch.replace(Some(abort_handle)) {
+            previous.abort();
+        }
+
+        let client = dbus_client.clone();
+        let win = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            let _ = Abortable::new(async move {
+                let mut progress = match client.receive_sync_progress().await {
+                    Ok(p) => Box::pin(p),
+                    Err(e) => {
+                        eprintln!("Could not subscribe to SyncProgress: {e}");
+                        return;
+                    }
+                };
+                while let Some((_file, current, total)) = progress.next().await {
+                    let Some(win) = win.upgrade() else { break };
+                    // In u64, since counts past 42 million would overflow.
+                    let percent =
+                        (u64::from(current.min(total)) * 100).checked_div(u64::from(total));
+                    if let Some(percent) = percent {
+                        // At most 100.
+                        win.imp().sync_percent.set(Some(percent as u32));
+                        let status = win.imp().sync_status.borrow().clone();
+                        win.update_status_page(status.as_deref());
+                    }
+                }
+            }, abort_registration).await;
+        });
+    }
+
+    /// Show `sync_status` on the backdrop's status page. The percentage is
+    /// only kept while syncing, so the next sync starts from zero.
+    fn update_status_page(&self, sync_status: Option<&str>) {
+        let imp = self.imp();
+        imp.sync_status.replace(sync_status.map(str::to_string));
+        if sync_status != Some("syncing") {
+            imp.sync_percent.set(None);
+        }
+        let Some(status) = imp.status_page.borrow().clone() else {
+            return;
+        };
+
+        let (icon, title, description) = match sync_status {
+            Some("idle") => (
+                "emblem-ok-symbolic",
+                gettext("Up to Date"),
+                gettext("Your OneDrive files are up to date."),
+            ),
+            Some("syncing") => (
+                "emblem-synchronizing-symbolic",
+                match imp.sync_percent.get() {
+                    Some(percent) => {
+                        gettext("Syncing… ({}%)").replace("{}", &percent.to_string())
+                    }
+                    None => gettext("Syncing…"),
+                },
+                gettext("Your OneDrive files are syncing."),
+            ),
+            Some("rate_limited") => (
+                "emblem-synchronizing-symbolic",
+                gettext("Waiting for OneDrive"),
+                gettext("OneDrive is throttling requests. Sync will resume on its own."),
+            ),
+            Some("paused") => (
+                "media-playback-pause-symbolic",
+                gettext("Paused"),
+                gettext("Changes will sync when you resume."),
+            ),
+            Some("error") => (
+                "dialog-error-symbolic",
+                gettext("Error"),
+                gettext("Sync stopped because of an error."),
+            ),
+            _ => ("emblem-synchronizing-symbolic", gettext("LNXDrive"), String::new()),
+        };
+        status.set_icon_name(Some(icon));
+        status.set_title(&title);
+        status.set_description(Some(&description));
+
+        if let Some(ref bar) = *imp.status_progress.borrow() {
+            let percent = imp.sync_percent.get().filter(|_| sync_status == Some("syncing"));
+            bar.set_visible(percent.is_some());
+            bar.set_fraction(f64::from(percent.unwrap_or(0)) / 100.0);
+        }
+    }
+
+    /// Reveal the last-error banner for `error`, or hide it. Rate limiting
+    /// (SyncStatus "rate_limited", or an error with a Retry-After) gets its
+    /// own wording with the time sync resumes; once that time has passed the
+    /// error is no longer shown.
+    fn show_last_error(&self, sync_status: Option<&str>, error: Option<&LastError>) {
+        let Some(banner) = self.imp().last_error_banner.borrow().clone() else {
+            return;
+        };
+
+        let retry_after = error.and_then(LastError::retry_after);
+        let retry_pending = retry_after
+            .as_ref()
+            .zip(glib::DateTime::now_utc().ok())
+            .is_some_and(|(retry, now)| retry > &now);
+
+        let title = if retry_pending || sync_status == Some("rate_limited") {
+            let resume_time = retry_after
+                .filter(|_| retry_pending)
+                .and_then(|dt| dt.to_local().ok())
+                .and_then(|dt| dt.format("%H:%M").ok());
+            Some(match resume_time {
+                Some(time) => gettext("OneDrive is throttling requests — sync will resume at {}")
+                    .replace("{}", &time),
+                None => gettext("OneDrive is throttling requests — sync will resume shortly"),
+            })
+        } else {
+            // A rate limit that has run out is over, whether or not it was
+            // acknowledged.
+            error.filter(|_| retry_after.is_none()).map(|error| {
+                gettext("{message} ({time})")
+                    .replace("{message}", &error.message)
+                    .replace("{time}", &error.occurred_absolute())
+            })
+        };
+
+        match title {
+            Some(title) => {
+                banner.set_title(&title);
+                banner.set_revealed(true);
+            }
+            None => banner.set_revealed(false),
+        }
+    }
+
+    /// Report the first sync started by onboarding once the daemon is idle
+    /// again. Call before `show_preferences`.
+    pub fn expect_initial_sync(&self) {
+        self.imp().initial_sync_pending.set(true);
+    }
+
+    fn check_initial_sync(&self, sync_status: Option<&str>) {
+        let imp = self.imp();
+        if !imp.initial_sync_pending.get() || sync_status != Some("idle") {
+            return;
+        }
+        imp.initial_sync_pending.set(false);
+
+        let toast = adw::Toast::new(&gettext("Initial sync complete"));
+        match imp.preferences_dialog.upgrade() {
+            Some(dialog) => dialog.add_toast(toast),
+            None => {
+                if let Some(ref overlay) = *imp.toast_overlay.borrow() {
+                    overlay.add_toast(toast);
+                }
+            }
+        }
+    }
+
+    fn stop_status_watch(&self) {
+        let imp = self.imp();
+        if let Some(handle) = imp.status_watch.borrow_mut().take() {
+            handle.abort();
+        }
+        if let Some(handle) = imp.progress_watch.borrow_mut().take() {
+            handle.abort();
+        }
+        imp.initial_sync_pending.set(false);
+        imp.window_title.replace(None);
+        self.set_title(Some(&gettext("LNXDrive")));
+    }
+
+    /// Show "Syncing…" or the number of unresolved conflicts (which takes
+    /// precedence) next to the app name; plain "LNXDrive" when idle.
+    fn update_title(&self, sync_status: Option<&str>, conflicts: usize) {
+        let summary = if conflicts > 0 {
+            ngettext("{} conflict", "{} conflicts", conflicts as u32)
+                .replace("{}", &conflicts.to_string())
+        } else if sync_status == Some("syncing") {
+            gettext("Syncing…")
+        } else {
+            String::new()
+        };
+
+        if summary.is_empty() {
+            self.set_title(Some(&gettext("LNXDrive")));
+        } else {
+            self.set_title(Some(&gettext("LNXDrive — {}").replace("{}", &summary)));
+        }
+
+        if let Some(ref title) = *self.imp().window_title.borrow() {
+            title.set_subtitle(&summary);
+        }
+    }
+
+    /// Whether the daemon last reported a sync in progress.
+    pub fn is_syncing(&self) -> bool {
+        self.imp().sync_status.borrow().as_deref() == Some("syncing")
+    }
+
+    /// Ask "Sync in progress — quit anyway?" and call `on_confirm` if the
+    /// user quits.
+    pub fn confirm_quit_during_sync(&self, on_confirm: impl Fn() + 'static) {
+        let confirm = util::confirmation_dialog(
+            &gettext("Sync in progress — quit anyway?"),
+            &gettext("Some files are still being synced."),
+        );
+        confirm.add_response("quit", &gettext("Quit"));
+        confirm.set_response_appearance("quit", adw::ResponseAppearance::Destructive);
+        confirm.connect_response(None, move |_dialog, response| {
+            if response == "quit" {
+                on_confirm();
+            }
+        });
+        adw::prelude::AdwDialogExt::present(&confirm, Some(self));
+    }
+
+    fn present_preferences_dialog(&self, dbus_client: &DbusClient, initial_page: Option<&str>) {
         let dialog = PreferencesDialog::new(dbus_client, initial_page);
+        self.imp().preferences_dialog.set(Some(&dialog));
         dialog.present(self);
     }
 
+    /// The daemon reported that the account is signed out (elsewhere, or the
+    /// token was revoked): close preferences and restart onboarding. Does
+    /// nothing if onboarding is already showing, since AuthPage handles auth
+    /// errors itself.
+    pub fn on_signed_out(&self, dbus_client: &DbusClient) {
+        if self.content().and_downcast::<OnboardingView>().is_some() {
+            return;
+        }
+
+        if let Some(dialog) = self.imp().preferences_dialog.upgrade() {
+            dialog.force_close();
+        }
+
+        self.show_onboarding(dbus_client.clone(), None);
+    }
+
+    /// The daemon reported that the token expired. Close preferences and
+    /// reveal the "session expired" banner instead of restarting onboarding,
+    /// so the sync configuration is left untouched. (The banner lives on the
+    /// window because AdwPreferencesDialog has no banner slot before
+    /// libadwaita 1.7.)
+    pub fn on_session_expired(&self, dbus_client: &DbusClient) {
+        let imp = self.imp();
+
+        // Our own sign-in attempt reports failures itself.
+        if imp.reauth_running.get() {
+            return;
+        }
+
+        let banner = match imp.session_banner.borrow().clone() {
+            Some(b) => b,
+            None => {
+                // Not showing preferences (e.g. the D-Bus error page):
+                // there is no session to resume.
+                self.on_signed_out(dbus_client);
+                return;
+            }
+        };
+
+        if let Some(dialog) = imp.preferences_dialog.upgrade() {
+            dialog.force_close();
+        }
+
+        banner.set_title(&gettext("Your session expired."));
+        banner.set_button_label(Some(&gettext("Sign In Again")));
+        banner.set_revealed(true);
+        self.update_network_banners();
+    }
+
+    /// Run the sign-in flow from the "session expired" banner and reopen
+    /// preferences once the daemon is authenticated again.
+    fn reauthenticate(&self, dbus_client: &DbusClient) {
+        let imp = self.imp();
+        if imp.reauth_running.replace(true) {
+            return;
+        }
+
+        let banner = match imp.session_banner.borrow().clone() {
+            Some(b) => b,
+            None => return,
+        };
+        banner.set_title(&gettext("Waiting for authentication..."));
+        banner.set_button_label(None);
+
+        let client = dbus_client.clone();
+        let win = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let link_banner = banner.clone();
+            let on_auth_url = move |auth_url: &str, opened: bool| {
+                if opened {
+                    return;
+                }
+                // No room for a copy row on a banner: put the link on the
+                // clipboard and say so.
+                link_banner.clipboard().set_text(auth_url);
+                link_banner.set_title(&gettext(
+                    "Could not open browser. The sign-in link was copied to the clipboard.",
+                ));
+            };
+            let result = auth_flow::sign_in(&client, Some(win.upcast_ref()), on_auth_url).await;
+
+            win.imp().reauth_running.set(false);
+
+            match result {
+                Ok(()) => {
+                    banner.set_revealed(false);
+                    win.update_network_banners();
+                    win.present_preferences_dialog(&client, None);
+                }
+                Err(e) => {
+                    banner.set_title(&e.to_string());
+                    banner.set_button_label(Some(&gettext("Try Again")));
+                }
+            }
+        });
+    }
+
+    /// Collect the diagnostics report and show it over whatever is on top
+    /// (the preferences dialog, if open).
+    fn report_problem(&self, dbus_client: &DbusClient) {
+        let client = dbus_client.clone();
+        let win = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let report = diagnostics::collect(&client).await;
+            match win.imp().preferences_dialog.upgrade() {
+                Some(dialog) => diagnostics::present_report(&dialog, report),
+                None => diagnostics::present_report(&win, report),
+            }
+        });
+    }
+
     /// Show an error status page when the D-Bus daemon is unreachable.
     pub fn show_dbus_error(&self, message: &str) {
+        self.imp().session_banner.replace(None);
+        self.imp().missing_root_banner.replace(None);
+        self.imp().last_error_banner.replace(None);
+        self.stop_status_watch();
+        self.stop_network_watch();
         let status = adw::StatusPage::builder()
             .icon_name("dialog-error-symbolic")
             .title(&gettext("Cannot Connect to LNXDrive"))
@@ -146,3 +1044,55 @@ impl LnxdriveWindow {
         self.set_content(Some(&toolbar_view));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::{run_widget_test, wait_until, MockState, TestDaemon};
+
+    #[test]
+    fn shows_and_dismisses_the_last_error() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState::default());
+            let window: LnxdriveWindow = glib::Object::new();
+            window.show_preferences(daemon.client(), None);
+            window.present();
+            let banner = window.imp().last_error_banner.borrow().clone().unwrap();
+
+            wait_until("the last error", || banner.is_revealed());
+            assert!(banner
+                .title()
+                .starts_with("Could not reach OneDrive: connection reset"));
+
+            banner.emit_by_name::<()>("button-clicked", &[]);
+            assert!(!banner.is_revealed());
+            wait_until("the error to be acknowledged", || {
+                daemon.state().last_error.0.is_empty()
+            });
+
+            window.destroy();
+        });
+    }
+
+    #[test]
+    fn shows_the_sync_status_in_the_title() {
+        run_widget_test(|| {
+            let daemon = TestDaemon::start(MockState {
+                sync_status: "syncing".to_string(),
+                conflicts: Vec::new(),
+                ..MockState::default()
+            });
+            let window: LnxdriveWindow = glib::Object::new();
+            window.show_preferences(daemon.client(), None);
+            window.present();
+
+            let title = gettext("LNXDrive — {}").replace("{}", &gettext("Syncing…"));
+            wait_until("the sync status", || {
+                window.title().is_some_and(|t| t.as_str() == title)
+            });
+            assert!(window.is_syncing());
+
+            window.destroy();
+        });
+    }
+}
@@ -26,11 +26,15 @@ const SyncInterfaceXml = `
   <interface name="com.enigmora.LNXDrive.Sync">
     <method name="SyncNow"/>
     <method name="Pause"/>
+    <method name="PauseUntil">
+      <arg type="x" name="timestamp" direction="in"/>
+    </method>
     <method name="Resume"/>
 
     <property name="SyncStatus" type="s" access="read"/>
     <property name="LastSyncTime" type="x" access="read"/>
     <property name="PendingChanges" type="u" access="read"/>
+    <property name="PausedUntil" type="x" access="read"/>
 
     <signal name="SyncStarted"/>
     <signal name="SyncCompleted">
@@ -8,7 +8,8 @@
  * - Sync progress section (current file, percentage, pending count)
  * - Conflicts section (conflict count updated via signals)
  * - Quota section (used/total with visual progress bar)
- * - Actions section (Pause/Resume, Sync Now, Preferences)
+ * - Actions section (Pause/Resume, Pause For 1 hour / 4 hours / until
+ *   tomorrow, Sync Now, Preferences)
  *
  * Implements: FR-010, FR-011, FR-026
  */
@@ -65,6 +66,34 @@ function _formatLastSyncTime(timestamp, _) {
     return `${days} ${days !== 1 ? _('days ago') : _('day ago')}`;
 }
 
+/**
+ * Format the Unix time a timed pause ends at: "14:30" today, with the
+ * weekday ("Tue 00:00") on another day.
+ *
+ * @param {number} timestamp - Unix timestamp in seconds.
+ * @returns {string} Local resume time.
+ */
+function _formatResumeTime(timestamp) {
+    const resume = GLib.DateTime.new_from_unix_local(timestamp);
+    const now = GLib.DateTime.new_now_local();
+    const sameDay = resume.get_year() === now.get_year() &&
+        resume.get_day_of_year() === now.get_day_of_year();
+
+    return resume.format(sameDay ? '%H:%M' : '%a %H:%M');
+}
+
+/**
+ * Unix time of the next local midnight, for "Until Tomorrow".
+ *
+ * @returns {number} Unix timestamp in seconds.
+ */
+function _startOfTomorrow() {
+    const now = GLib.DateTime.new_now_local();
+    return GLib.DateTime.new_local(
+        now.get_year(), now.get_month(), now.get_day_of_month(), 0, 0, 0,
+    ).add_days(1).to_unix();
+}
+
 /**
  * Build the complete dropdown menu for the LNXDrive indicator.
  *
@@ -165,7 +194,7 @@ export function buildMenu(menu, proxies, gettext) {
                 lastSyncLabel.set_text(_getLastSyncText(proxies.sync, _));
 
             const statusVariant = changed.lookup_value('SyncStatus', null);
-            if (statusVariant)
+            if (statusVariant || changed.lookup_value('PausedUntil', null))
                 statusLabel.set_text(_getSyncStatusText(proxies.sync, _));
         },
     );
@@ -502,6 +531,26 @@ export function buildMenu(menu, proxies, gettext) {
         }
     });
 
+    // Timed pauses; the daemon resumes on its own when the time is up.
+    // Hidden while paused, when "Resume Sync" is the only choice.
+    const pauseForItem = new PopupMenu.PopupSubMenuMenuItem(_('Pause For'));
+    pauseForItem.visible = !isPaused;
+    const pauseDurations = [
+        [_('1 Hour'), () => Math.floor(GLib.get_real_time() / 1000000) + 3600],
+        [_('4 Hours'), () => Math.floor(GLib.get_real_time() / 1000000) + 4 * 3600],
+        [_('Until Tomorrow'), _startOfTomorrow],
+    ];
+    for (const [label, until] of pauseDurations) {
+        const item = new PopupMenu.PopupMenuItem(label);
+        item.connect('activate', () => {
+            proxies.sync.PauseUntilRemote(until(), (_result, error) => {
+                if (error)
+                    console.error(`[LNXDrive] PauseUntil failed: ${error.message}`);
+            });
+        });
+        pauseForItem.menu.addMenuItem(item);
+    }
+
     // Track paused state from SyncStatus changes
     const pauseStateId = proxies.sync.connect(
         'g-properties-changed',
@@ -516,12 +565,14 @@ export function buildMenu(menu, proxies, gettext) {
                     isPaused = false;
                     pauseResumeItem.label.set_text(_('Pause Sync'));
                 }
+                pauseForItem.visible = !isPaused;
             }
         },
     );
     signalIds.push({proxy: proxies.sync, id: pauseStateId});
 
     actionsSection.addMenuItem(pauseResumeItem);
+    actionsSection.addMenuItem(pauseForItem);
 
     // Sync Now
     const syncNowItem = new PopupMenu.PopupMenuItem(_('Sync Now'));
@@ -569,8 +620,14 @@ function _getSyncStatusText(syncProxy, _) {
             return _('Idle');
         case 'syncing':
             return `${_('Syncing')}\u2026`;
-        case 'paused':
+        case 'paused': {
+            const until = syncProxy.PausedUntil;
+            if (until > 0) {
+                /* Translators: %s is a time, e.g. "14:30" or "Mon 09:00" */
+                return _('Paused until %s').format(_formatResumeTime(until));
+            }
             return _('Paused');
+        }
         case 'error':
             return _('Error');
         case 'offline':
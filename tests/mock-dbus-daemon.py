@@ -13,6 +13,7 @@ Implements all 7 D-Bus interfaces of com.enigmora.LNXDrive on the session bus:
 
 Usage:
     python3 mock-dbus-daemon.py [--authenticated] [--signal-interval N] [--sync-root PATH]
+                                [--business] [--extra-folders N] [--bus-address ADDRESS]
 
 Requirements:
     pip install dbus-next
@@ -22,6 +23,8 @@ from __future__ import annotations
 
 import argparse
 import asyncio
+import copy
+import fnmatch
 import json
 import logging
 import os
@@ -57,9 +60,11 @@ OBJECT_PATH = "/com/enigmora/LNXDrive"
 class FilesInterface(ServiceInterface):
     """Mock implementation of com.enigmora.LNXDrive.Files."""
 
-    def __init__(self, sync_root: str) -> None:
+    def __init__(self, sync_root: str, sync_iface: "SyncInterface") -> None:
         super().__init__("com.enigmora.LNXDrive.Files")
         self._sync_root = sync_root
+        # FreeUpAllSpace reports progress through Sync.SyncProgress.
+        self._sync_iface = sync_iface
 
         # Hardcoded file statuses keyed by path relative to sync_root.
         self._statuses: dict[str, str] = {
@@ -78,6 +83,54 @@ class FilesInterface(ServiceInterface):
             "shared/team-notes.docx": "error",
         }
 
+        # File sizes in bytes; a file takes local space unless it is cloud-only.
+        self._sizes: dict[str, int] = {
+            "document.pdf": 2_400_000,
+            "photos/vacation/beach.jpg": 4_800_000,
+            "report.docx": 350_000,
+            "budget.xlsx": 120_000,
+            "notes.txt": 4_000,
+            "presentation.pptx": 8_200_000,
+            "archive.zip": 52_000_000,
+            "projects/readme.md": 6_000,
+            "projects/src/main.rs": 18_000,
+            "shared/team-notes.docx": 75_000,
+        }
+
+        # Collaborators of shared folders, keyed by relative path.
+        self._shared: dict[str, list[dict[str, str]]] = {
+            "Projects": [
+                {"name": "Alex Kim", "email": "alex.kim@example.com"},
+                {"name": "Sam Rivera", "email": "sam.rivera@example.com"},
+            ],
+            "shared": [
+                {"name": "Alex Kim", "email": "alex.kim@example.com"},
+            ],
+        }
+
+        # Items deleted in the cloud and then removed locally by sync.
+        self._deleted: list[dict[str, Any]] = [
+            {
+                "id": "del-001",
+                "path": "/Documents/old-budget.xlsx",
+                "deleted_at": "2026-02-06T16:20:00Z",
+                "restorable": True,
+            },
+            {
+                "id": "del-002",
+                "path": "/Photos/Vacation/sunset.jpg",
+                "deleted_at": "2026-02-07T09:45:00Z",
+                "restorable": True,
+            },
+            {
+                # Past the recycle bin's retention period.
+                "id": "del-003",
+                "path": "/Archive/2019-taxes.pdf",
+                "deleted_at": "2025-11-02T12:00:00Z",
+                "restorable": False,
+            },
+        ]
+
     # -- helpers ----------------------------------------------------------
 
     def _relative_path(self, path: str) -> str:
@@ -113,6 +166,60 @@ class FilesInterface(ServiceInterface):
         log.info("Files.GetBatchFileStatus(%d paths)", len(paths))
         return result
 
+    @method()
+    def GetHydrationStates(self, paths: "as") -> "a{ss}":
+        result: dict[str, str] = {}
+        for p in paths:
+            rel = self._relative_path(p).rstrip("/")
+            status = self._statuses.get(rel) or self._statuses.get(rel + "/")
+            # A folder is partial when some of its files are placeholders.
+            below = [v for k, v in self._statuses.items() if k.startswith(rel + "/")]
+            if status == "cloud-only" and all(v == "cloud-only" for v in below):
+                result[p] = "cloud-only"
+            elif status is None and not below:
+                result[p] = "unknown"
+            elif any(v == "cloud-only" for v in below):
+                result[p] = "partial"
+            else:
+                result[p] = "hydrated"
+        log.info("Files.GetHydrationStates(%d paths)", len(paths))
+        return result
+
+    @method()
+    def GetFolderSizes(self, paths: "as") -> "a{st}":
+        result: dict[str, int] = {}
+        for p in paths:
+            rel = self._relative_path(p).rstrip("/")
+            result[p] = sum(
+                size
+                for k, size in self._sizes.items()
+                if (k == rel or k.startswith(rel + "/"))
+                and self._statuses.get(k) != "cloud-only"
+            )
+        log.info("Files.GetFolderSizes(%d paths)", len(paths))
+        return result
+
+    @method()
+    def GetSharedInfo(self, path: "s") -> "s":
+        collaborators = self._shared.get(self._relative_path(path).rstrip("/"), [])
+        log.info("Files.GetSharedInfo(%s) -> %d collaborators", path, len(collaborators))
+        return json.dumps({"shared": bool(collaborators), "collaborators": collaborators})
+
+    @method()
+    def ListDeleted(self) -> "s":
+        log.info("Files.ListDeleted() -> %d items", len(self._deleted))
+        return json.dumps(self._deleted)
+
+    @method()
+    def RestoreDeleted(self, item_id: "s") -> "b":
+        item = next((d for d in self._deleted if d["id"] == item_id), None)
+        if item is None or not item["restorable"]:
+            log.info("Files.RestoreDeleted(%s) -> False", item_id)
+            return False
+        self._deleted.remove(item)
+        log.info("Files.RestoreDeleted(%s) -> True (%s)", item_id, item["path"])
+        return True
+
     @method()
     def PinFile(self, path: "s"):
         rel = self._relative_path(path)
@@ -127,6 +234,18 @@ class FilesInterface(ServiceInterface):
         self._statuses[rel] = "cloud-only"
         self.FileStatusChanged(path, "cloud-only")
 
+    @method()
+    def FreeUpAllSpace(self) -> "t":
+        synced = [k for k, v in self._statuses.items() if v == "synced"]
+        freed = sum(self._sizes.get(k, 0) for k in synced)
+        for idx, rel in enumerate(synced, start=1):
+            self._statuses[rel] = "cloud-only"
+            path = os.path.join(self._sync_root, rel)
+            self.FileStatusChanged(path, "cloud-only")
+            self._sync_iface.SyncProgress(rel, idx, len(synced))
+        log.info("Files.FreeUpAllSpace() -> %d items, %d bytes", len(synced), freed)
+        return freed
+
     @method()
     def SyncPath(self, path: "s"):
         rel = self._relative_path(path)
@@ -163,6 +282,33 @@ class SyncInterface(ServiceInterface):
         self._last_sync_time: int = int(time.time()) - 300  # 5 min ago
         self._pending_changes: int = 7
         self._syncing_task: asyncio.Task[None] | None = None
+        # Unix time a timed pause ends at; 0 when not paused or indefinite.
+        self._paused_until: int = 0
+        self._resume_handle: asyncio.TimerHandle | None = None
+        self._sync_errors: list[dict[str, Any]] = [
+            {
+                "path": "/shared/team-notes.docx",
+                "message": "Permission denied",
+                "timestamp": "2026-02-07T11:05:00Z",
+                "kind": "permanent",
+                "failed_permanently": False,
+            },
+            {
+                "path": "/Photos/Vacation/beach.jpg",
+                "message": "Network timeout",
+                "timestamp": "2026-02-07T11:07:00Z",
+                "kind": "transient",
+                "failed_permanently": False,
+            },
+            {
+                # Out of automatic retries; only a manual retry is tried.
+                "path": "/Videos/holiday.mp4",
+                "message": "Upload interrupted",
+                "timestamp": "2026-02-07T11:09:00Z",
+                "kind": "transient",
+                "failed_permanently": True,
+            },
+        ]
 
     # -- properties -------------------------------------------------------
 
@@ -178,6 +324,10 @@ class SyncInterface(ServiceInterface):
     def PendingChanges(self) -> "u":
         return self._pending_changes
 
+    @dbus_property(access=PropertyAccess.READ)
+    def PausedUntil(self) -> "x":
+        return self._paused_until
+
     # -- methods ----------------------------------------------------------
 
     @method()
@@ -196,18 +346,67 @@ class SyncInterface(ServiceInterface):
     @method()
     def Pause(self):
         log.info("Sync.Pause()")
+        self._pause(0)
+
+    @method()
+    def PauseUntil(self, timestamp: "x"):
+        log.info("Sync.PauseUntil(%d)", timestamp)
+        self._pause(timestamp)
+        delay = max(0, timestamp - time.time())
+        self._resume_handle = asyncio.get_event_loop().call_later(delay, self.Resume)
+
+    def _pause(self, until: int) -> None:
         if self._syncing_task and not self._syncing_task.done():
             self._syncing_task.cancel()
             self._syncing_task = None
+        if self._resume_handle:
+            self._resume_handle.cancel()
+            self._resume_handle = None
         self._sync_status = "paused"
-        self.emit_properties_changed({"SyncStatus": self._sync_status})
+        self._paused_until = until
+        self.emit_properties_changed(
+            {"SyncStatus": self._sync_status, "PausedUntil": self._paused_until}
+        )
+
+    @method()
+    def GetSyncErrors(self) -> "s":
+        log.info("Sync.GetSyncErrors() -> %d errors", len(self._sync_errors))
+        return json.dumps(self._sync_errors)
+
+    @method()
+    def RetryItem(self, path: "s"):
+        log.info("Sync.RetryItem(%s)", path)
+        # Transient failures succeed on retry; permanent ones stay listed.
+        self._sync_errors = [
+            e for e in self._sync_errors
+            if not (e["path"] == path and e["kind"] == "transient")
+        ]
+
+    @method()
+    async def VerifyIntegrity(self) -> "u":
+        # A full re-scan walks every file; items the daemon had given up on
+        # are picked up again, which is what counts as repaired here.
+        files = ["document.pdf", "notes.txt", "presentation.pptx", "projects/readme.md"]
+        for idx, filename in enumerate(files, start=1):
+            await asyncio.sleep(0.2)
+            self.SyncProgress(filename, idx, len(files))
+        repaired = [e for e in self._sync_errors if e["failed_permanently"]]
+        self._sync_errors = [e for e in self._sync_errors if not e["failed_permanently"]]
+        log.info("Sync.VerifyIntegrity() -> %d repaired", len(repaired))
+        return len(repaired)
 
     @method()
     def Resume(self):
         log.info("Sync.Resume()")
+        if self._resume_handle:
+            self._resume_handle.cancel()
+            self._resume_handle = None
         if self._sync_status == "paused":
             self._sync_status = "idle"
-            self.emit_properties_changed({"SyncStatus": self._sync_status})
+            self._paused_until = 0
+            self.emit_properties_changed(
+                {"SyncStatus": self._sync_status, "PausedUntil": self._paused_until}
+            )
             log.info("Sync.Resume() — status set to idle")
 
     # -- signals ----------------------------------------------------------
@@ -270,11 +469,24 @@ class SyncInterface(ServiceInterface):
 class StatusInterface(ServiceInterface):
     """Mock implementation of com.enigmora.LNXDrive.Status."""
 
-    def __init__(self) -> None:
+    def __init__(self, business: bool = False) -> None:
         super().__init__("com.enigmora.LNXDrive.Status")
+        self._business: bool = business
         self._connection_status: str = "online"
+        # When the account info and quota were last fetched, sent as as_of
+        # while they are served from cache.
+        self._as_of: str = time.strftime("%Y-%m-%dT%H:%M:%SZ", time.gmtime())
         self._used: int = 5_368_709_120   # 5 GB
         self._total: int = 16_106_127_360  # ~15 GB
+        self._session_uploaded: int = 12_582_912     # 12 MB
+        self._session_downloaded: int = 98_566_144   # 94 MB
+        self._total_uploaded: int = 2_147_483_648    # 2 GB
+        self._total_downloaded: int = 9_663_676_416  # 9 GB
+        # Throttled just now, so the client shows the resume time.
+        self._last_error: tuple[str, str] = (
+            "Microsoft Graph is rate limiting requests (Retry-After: 120)",
+            time.strftime("%Y-%m-%dT%H:%M:%SZ", time.gmtime()),
+        )
 
     # -- properties -------------------------------------------------------
 
@@ -295,10 +507,70 @@ class StatusInterface(ServiceInterface):
             "email": Variant("s", "user@example.com"),
             "display_name": Variant("s", "Test User"),
             "provider": Variant("s", "onedrive"),
+            "account_type": Variant("s", "Business" if self._business else "Personal"),
         }
+        if self._connection_status != "online":
+            info["as_of"] = Variant("s", self._as_of)
         log.info("Status.GetAccountInfo() -> %s", {k: v.value for k, v in info.items()})
         return info
 
+    @method()
+    def GetTransferStats(self) -> "a{st}":
+        stats = {
+            "session_uploaded": self._session_uploaded,
+            "session_downloaded": self._session_downloaded,
+            "total_uploaded": self._total_uploaded,
+            "total_downloaded": self._total_downloaded,
+        }
+        log.info("Status.GetTransferStats() -> %s", stats)
+        return stats
+
+    @method()
+    def GetActiveTransfers(self) -> "s":
+        # Progress advances with wall-clock time so polling clients see movement.
+        tick = int(time.time()) % 20
+        transfers = [
+            {
+                "path": "/Documents/report.docx",
+                "direction": "upload",
+                "bytes_done": min(tick, 10) * 52_428,
+                "bytes_total": 524_288,
+                "speed": 52_428,
+            },
+            {
+                "path": "/Photos/Vacation/beach.jpg",
+                "direction": "download",
+                "bytes_done": tick * 209_715,
+                "bytes_total": 4_194_304,
+                "speed": 209_715,
+            },
+        ]
+        transfers = [t for t in transfers if t["bytes_done"] < t["bytes_total"]]
+        log.info("Status.GetActiveTransfers() -> %d transfers", len(transfers))
+        return json.dumps(transfers)
+
+    @method()
+    def ResetTransferStats(self):
+        log.info("Status.ResetTransferStats()")
+        self._session_uploaded = 0
+        self._session_downloaded = 0
+
+    @method()
+    def GetLastError(self) -> "ss":
+        log.info("Status.GetLastError() -> %s", self._last_error)
+        return list(self._last_error)
+
+    @method()
+    def ClearLastError(self):
+        log.info("Status.ClearLastError()")
+        self._last_error = ("", "")
+
+    @method()
+    def PingRemote(self) -> "bus":
+        latency_ms = 120
+        log.info("Status.PingRemote() -> %d ms", latency_ms)
+        return [True, latency_ms, ""]
+
     # -- signals ----------------------------------------------------------
 
     @dbus_signal()
@@ -313,6 +585,17 @@ class StatusInterface(ServiceInterface):
 # ===================================================================
 # 4. com.enigmora.LNXDrive.Manager
 # ===================================================================
+# Daemon log lines served by GetRecentLogs; one carries a token so the
+# diagnostics report's redaction can be checked.
+_RECENT_LOGS = [
+    "2026-02-05T09:00:00Z INFO lnxdrive: daemon started",
+    "2026-02-05T09:00:01Z INFO auth: refresh_token: M.R3_BAY.mock-refresh-token",
+    "2026-02-05T09:00:02Z INFO sync: cycle started",
+    "2026-02-05T09:00:05Z WARN sync: conflict detected in /Documents/report.docx",
+    "2026-02-05T09:00:06Z INFO sync: cycle completed (12 files, 0 errors)",
+]
+
+
 class ManagerInterface(ServiceInterface):
     """Mock implementation of com.enigmora.LNXDrive.Manager."""
 
@@ -359,6 +642,11 @@ class ManagerInterface(ServiceInterface):
         log.info("Manager.GetStatus() -> %s", status)
         return status
 
+    @method()
+    def GetRecentLogs(self, lines: "u") -> "as":
+        log.info("Manager.GetRecentLogs(%d)", lines)
+        return _RECENT_LOGS[-lines:] if lines else []
+
 
 # ===================================================================
 # 5. com.enigmora.LNXDrive.Conflicts
@@ -401,9 +689,29 @@ class ConflictsInterface(ServiceInterface):
                     "hash": "stu901vwx234",
                     "size_bytes": 130_048,
                     "modified_at": "2026-02-07T10:58:00Z",
+                    # Only reported for files in shared folders
+                    "modified_by": "Alex Kim",
                 },
             },
         ]
+        # Resolutions, oldest first: {id, item_path, strategy, resolved_at}
+        self._history: list[dict[str, Any]] = []
+
+    def _mark_resolved(self, conflict: dict[str, Any], strategy: str) -> None:
+        conflict["resolved"] = True
+        conflict["resolution"] = strategy
+        if strategy == "keep_both":
+            # The local version is moved aside; GetDetails reports where.
+            stem, ext = os.path.splitext(conflict["item_path"])
+            conflict["renamed_copy"] = f"{stem} (conflicted copy){ext}"
+        self._history.append(
+            {
+                "id": conflict["id"],
+                "item_path": conflict["item_path"],
+                "strategy": strategy,
+                "resolved_at": time.strftime("%Y-%m-%dT%H:%M:%SZ", time.gmtime()),
+            }
+        )
 
     @method()
     def List(self) -> "s":
@@ -425,8 +733,7 @@ class ConflictsInterface(ServiceInterface):
     def Resolve(self, conflict_id: "s", strategy: "s") -> "b":
         for c in self._conflicts:
             if c["id"] == conflict_id and "resolved" not in c:
-                c["resolved"] = True
-                c["resolution"] = strategy
+                self._mark_resolved(c, strategy)
                 log.info("Conflicts.Resolve(%s, %s) -> true", conflict_id, strategy)
                 self.ConflictResolved(conflict_id, strategy)
                 return True
@@ -438,13 +745,50 @@ class ConflictsInterface(ServiceInterface):
         count = 0
         for c in self._conflicts:
             if "resolved" not in c:
-                c["resolved"] = True
-                c["resolution"] = strategy
+                self._mark_resolved(c, strategy)
                 count += 1
                 self.ConflictResolved(c["id"], strategy)
         log.info("Conflicts.ResolveAll(%s) -> %d resolved", strategy, count)
         return count
 
+    @method()
+    def ResolveMany(self, conflict_ids: "as", strategy: "s") -> "u":
+        wanted = set(conflict_ids)
+        count = 0
+        for c in self._conflicts:
+            if c["id"] in wanted and "resolved" not in c:
+                self._mark_resolved(c, strategy)
+                count += 1
+                self.ConflictResolved(c["id"], strategy)
+        log.info("Conflicts.ResolveMany(%d ids, %s) -> %d resolved", len(wanted), strategy, count)
+        return count
+
+    @method()
+    def GetResolutionHistory(self, limit: "u") -> "s":
+        # Only the latest resolution of each conflict can be reverted.
+        still_resolved = {c["id"] for c in self._conflicts if "resolved" in c}
+        entries = []
+        for entry in reversed(self._history):
+            reopenable = entry["id"] in still_resolved
+            still_resolved.discard(entry["id"])
+            entries.append({**entry, "reopenable": reopenable})
+        entries = entries[:limit]
+        log.info("Conflicts.GetResolutionHistory(%d) -> %d entries", limit, len(entries))
+        return json.dumps(entries)
+
+    @method()
+    def Reopen(self, conflict_id: "s") -> "b":
+        for c in self._conflicts:
+            if c["id"] == conflict_id and "resolved" in c:
+                del c["resolved"]
+                c.pop("resolution", None)
+                c.pop("renamed_copy", None)
+                log.info("Conflicts.Reopen(%s) -> true", conflict_id)
+                self.ConflictDetected(json.dumps(c))
+                return True
+        log.info("Conflicts.Reopen(%s) -> false", conflict_id)
+        return False
+
     # -- signals ----------------------------------------------------------
 
     @dbus_signal()
@@ -464,6 +808,11 @@ _DEFAULT_CONFIG_YAML = """\
 sync_root: ~/OneDrive
 sync_mode: hybrid
 conflict_policy: rename_local
+sync_connection_types: [wifi, ethernet, mobile]
+max_parallel_transfers: 4
+exclusion_case_sensitive: false
+exclusion_match_basename: true
+conflict_copy_template: "{name} (conflict {date}){ext}"
 bandwidth:
   upload_limit_kbps: 0
   download_limit_kbps: 0
@@ -477,33 +826,57 @@ logging:
   file: ~/.local/share/lnxdrive/lnxdrive.log
 """
 
-_REMOTE_FOLDER_TREE = json.dumps(
-    {
-        "name": "root",
-        "path": "/",
-        "children": [
-            {"name": "Documents", "path": "/Documents", "children": []},
-            {
-                "name": "Photos",
-                "path": "/Photos",
-                "children": [
-                    {"name": "Vacation", "path": "/Photos/Vacation", "children": []},
-                ],
-            },
-            {"name": "Projects", "path": "/Projects", "children": []},
-        ],
-    },
-    indent=None,
+# Remote paths the exclusion-pattern preview is matched against.
+_REMOTE_PATHS = [
+    "/Documents/report.docx",
+    "/Documents/~$report.docx",
+    "/Documents/Work/budget.xlsx",
+    "/Documents/Work/budget.xlsx.tmp",
+    "/Documents/Personal/.~lock.notes.odt#",
+    "/Photos/Vacation/beach.jpg",
+    "/Photos/Vacation/Thumbs.db",
+    "/Projects/readme.md",
+    "/Projects/.git/config",
+    "/Projects/build.log",
+]
+
+# The default root's folder tree and selection, shared with the widget tests
+# mock (preferences/src/test_harness.rs). The tree has two folders at
+# /Projects, one owned and one shared with the user; only ids tell them apart.
+_FOLDER_TREE_FIXTURE: dict[str, Any] = json.loads(
+    (Path(__file__).parent / "fixtures" / "folder-tree.json").read_text()
 )
 
 
 class SettingsInterface(ServiceInterface):
     """Mock implementation of com.enigmora.LNXDrive.Settings."""
 
-    def __init__(self, sync_root: str) -> None:
+    def __init__(self, sync_root: str, business: bool = False, extra_folders: int = 0) -> None:
         super().__init__("com.enigmora.LNXDrive.Settings")
         self._config_yaml: str = _DEFAULT_CONFIG_YAML.replace("~/OneDrive", sync_root)
-        self._selected_folders: list[str] = ["/Documents", "/Photos", "/Projects"]
+        # "Folder 001" ... appended to the top level, for trees long enough
+        # that the list recycles its rows while scrolling.
+        tree = copy.deepcopy(_FOLDER_TREE_FIXTURE["folder_tree"])
+        tree["children"].extend(
+            {"name": f"Folder {n:03}", "path": f"/Folder {n:03}", "children": []}
+            for n in range(1, extra_folders + 1)
+        )
+        self._remote_folder_tree: str = json.dumps(tree)
+        # The single-root config's selected folders become the "default"
+        # root, mirroring the daemon's migration to multiple sync roots.
+        self._roots: dict[str, dict[str, Any]] = {
+            "default": {
+                "name": "OneDrive",
+                "local_path": sync_root,
+                "selected": list(_FOLDER_TREE_FIXTURE["selected_folders"]),
+            },
+        }
+        if business:
+            self._roots["team-site"] = {
+                "name": "Team Site — Documents",
+                "local_path": os.path.join(sync_root, "Team Site"),
+                "selected": ["/Documents"],
+            }
         self._exclusion_patterns: list[str] = ["*.tmp", "~$*", ".~lock.*", "Thumbs.db"]
 
     # -- methods ----------------------------------------------------------
@@ -519,15 +892,40 @@ class SettingsInterface(ServiceInterface):
         self._config_yaml = yaml_str
         self.ConfigChanged("config")
 
+    @method()
+    def GetSyncRoots(self) -> "s":
+        roots = [
+            {"id": root_id, "name": root["name"], "local_path": root["local_path"]}
+            for root_id, root in self._roots.items()
+        ]
+        log.info("Settings.GetSyncRoots() -> %d root(s)", len(roots))
+        return json.dumps(roots)
+
+    @method()
+    def GetRootSelectedFolders(self, root_id: "s") -> "as":
+        selected = self._roots.get(root_id, {}).get("selected", [])
+        log.info("Settings.GetRootSelectedFolders(%s) -> %s", root_id, selected)
+        return selected
+
+    @method()
+    def SetRootSelectedFolders(self, root_id: "s", folders: "as"):
+        log.info("Settings.SetRootSelectedFolders(%s, %s)", root_id, folders)
+        if root_id in self._roots:
+            self._roots[root_id]["selected"] = list(folders)
+        self.ConfigChanged("selected_folders")
+
+    # The single-root forms act on the "default" root.
+
     @method()
     def GetSelectedFolders(self) -> "as":
-        log.info("Settings.GetSelectedFolders() -> %s", self._selected_folders)
-        return self._selected_folders
+        selected = self._roots["default"]["selected"]
+        log.info("Settings.GetSelectedFolders() -> %s", selected)
+        return selected
 
     @method()
     def SetSelectedFolders(self, folders: "as"):
         log.info("Settings.SetSelectedFolders(%s)", folders)
-        self._selected_folders = list(folders)
+        self._roots["default"]["selected"] = list(folders)
         self.ConfigChanged("selected_folders")
 
     @method()
@@ -541,10 +939,27 @@ class SettingsInterface(ServiceInterface):
         self._exclusion_patterns = list(patterns)
         self.ConfigChanged("exclusion_patterns")
 
+    @method()
+    def TestExclusionPattern(self, pattern: "s", limit: "u") -> "as":
+        # Match against the full path and the basename, like the daemon does.
+        matches = [
+            p for p in _REMOTE_PATHS
+            if fnmatch.fnmatch(p, pattern)
+            or fnmatch.fnmatch(os.path.basename(p), pattern)
+            or (pattern.endswith("/") and f"/{pattern}" in p)
+        ][:limit]
+        log.info("Settings.TestExclusionPattern(%s, %d) -> %d match(es)", pattern, limit, len(matches))
+        return matches
+
+    @method()
+    def GetRootRemoteFolderTree(self, root_id: "s") -> "s":
+        log.info("Settings.GetRootRemoteFolderTree(%s)", root_id)
+        return self._remote_folder_tree
+
     @method()
     def GetRemoteFolderTree(self) -> "s":
         log.info("Settings.GetRemoteFolderTree()")
-        return _REMOTE_FOLDER_TREE
+        return self._remote_folder_tree
 
     # -- signals ----------------------------------------------------------
 
@@ -697,6 +1112,27 @@ def parse_args() -> argparse.Namespace:
         metavar="PATH",
         help="Mock sync root path (default: ~/OneDrive).",
     )
+    parser.add_argument(
+        "--business",
+        action="store_true",
+        default=False,
+        help="Report a business account with a second document library.",
+    )
+    parser.add_argument(
+        "--extra-folders",
+        type=int,
+        default=0,
+        metavar="N",
+        help="Add N empty top-level folders to the remote folder tree (default: 0).",
+    )
+    parser.add_argument(
+        "--bus-address",
+        type=str,
+        default=None,
+        metavar="ADDRESS",
+        help="Connect to this D-Bus address instead of the session bus "
+        "(e.g. a private dbus-daemon started by a test).",
+    )
     return parser.parse_args()
 
 
@@ -708,16 +1144,24 @@ async def run(args: argparse.Namespace) -> None:
     for sig in (signal.SIGINT, signal.SIGTERM):
         loop.add_signal_handler(sig, stop_event.set)
 
-    log.info("Connecting to session bus...")
-    bus = await MessageBus().connect()
+    if args.bus_address:
+        log.info("Connecting to bus at %s...", args.bus_address)
+        bus = await MessageBus(bus_address=args.bus_address).connect()
+    else:
+        log.info("Connecting to session bus...")
+        bus = await MessageBus().connect()
 
     # Instantiate all interfaces.
-    files_iface = FilesInterface(sync_root=args.sync_root)
     sync_iface = SyncInterface()
-    status_iface = StatusInterface()
+    files_iface = FilesInterface(sync_root=args.sync_root, sync_iface=sync_iface)
+    status_iface = StatusInterface(business=args.business)
     manager_iface = ManagerInterface()
     conflicts_iface = ConflictsInterface(sync_root=args.sync_root)
-    settings_iface = SettingsInterface(sync_root=args.sync_root)
+    settings_iface = SettingsInterface(
+        sync_root=args.sync_root,
+        business=args.business,
+        extra_folders=args.extra_folders,
+    )
     auth_iface = AuthInterface(authenticated=args.authenticated)
 
     # Export all interfaces on the same object path.
@@ -0,0 +1,319 @@
+#!/usr/bin/env python3
+"""
+UI tests for the Selective Sync folder tree of the LNXDrive preferences app.
+
+Each test runs the preferences binary (--page sync) on a private bus with the
+mock daemon, toggles folder checkboxes through the accessibility tree
+(AT-SPI) and checks the selection the mock daemon received:
+
+- Toggling "Projects (shared)", one of two folders at /Projects, saves that
+  folder's id and leaves the other /Projects folder alone.
+- In a tree of a few hundred folders, scrolled to the end so the list has
+  recycled its rows, toggling a row saves that row's folder only.
+- Scrolled to the end and back, so each row has been rebound to another
+  folder and then to its first one again, toggling a row changes only the
+  folder it shows now.
+
+Usage:
+    cargo build --manifest-path preferences/Cargo.toml
+    python3 tests/test-preferences-folder-tree.py
+
+    LNXDRIVE_PREFERENCES_BIN selects another binary (e.g. a meson build).
+
+Requirements:
+    - A display (WAYLAND_DISPLAY or DISPLAY) and a running accessibility bus;
+      the tests are skipped without them
+    - pip install dbus-next   (for the mock daemon)
+    - gi.repository (PyGObject) with the Atspi typelib must be available
+    - dbus-daemon (used by Gio.TestDBus)
+"""
+
+from __future__ import annotations
+
+import os
+import signal
+import subprocess
+import sys
+import time
+import unittest
+from pathlib import Path
+
+import gi
+
+gi.require_version("Atspi", "2.0")
+gi.require_version("Gio", "2.0")
+gi.require_version("GLib", "2.0")
+from gi.repository import Atspi, Gio, GLib  # noqa: E402
+
+BUS_NAME = "com.enigmora.LNXDrive"
+OBJECT_PATH = "/com/enigmora/LNXDrive"
+IFACE_SETTINGS = "com.enigmora.LNXDrive.Settings"
+
+TESTS_DIR = Path(__file__).resolve().parent
+MOCK_DAEMON = TESTS_DIR / "mock-dbus-daemon.py"
+PREFERENCES_BIN = Path(
+    os.environ.get(
+        "LNXDRIVE_PREFERENCES_BIN",
+        TESTS_DIR.parent / "preferences" / "target" / "debug" / "lnxdrive-preferences",
+    )
+)
+
+# How long to wait for the window, a row or a saved selection.
+TIMEOUT_SECONDS = 10
+# Longer than the folder tree's 500ms save debounce.
+SAVE_SECONDS = 1.5
+# Top-level folders the mock adds for the scrolling tests; far more rows than
+# the list shows at once.
+EXTRA_FOLDERS = 300
+KEY_HOME = 0xFF50
+KEY_END = 0xFF57
+
+
+def _a11y_bus_address() -> str | None:
+    """Address of the session's accessibility bus, which the app is pointed
+    at since its own session bus is the private test bus."""
+    try:
+        session = Gio.bus_get_sync(Gio.BusType.SESSION, None)
+        result = session.call_sync(
+            "org.a11y.Bus",
+            "/org/a11y/bus",
+            "org.a11y.Bus",
+            "GetAddress",
+            None,
+            GLib.VariantType.new("(s)"),
+            Gio.DBusCallFlags.NONE,
+            5000,
+            None,
+        )
+    except GLib.Error:
+        return None
+    return result.unpack()[0]
+
+
+def _stop(proc: subprocess.Popen) -> None:
+    try:
+        proc.send_signal(signal.SIGTERM)
+        proc.wait(timeout=5)
+    except (subprocess.TimeoutExpired, OSError):
+        proc.kill()
+        proc.wait(timeout=5)
+
+
+def _wait_for(predicate, what: str):
+    """Poll `predicate` until it returns something truthy and return that."""
+    deadline = time.monotonic() + TIMEOUT_SECONDS
+    while time.monotonic() < deadline:
+        value = predicate()
+        if value:
+            return value
+        time.sleep(0.2)
+    raise AssertionError(f"Timed out waiting for {what}")
+
+
+def _find(node: Atspi.Accessible, role: Atspi.Role, name: str) -> Atspi.Accessible | None:
+    """First descendant of `node` with `role` and accessible `name`."""
+    stack = [node]
+    while stack:
+        current = stack.pop()
+        try:
+            if current.get_role() == role and current.get_name() == name:
+                return current
+            count = current.get_child_count()
+        except GLib.Error:
+            # The widget went away while walking the tree.
+            continue
+        stack.extend(
+            child
+            for child in (current.get_child_at_index(i) for i in range(count))
+            if child is not None
+        )
+    return None
+
+
+def _is_checked(accessible: Atspi.Accessible) -> bool:
+    return accessible.get_state_set().contains(Atspi.StateType.CHECKED)
+
+
+def _press(keysym: int) -> None:
+    Atspi.generate_keyboard_event(keysym, None, Atspi.KeySynthType.SYM)
+
+
+def _toggle(accessible: Atspi.Accessible) -> None:
+    """Toggle a checkbox through its first (and only) accessible action."""
+    action = accessible.get_action_iface()
+    if action is None or action.get_n_actions() == 0:
+        raise AssertionError(f"{accessible.get_name()} has no action to toggle it")
+    action.do_action(0)
+
+
+@unittest.skipUnless(
+    os.environ.get("WAYLAND_DISPLAY") or os.environ.get("DISPLAY"),
+    "no display to open the window on",
+)
+@unittest.skipUnless(_a11y_bus_address(), "no accessibility bus")
+@unittest.skipUnless(PREFERENCES_BIN.exists(), f"{PREFERENCES_BIN} not built")
+class FolderTreeUiTest(unittest.TestCase):
+    """The folder tree saves exactly the folders toggled in it."""
+
+    def setUp(self) -> None:
+        self._test_bus = Gio.TestDBus.new(Gio.TestDBusFlags.NONE)
+        self._test_bus.up()
+        self.addCleanup(self._test_bus.down)
+        address = self._test_bus.get_bus_address()
+
+        self._daemon = subprocess.Popen(
+            [
+                sys.executable,
+                str(MOCK_DAEMON),
+                "--authenticated",
+                "--signal-interval", "999",
+                "--bus-address", address,
+                *self.daemon_args(),
+            ],
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.DEVNULL,
+        )
+        self.addCleanup(_stop, self._daemon)
+
+        self._connection = Gio.DBusConnection.new_for_address_sync(
+            address,
+            Gio.DBusConnectionFlags.AUTHENTICATION_CLIENT
+            | Gio.DBusConnectionFlags.MESSAGE_BUS_CONNECTION,
+            None,
+            None,
+        )
+        _wait_for(self._daemon_running, "the mock daemon")
+
+        app_env = dict(os.environ)
+        app_env["DBUS_SESSION_BUS_ADDRESS"] = address
+        app_env["AT_SPI_BUS_ADDRESS"] = _a11y_bus_address() or ""
+        self._app = subprocess.Popen(
+            [str(PREFERENCES_BIN), "--page", "sync"],
+            env=app_env,
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.PIPE,
+            text=True,
+        )
+        self.addCleanup(self._stop_app)
+
+        self._window = _wait_for(self._app_accessible, "the preferences window")
+
+    def daemon_args(self) -> list[str]:
+        """Extra mock daemon arguments for this test case."""
+        return []
+
+    def _stop_app(self) -> None:
+        _stop(self._app)
+        if self._app.stderr:
+            self._app.stderr.close()
+
+    def _daemon_running(self) -> bool:
+        try:
+            result = self._connection.call_sync(
+                "org.freedesktop.DBus",
+                "/org/freedesktop/DBus",
+                "org.freedesktop.DBus",
+                "NameHasOwner",
+                GLib.Variant("(s)", (BUS_NAME,)),
+                GLib.VariantType.new("(b)"),
+                Gio.DBusCallFlags.NONE,
+                5000,
+                None,
+            )
+        except GLib.Error:
+            return False
+        return result.get_child_value(0).get_boolean()
+
+    def _app_accessible(self) -> Atspi.Accessible | None:
+        desktop = Atspi.get_desktop(0)
+        for i in range(desktop.get_child_count()):
+            app = desktop.get_child_at_index(i)
+            if app is not None and app.get_process_id() == self._app.pid:
+                return app
+        return None
+
+    def selected_folders(self) -> list[str]:
+        """The mock daemon's current selection for the "default" root."""
+        return self._connection.call_sync(
+            BUS_NAME,
+            OBJECT_PATH,
+            IFACE_SETTINGS,
+            "GetRootSelectedFolders",
+            GLib.Variant("(s)", ("default",)),
+            GLib.VariantType.new("(as)"),
+            Gio.DBusCallFlags.NONE,
+            5000,
+            None,
+        ).unpack()[0]
+
+    def checkbox(self, name: str) -> Atspi.Accessible:
+        """The checkbox of the folder row labelled `name`, once it is shown."""
+        return _wait_for(
+            lambda: _find(self._window, Atspi.Role.CHECK_BOX, name),
+            f"the {name!r} row",
+        )
+
+    def toggle_and_save(self, name: str) -> tuple[set[str], set[str]]:
+        """Toggle the row labelled `name`, wait for the debounced save and
+        return the folder keys added to and removed from the selection."""
+        before = set(self.selected_folders())
+        check = self.checkbox(name)
+        was_checked = _is_checked(check)
+        _toggle(check)
+        _wait_for(lambda: _is_checked(check) != was_checked, f"{name!r} to toggle")
+        time.sleep(SAVE_SECONDS)
+        after = set(self.selected_folders())
+        return after - before, before - after
+
+    def test_duplicate_paths_toggle_one_node(self) -> None:
+        # The mock selects "projects-owned", the other /Projects folder.
+        self.assertIn("projects-owned", self.selected_folders())
+        self.assertFalse(_is_checked(self.checkbox("Projects (shared)")))
+        self.assertTrue(_is_checked(self.checkbox("Projects")))
+
+        added, removed = self.toggle_and_save("Projects (shared)")
+
+        self.assertEqual(added, {"projects-shared"})
+        self.assertEqual(removed, set())
+        self.assertTrue(_is_checked(self.checkbox("Projects")))
+
+
+class LargeFolderTreeUiTest(FolderTreeUiTest):
+    """Rows recycled while scrolling stay tied to the folder they show."""
+
+    def daemon_args(self) -> list[str]:
+        return ["--extra-folders", str(EXTRA_FOLDERS)]
+
+    def scroll_to_end(self) -> None:
+        """Focus the first row and press End, so the list scrolls to the last
+        folder and rebinds its rows to folders further down."""
+        last = f"Folder {EXTRA_FOLDERS:03}"
+        self.checkbox("Documents").get_component_iface().grab_focus()
+        _press(KEY_END)
+        self.checkbox(last)
+        # The first row's widget now shows another folder.
+        self.assertIsNone(_find(self._window, Atspi.Role.CHECK_BOX, "Documents"))
+
+    def test_toggle_recycled_row(self) -> None:
+        self.scroll_to_end()
+
+        added, removed = self.toggle_and_save(f"Folder {EXTRA_FOLDERS - 1:03}")
+
+        self.assertEqual(added, {f"/Folder {EXTRA_FOLDERS - 1:03}"})
+        self.assertEqual(removed, set())
+
+    def test_toggle_after_rebind(self) -> None:
+        self.scroll_to_end()
+        self.checkbox(f"Folder {EXTRA_FOLDERS:03}").get_component_iface().grab_focus()
+        _press(KEY_HOME)
+        self.assertTrue(_is_checked(self.checkbox("Documents")))
+
+        added, removed = self.toggle_and_save("Documents")
+
+        self.assertEqual(added, set())
+        self.assertEqual(removed, {"/Documents"})
+        self.assertFalse(_is_checked(self.checkbox("Documents")))
+
+
+if __name__ == "__main__":
+    unittest.main()
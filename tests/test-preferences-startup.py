@@ -0,0 +1,322 @@
+#!/usr/bin/env python3
+"""
+Startup tests for the LNXDrive preferences app.
+
+Each test runs the preferences binary on a private bus with the mock daemon:
+
+- Without an installed GSettings schema (GSETTINGS_SCHEMA_DIR and
+  XDG_DATA_DIRS point at an empty directory): the process must stay up, the
+  window still constructed (at the default size, without persisting it) and
+  the missing-schema warning logged instead of aborting.
+- With --page advanced while signed in, the window goes straight to
+  preferences and the Advanced page is on screen, as seen through the
+  accessibility tree (AT-SPI).
+- With --page advanced while signed out, onboarding comes first and the
+  Advanced page is not shown; the test signs in through the mock daemon,
+  continues with the default folder and starts syncing, after which the
+  Advanced page is on screen.
+
+Usage:
+    cargo build --manifest-path preferences/Cargo.toml
+    python3 tests/test-preferences-startup.py
+
+    LNXDRIVE_PREFERENCES_BIN selects another binary (e.g. a meson build).
+
+Requirements:
+    - A display (WAYLAND_DISPLAY or DISPLAY); the tests are skipped without one
+    - A running accessibility bus for the --page tests; they are skipped
+      without one
+    - pip install dbus-next   (for the mock daemon)
+    - gi.repository (PyGObject) with the Atspi typelib must be available
+    - dbus-daemon (used by Gio.TestDBus)
+"""
+
+from __future__ import annotations
+
+import os
+import signal
+import subprocess
+import sys
+import tempfile
+import time
+import unittest
+from pathlib import Path
+
+import gi
+
+gi.require_version("Atspi", "2.0")
+gi.require_version("Gio", "2.0")
+gi.require_version("GLib", "2.0")
+from gi.repository import Atspi, Gio, GLib  # noqa: E402
+
+BUS_NAME = "com.enigmora.LNXDrive"
+OBJECT_PATH = "/com/enigmora/LNXDrive"
+IFACE_AUTH = "com.enigmora.LNXDrive.Auth"
+
+TESTS_DIR = Path(__file__).resolve().parent
+MOCK_DAEMON = TESTS_DIR / "mock-dbus-daemon.py"
+PREFERENCES_BIN = Path(
+    os.environ.get(
+        "LNXDRIVE_PREFERENCES_BIN",
+        TESTS_DIR.parent / "preferences" / "target" / "debug" / "lnxdrive-preferences",
+    )
+)
+
+# How long the app must stay up after launch.
+STARTUP_SECONDS = 3
+# How long to wait for the window or a widget. Covers the onboarding's
+# few seconds of first-sync preview before preferences open.
+TIMEOUT_SECONDS = 15
+# A row only the Advanced page has.
+ADVANCED_ROW = "Enable Sync at Login"
+
+
+def _a11y_bus_address() -> str | None:
+    """Address of the session's accessibility bus, which the app is pointed
+    at since its own session bus is the private test bus."""
+    try:
+        session = Gio.bus_get_sync(Gio.BusType.SESSION, None)
+        result = session.call_sync(
+            "org.a11y.Bus",
+            "/org/a11y/bus",
+            "org.a11y.Bus",
+            "GetAddress",
+            None,
+            GLib.VariantType.new("(s)"),
+            Gio.DBusCallFlags.NONE,
+            5000,
+            None,
+        )
+    except GLib.Error:
+        return None
+    return result.unpack()[0]
+
+
+def _stop(proc: subprocess.Popen) -> None:
+    try:
+        proc.send_signal(signal.SIGTERM)
+        proc.wait(timeout=5)
+    except (subprocess.TimeoutExpired, OSError):
+        proc.kill()
+        proc.wait(timeout=5)
+
+
+def _wait_for(predicate, what: str):
+    """Poll `predicate` until it returns something truthy and return that."""
+    deadline = time.monotonic() + TIMEOUT_SECONDS
+    while time.monotonic() < deadline:
+        value = predicate()
+        if value:
+            return value
+        time.sleep(0.2)
+    raise AssertionError(f"Timed out waiting for {what}")
+
+
+def _find_showing(node: Atspi.Accessible, name: str) -> Atspi.Accessible | None:
+    """First descendant of `node` named `name` that is on screen."""
+    stack = [node]
+    while stack:
+        current = stack.pop()
+        try:
+            if (
+                current.get_name() == name
+                and current.get_state_set().contains(Atspi.StateType.SHOWING)
+            ):
+                return current
+            count = current.get_child_count()
+        except GLib.Error:
+            # The widget went away while walking the tree.
+            continue
+        stack.extend(
+            child
+            for child in (current.get_child_at_index(i) for i in range(count))
+            if child is not None
+        )
+    return None
+
+
+def _click(accessible: Atspi.Accessible) -> None:
+    """Activate a button through its first (and only) accessible action."""
+    action = accessible.get_action_iface()
+    if action is None or action.get_n_actions() == 0:
+        raise AssertionError(f"{accessible.get_name()} has no action to click it")
+    action.do_action(0)
+
+
+def _run_app(app_args: list[str], env: dict[str, str] | None = None) -> tuple[int | None, str]:
+    """Launch the app with `app_args` against a fresh mock daemon and stop it
+    after STARTUP_SECONDS. Returns its exit code (None if it was still
+    running) and its stderr."""
+    test_bus = Gio.TestDBus.new(Gio.TestDBusFlags.NONE)
+    test_bus.up()
+    address = test_bus.get_bus_address()
+    daemon = subprocess.Popen(
+        [
+            sys.executable,
+            str(MOCK_DAEMON),
+            "--authenticated",
+            "--signal-interval", "999",
+            "--bus-address", address,
+        ],
+        stdout=subprocess.DEVNULL,
+        stderr=subprocess.DEVNULL,
+    )
+    try:
+        app_env = dict(os.environ)
+        app_env.update(env or {})
+        app_env["DBUS_SESSION_BUS_ADDRESS"] = address
+
+        app = subprocess.Popen(
+            [str(PREFERENCES_BIN), *app_args],
+            env=app_env,
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.PIPE,
+            text=True,
+        )
+        time.sleep(STARTUP_SECONDS)
+        exit_code = app.poll()
+        if exit_code is None:
+            _stop(app)
+        stderr = app.stderr.read() if app.stderr else ""
+        return exit_code, stderr
+    finally:
+        _stop(daemon)
+        test_bus.down()
+
+
+@unittest.skipUnless(
+    os.environ.get("WAYLAND_DISPLAY") or os.environ.get("DISPLAY"),
+    "no display to open the window on",
+)
+@unittest.skipUnless(PREFERENCES_BIN.exists(), f"{PREFERENCES_BIN} not built")
+class PreferencesStartupTest(unittest.TestCase):
+    """The window is constructed without a GSettings schema."""
+
+    def test_window_without_schema(self) -> None:
+        with tempfile.TemporaryDirectory() as empty_dir:
+            exit_code, stderr = _run_app(
+                [],
+                env={"GSETTINGS_SCHEMA_DIR": empty_dir, "XDG_DATA_DIRS": empty_dir},
+            )
+        self.assertIsNone(exit_code, f"app exited with {exit_code}:\n{stderr}")
+        self.assertIn("com.enigmora.LNXDrive.Preferences is not installed", stderr)
+
+
+@unittest.skipUnless(
+    os.environ.get("WAYLAND_DISPLAY") or os.environ.get("DISPLAY"),
+    "no display to open the window on",
+)
+@unittest.skipUnless(_a11y_bus_address(), "no accessibility bus")
+@unittest.skipUnless(PREFERENCES_BIN.exists(), f"{PREFERENCES_BIN} not built")
+class InitialPageTest(unittest.TestCase):
+    """--page opens that preferences page, right away or after onboarding."""
+
+    def start(self, authenticated: bool) -> None:
+        """Run the mock daemon and the app with --page advanced, and wait for
+        the app's window in the accessibility tree."""
+        test_bus = Gio.TestDBus.new(Gio.TestDBusFlags.NONE)
+        test_bus.up()
+        self.addCleanup(test_bus.down)
+        address = test_bus.get_bus_address()
+
+        daemon_args = ["--signal-interval", "999", "--bus-address", address]
+        if authenticated:
+            daemon_args.insert(0, "--authenticated")
+        daemon = subprocess.Popen(
+            [sys.executable, str(MOCK_DAEMON), *daemon_args],
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.DEVNULL,
+        )
+        self.addCleanup(_stop, daemon)
+
+        self._connection = Gio.DBusConnection.new_for_address_sync(
+            address,
+            Gio.DBusConnectionFlags.AUTHENTICATION_CLIENT
+            | Gio.DBusConnectionFlags.MESSAGE_BUS_CONNECTION,
+            None,
+            None,
+        )
+        _wait_for(self._daemon_running, "the mock daemon")
+
+        app_env = dict(os.environ)
+        app_env["DBUS_SESSION_BUS_ADDRESS"] = address
+        app_env["AT_SPI_BUS_ADDRESS"] = _a11y_bus_address() or ""
+        self._app = subprocess.Popen(
+            [str(PREFERENCES_BIN), "--page", "advanced"],
+            env=app_env,
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.DEVNULL,
+        )
+        self.addCleanup(_stop, self._app)
+
+        self._window = _wait_for(self._app_accessible, "the preferences window")
+
+    def _daemon_running(self) -> bool:
+        try:
+            result = self._connection.call_sync(
+                "org.freedesktop.DBus",
+                "/org/freedesktop/DBus",
+                "org.freedesktop.DBus",
+                "NameHasOwner",
+                GLib.Variant("(s)", (BUS_NAME,)),
+                GLib.VariantType.new("(b)"),
+                Gio.DBusCallFlags.NONE,
+                5000,
+                None,
+            )
+        except GLib.Error:
+            return False
+        return result.get_child_value(0).get_boolean()
+
+    def _app_accessible(self) -> Atspi.Accessible | None:
+        desktop = Atspi.get_desktop(0)
+        for i in range(desktop.get_child_count()):
+            app = desktop.get_child_at_index(i)
+            if app is not None and app.get_process_id() == self._app.pid:
+                return app
+        return None
+
+    def showing(self, name: str) -> Atspi.Accessible:
+        """The on-screen widget named `name`, once it is shown."""
+        return _wait_for(lambda: _find_showing(self._window, name), f"{name!r} on screen")
+
+    def complete_auth(self) -> None:
+        """Finish the sign-in as the browser redirect would."""
+        self._connection.call_sync(
+            BUS_NAME,
+            OBJECT_PATH,
+            IFACE_AUTH,
+            "CompleteAuth",
+            GLib.Variant("(ss)", ("mock-code", "mock123")),
+            GLib.VariantType.new("(b)"),
+            Gio.DBusCallFlags.NONE,
+            5000,
+            None,
+        )
+
+    def test_page_when_signed_in(self) -> None:
+        self.start(authenticated=True)
+
+        self.showing(ADVANCED_ROW)
+
+    def test_page_before_sign_in(self) -> None:
+        self.start(authenticated=False)
+
+        _click(self.showing("Sign In"))
+        self.assertIsNone(_find_showing(self._window, ADVANCED_ROW))
+        # Shown once the app listens for the sign-in, depending on whether
+        # a browser could be opened.
+        _wait_for(
+            lambda: _find_showing(self._window, "Open Sign-In Page Again")
+            or _find_showing(self._window, "Copy Link"),
+            "the sign-in link",
+        )
+        self.complete_auth()
+        _click(self.showing("Continue"))
+        _click(self.showing("Start Syncing"))
+
+        self.showing(ADVANCED_ROW)
+
+
+if __name__ == "__main__":
+    unittest.main()
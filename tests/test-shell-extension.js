@@ -501,6 +501,22 @@ if (noDaemonMode) {
             });
         });
 
+        // ----- Test: sync proxy can call PauseUntil -----
+        await runTest('sync proxy can call PauseUntil', async () => {
+            const proxies = await dbusModule.createProxies();
+            assert(proxies !== null, 'proxies should not be null');
+
+            const inOneHour = Math.floor(GLib.get_real_time() / 1000000) + 3600;
+            await new Promise((resolve, reject) => {
+                proxies.sync.PauseUntilRemote(inOneHour, (_, error) => {
+                    if (error)
+                        reject(new Error(`PauseUntil failed: ${error.message}`));
+                    else
+                        resolve();
+                });
+            });
+        });
+
         // ----- Test: sync proxy can call Resume -----
         await runTest('sync proxy can call Resume', async () => {
             const proxies = await dbusModule.createProxies();